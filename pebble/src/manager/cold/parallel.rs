@@ -11,7 +11,7 @@ use alloc::vec::Vec;
 use core::hash::Hash;
 use std::sync::{Arc, Mutex};
 
-use spill_ring::{MpscRing, SpillRing};
+use spill_ring::{MpscRing, PoolError, SpillRing};
 use spout::Spout;
 
 use crate::manager::traits::{CheckpointSerializer, Checkpointable};
@@ -170,7 +170,15 @@ where
         };
 
         if let Some(pool) = self.pool.as_mut() {
-            pool.run(&batch);
+            // A panicking serializer is a bug, not a storage error: re-raise
+            // it on the caller's thread as it would without the pool.
+            match pool.run(&batch) {
+                Ok(()) => {}
+                Err(PoolError::WorkerPanicked { payload, .. }) => {
+                    std::panic::resume_unwind(payload)
+                }
+                Err(err) => panic!("{err}"),
+            }
         }
     }
 
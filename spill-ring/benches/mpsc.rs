@@ -29,7 +29,7 @@ fn mpsc_throughput(c: &mut Criterion) {
                 });
 
                 b.iter(|| {
-                    pool.run(&iterations_per_worker);
+                    pool.run(&iterations_per_worker).unwrap();
                 });
             },
         );
@@ -57,7 +57,7 @@ fn mpsc_full_cycle(c: &mut Criterion) {
                 });
 
                 b.iter(|| {
-                    pool.run(&iterations_per_worker);
+                    pool.run(&iterations_per_worker).unwrap();
                 });
                 // Note: drain happens once after all iterations, not per-iteration.
                 // This isolates push throughput from drain cost.
@@ -95,7 +95,7 @@ fn mpsc_vs_single(c: &mut Criterion) {
         });
 
         b.iter(|| {
-            pool.run(&per_worker);
+            pool.run(&per_worker).unwrap();
         });
     });
 
@@ -123,7 +123,7 @@ fn mpsc_scaling(c: &mut Criterion) {
                 });
 
                 b.iter(|| {
-                    pool.run(&per_worker);
+                    pool.run(&per_worker).unwrap();
                 });
             },
         );
@@ -149,7 +149,7 @@ fn spout_overhead(c: &mut Criterion) {
         });
 
         b.iter(|| {
-            pool.run(&iterations_per_worker);
+            pool.run(&iterations_per_worker).unwrap();
         });
     });
 
@@ -164,7 +164,7 @@ fn spout_overhead(c: &mut Criterion) {
                 });
 
         b.iter(|| {
-            pool.run(&iterations_per_worker);
+            pool.run(&iterations_per_worker).unwrap();
         });
     });
 
@@ -0,0 +1,148 @@
+//! Overflow policy benchmarks.
+//!
+//! Every iteration pushes far more items than the ring holds, so the
+//! overflow path dominates. Rings are reused across iterations via
+//! `clear()` (which flushes to the sink outside the overflow path).
+//!
+//! Two sinks are used: `DropSpout` isolates the ring's own cost, and
+//! `VecSink` appends to a pre-allocated `Vec`, with `send_all` going
+//! through a single `extend` to show what batching saves per call.
+
+use std::convert::Infallible;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use spill_ring::{OverflowPolicy, Overwrite, SpillBatch, SpillNewest, SpillOldest, SpillRing};
+use spout::{DropSpout, Spout};
+
+const ITERATIONS: u64 = 10_000;
+
+/// Appends to a reusable buffer; cleared by the benchmark each iteration.
+struct VecSink(Vec<u64>);
+
+impl VecSink {
+    fn new() -> Self {
+        Self(Vec::with_capacity(ITERATIONS as usize))
+    }
+}
+
+impl Spout<u64> for VecSink {
+    type Error = Infallible;
+
+    #[inline]
+    fn send(&mut self, item: u64) -> Result<(), Infallible> {
+        self.0.push(item);
+        Ok(())
+    }
+
+    #[inline]
+    fn send_all(&mut self, items: impl Iterator<Item = u64>) -> Result<(), Infallible> {
+        self.0.extend(items);
+        Ok(())
+    }
+}
+
+fn bench_drop<P: OverflowPolicy>(b: &mut criterion::Bencher<'_>, policy: P) {
+    let mut ring = SpillRing::<u64, 64>::builder().policy(policy).build();
+    b.iter(|| {
+        ring.clear();
+        for i in 0..ITERATIONS {
+            ring.push_mut(black_box(i));
+        }
+    });
+}
+
+fn bench_vec<P: OverflowPolicy>(b: &mut criterion::Bencher<'_>, policy: P) {
+    let mut ring = SpillRing::<u64, 64>::builder()
+        .sink(VecSink::new())
+        .policy(policy)
+        .build();
+    b.iter(|| {
+        ring.clear();
+        ring.sink_mut().0.clear();
+        for i in 0..ITERATIONS {
+            ring.push_mut(black_box(i));
+        }
+    });
+}
+
+/// All policies against a sink that discards.
+fn policies_drop_sink(c: &mut Criterion) {
+    let mut group = c.benchmark_group("policy/drop_sink");
+    group.throughput(Throughput::Elements(ITERATIONS));
+
+    group.bench_function("spill_oldest", |b| bench_drop(b, SpillOldest));
+    group.bench_function("spill_newest", |b| bench_drop(b, SpillNewest));
+    group.bench_function("spill_batch_16", |b| bench_drop(b, SpillBatch::<16>));
+    group.bench_function("overwrite", |b| bench_drop(b, Overwrite));
+
+    group.finish();
+}
+
+/// All policies against a sink that stores evicted items.
+fn policies_vec_sink(c: &mut Criterion) {
+    let mut group = c.benchmark_group("policy/vec_sink");
+    group.throughput(Throughput::Elements(ITERATIONS));
+
+    group.bench_function("spill_oldest", |b| bench_vec(b, SpillOldest));
+    group.bench_function("spill_newest", |b| bench_vec(b, SpillNewest));
+    group.bench_function("spill_batch_16", |b| bench_vec(b, SpillBatch::<16>));
+    group.bench_function("overwrite", |b| bench_vec(b, Overwrite));
+
+    group.finish();
+}
+
+/// `SpillBatch` size sweep. Batch 1 matches `SpillOldest`.
+fn batch_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("policy/batch_size");
+    group.throughput(Throughput::Elements(ITERATIONS));
+
+    for k in [1, 4, 16, 64] {
+        group.bench_with_input(BenchmarkId::from_parameter(k), &k, |b, &k| match k {
+            1 => bench_vec(b, SpillBatch::<1>),
+            4 => bench_vec(b, SpillBatch::<4>),
+            16 => bench_vec(b, SpillBatch::<16>),
+            64 => bench_vec(b, SpillBatch::<64>),
+            _ => unreachable!(),
+        });
+    }
+
+    group.finish();
+}
+
+/// No-overflow push for each policy — the policy must not cost anything
+/// until the ring is full.
+fn policies_no_overflow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("policy/no_overflow");
+    group.throughput(Throughput::Elements(64));
+
+    fn run<P: OverflowPolicy>(b: &mut criterion::Bencher<'_>, policy: P) {
+        let mut ring = SpillRing::<u64, 64>::builder()
+            .sink(DropSpout)
+            .policy(policy)
+            .build();
+        b.iter(|| {
+            ring.clear();
+            for i in 0..64u64 {
+                ring.push_mut(black_box(i));
+            }
+        });
+    }
+
+    group.bench_function("spill_oldest", |b| run(b, SpillOldest));
+    group.bench_function("spill_newest", |b| run(b, SpillNewest));
+    group.bench_function("spill_batch_16", |b| run(b, SpillBatch::<16>));
+    group.bench_function("overwrite", |b| run(b, Overwrite));
+
+    group.finish();
+}
+
+criterion_group!(
+    policy_benches,
+    policies_drop_sink,
+    policies_vec_sink,
+    batch_sizes,
+    policies_no_overflow,
+);
+
+criterion_main!(policy_benches);
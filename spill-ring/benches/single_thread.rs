@@ -7,7 +7,7 @@
 //! via `clear()` unless noted otherwise.
 
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
-use spill_ring::SpillRing;
+use spill_ring::{Counters, NoStats, SpillRing};
 use spout::{CollectSpout, DropSpout};
 use std::collections::VecDeque;
 use std::hint::black_box;
@@ -581,6 +581,40 @@ fn eviction_overhead(c: &mut Criterion) {
     group.finish();
 }
 
+/// Cost of the `Counters` stats layer against the default `NoStats`.
+fn stats_overhead(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single/stats_overhead");
+    let iterations = 10_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    {
+        let mut ring = SpillRing::<u64, 64>::builder().stats::<NoStats>().build();
+        group.bench_function("no_stats", |b| {
+            b.iter(|| {
+                ring.clear();
+                for i in 0..iterations {
+                    ring.push_mut(black_box(i));
+                }
+            })
+        });
+    }
+
+    {
+        let mut ring = SpillRing::<u64, 64>::builder().stats::<Counters>().build();
+        group.bench_function("counters", |b| {
+            b.iter(|| {
+                ring.clear();
+                for i in 0..iterations {
+                    ring.push_mut(black_box(i));
+                }
+            })
+        });
+        black_box(ring.stats());
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     throughput_benches,
     push_mut_drop_sink,
@@ -606,6 +640,7 @@ criterion_group!(
     vs_vecdeque_interleaved,
     cache_effects,
     eviction_overhead,
+    stats_overhead,
 );
 
 criterion_main!(throughput_benches, latency_benches, comparison_benches);
@@ -0,0 +1,517 @@
+//! SPSC (Single-Producer, Single-Consumer) benchmarks.
+//!
+//! Mirrors `single_thread.rs` for `SpscRing`, so each group can be compared
+//! with its `single/...` counterpart to see the cost of the atomic indices
+//! and slot stamps. Unless noted, producer and consumer run on the same
+//! thread; the `spsc/concurrent` group runs the consumer on its own thread.
+//! There is no peek benchmark because the consumer cannot hand out
+//! references to items the producer may evict.
+//!
+//! Rings are reused across iterations via `flush()` unless noted otherwise.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use spill_ring::{SpillRing, SpscConsumer, SpscProducer, SpscRing};
+use spout::{CollectSpout, DropSpout};
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// Push `count` items, reusing one ring across iterations.
+fn bench_push<const N: usize>(b: &mut criterion::Bencher<'_>, count: u64) {
+    let (mut producer, _consumer) = SpscRing::<u64, N>::new();
+    b.iter(|| {
+        producer.flush();
+        for i in 0..count {
+            producer.push(black_box(i));
+        }
+    });
+}
+
+/// Push throughput with DropSpout (items discarded on eviction).
+fn push_drop_sink(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/push/drop_sink");
+
+    for capacity in [16, 64, 256, 1024] {
+        group.throughput(Throughput::Elements(10_000));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(capacity),
+            &capacity,
+            |b, &cap| match cap {
+                16 => bench_push::<16>(b, 10_000),
+                64 => bench_push::<64>(b, 10_000),
+                256 => bench_push::<256>(b, 10_000),
+                1024 => bench_push::<1024>(b, 10_000),
+                _ => unreachable!(),
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_push_collect<const N: usize>(b: &mut criterion::Bencher<'_>) {
+    b.iter(|| {
+        let (mut producer, _consumer) = SpscRing::<u64, N>::with_sink(CollectSpout::new());
+        for i in 0..10_000u64 {
+            producer.push(black_box(i));
+        }
+    });
+}
+
+/// Push throughput with CollectSpout (items collected on eviction).
+///
+/// The ring and sink are recreated each iteration, so allocation is
+/// included in the measurement.
+fn push_collect_sink(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/push/collect_sink");
+
+    for capacity in [16, 64, 256] {
+        group.throughput(Throughput::Elements(10_000));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(capacity),
+            &capacity,
+            |b, &cap| match cap {
+                16 => bench_push_collect::<16>(b),
+                64 => bench_push_collect::<64>(b),
+                256 => bench_push_collect::<256>(b),
+                _ => unreachable!(),
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_pop<const N: usize>(b: &mut criterion::Bencher<'_>) {
+    let (mut producer, mut consumer) = SpscRing::<u64, N>::new();
+    b.iter(|| {
+        for i in 0..N as u64 {
+            producer.push(i);
+        }
+        for _ in 0..N {
+            black_box(consumer.pop());
+        }
+    });
+}
+
+/// Pop throughput. Each iteration refills the ring then pops all items.
+fn pop_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/pop");
+
+    for capacity in [16, 64, 256, 1024] {
+        group.throughput(Throughput::Elements(capacity as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(capacity),
+            &capacity,
+            |b, &cap| match cap {
+                16 => bench_pop::<16>(b),
+                64 => bench_pop::<64>(b),
+                256 => bench_pop::<256>(b),
+                1024 => bench_pop::<1024>(b),
+                _ => unreachable!(),
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_interleaved<const N: usize>(b: &mut criterion::Bencher<'_>) {
+    let (mut producer, mut consumer) = SpscRing::<u64, N>::new();
+    b.iter(|| {
+        producer.flush();
+        for i in 0..10_000u64 {
+            producer.push(black_box(i));
+            if i % 2 == 0 {
+                black_box(consumer.pop());
+            }
+        }
+    });
+}
+
+/// Push+pop interleaved on one thread.
+fn push_pop_interleaved(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/push_pop");
+
+    for capacity in [16, 64, 256] {
+        group.throughput(Throughput::Elements(10_000));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(capacity),
+            &capacity,
+            |b, &cap| match cap {
+                16 => bench_interleaved::<16>(b),
+                64 => bench_interleaved::<64>(b),
+                256 => bench_interleaved::<256>(b),
+                _ => unreachable!(),
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Impact of item size on push throughput.
+fn item_size_impact(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/item_size");
+    group.throughput(Throughput::Elements(10_000));
+
+    {
+        let (mut producer, _consumer) = SpscRing::<u64, 64>::new();
+        group.bench_function("u64_8bytes", |b| {
+            b.iter(|| {
+                producer.flush();
+                for i in 0..10_000u64 {
+                    producer.push(black_box(i));
+                }
+            })
+        });
+    }
+
+    {
+        let (mut producer, _consumer) = SpscRing::<[u64; 4], 64>::new();
+        group.bench_function("array_32bytes", |b| {
+            b.iter(|| {
+                producer.flush();
+                for i in 0..10_000u64 {
+                    producer.push(black_box([i, i, i, i]));
+                }
+            })
+        });
+    }
+
+    {
+        let (mut producer, _consumer) = SpscRing::<[u64; 16], 64>::new();
+        group.bench_function("array_128bytes", |b| {
+            b.iter(|| {
+                producer.flush();
+                for i in 0..10_000u64 {
+                    producer.push(black_box([i; 16]));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Single push latency when buffer has room.
+fn push_latency_not_full(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/push_latency");
+
+    group.bench_function("not_full", |b| {
+        let (mut producer, mut consumer) = SpscRing::<u64, 256>::new();
+        let mut i = 0u64;
+        b.iter(|| {
+            producer.push(black_box(i));
+            i = i.wrapping_add(1);
+            if producer.len() > 128 {
+                let _ = consumer.pop();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+/// Single push latency when buffer is full (causes eviction).
+fn push_latency_full(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/push_latency");
+
+    group.bench_function("full_evicting", |b| {
+        let (mut producer, _consumer) = SpscRing::<u64, 64>::new();
+        for i in 0..64u64 {
+            producer.push(i);
+        }
+        let mut i = 64u64;
+        b.iter(|| {
+            producer.push(black_box(i));
+            i = i.wrapping_add(1);
+        })
+    });
+
+    group.finish();
+}
+
+/// Single pop latency.
+fn pop_latency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/pop_latency");
+
+    group.bench_function("single_pop", |b| {
+        let (mut producer, mut consumer) = SpscRing::<u64, 256>::new();
+        let mut i = 0u64;
+        b.iter(|| {
+            if consumer.len() < 128 {
+                for _ in 0..64 {
+                    producer.push(i);
+                    i = i.wrapping_add(1);
+                }
+            }
+            black_box(consumer.pop())
+        })
+    });
+
+    group.finish();
+}
+
+/// Drain iterator latency. Each iteration refills and drains.
+fn drain_latency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/drain_latency");
+
+    group.bench_function("drain_64_items", |b| {
+        let (mut producer, mut consumer) = SpscRing::<u64, 64>::new();
+        b.iter(|| {
+            for i in 0..64u64 {
+                producer.push(i);
+            }
+            let sum: u64 = consumer.drain().sum();
+            black_box(sum)
+        })
+    });
+
+    group.finish();
+}
+
+/// Push comparison: SpscRing vs SpillRing vs VecDeque.
+fn vs_spill_ring_push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/vs/push");
+    let iterations = 10_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    {
+        let (mut producer, _consumer) = SpscRing::<u64, 64>::new();
+        group.bench_function("spsc_ring", |b| {
+            b.iter(|| {
+                producer.flush();
+                for i in 0..iterations {
+                    producer.push(black_box(i));
+                }
+            })
+        });
+    }
+
+    {
+        let mut ring: SpillRing<u64, 64> = SpillRing::new();
+        group.bench_function("spill_ring", |b| {
+            b.iter(|| {
+                ring.clear();
+                for i in 0..iterations {
+                    ring.push_mut(black_box(i));
+                }
+            })
+        });
+    }
+
+    {
+        let mut deque: VecDeque<u64> = VecDeque::with_capacity(64);
+        group.bench_function("vecdeque_evict", |b| {
+            b.iter(|| {
+                deque.clear();
+                for i in 0..iterations {
+                    if deque.len() >= 64 {
+                        black_box(deque.pop_front());
+                    }
+                    deque.push_back(black_box(i));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Pop comparison: SpscRing vs SpillRing.
+fn vs_spill_ring_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/vs/pop");
+    let size = 1000u64;
+    group.throughput(Throughput::Elements(size));
+
+    {
+        let (mut producer, mut consumer) = SpscRing::<u64, 1024>::new();
+        group.bench_function("spsc_ring", |b| {
+            b.iter(|| {
+                for i in 0..size {
+                    producer.push(i);
+                }
+                for _ in 0..size {
+                    black_box(consumer.pop());
+                }
+            })
+        });
+    }
+
+    {
+        let mut ring: SpillRing<u64, 1024> = SpillRing::new();
+        group.bench_function("spill_ring", |b| {
+            b.iter(|| {
+                for i in 0..size {
+                    ring.push_mut(i);
+                }
+                for _ in 0..size {
+                    black_box(ring.pop_mut());
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Interleaved push/pop comparison.
+fn vs_spill_ring_interleaved(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/vs/interleaved");
+    let iterations = 10_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    {
+        let (mut producer, mut consumer) = SpscRing::<u64, 64>::new();
+        group.bench_function("spsc_ring", |b| {
+            b.iter(|| {
+                producer.flush();
+                for i in 0..iterations {
+                    producer.push(black_box(i));
+                    if i % 2 == 0 {
+                        black_box(consumer.pop());
+                    }
+                }
+            })
+        });
+    }
+
+    {
+        let mut ring: SpillRing<u64, 64> = SpillRing::new();
+        group.bench_function("spill_ring", |b| {
+            b.iter(|| {
+                ring.clear();
+                for i in 0..iterations {
+                    ring.push_mut(black_box(i));
+                    if i % 2 == 0 {
+                        black_box(ring.pop_mut());
+                    }
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Cache effects — small vs large capacity.
+fn cache_effects(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/cache_effects");
+    let iterations = 50_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    group.bench_function("cap_16_L1", |b| bench_push::<16>(b, iterations));
+    group.bench_function("cap_4096_L2", |b| bench_push::<4096>(b, iterations));
+    group.bench_function("cap_65536_L3", |b| bench_push::<65536>(b, iterations));
+
+    group.finish();
+}
+
+/// Eviction cost isolation — high eviction vs no eviction.
+fn eviction_overhead(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/eviction_overhead");
+    let iterations = 10_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    group.bench_function("cap_8_high_eviction", |b| bench_push::<8>(b, iterations));
+    group.bench_function("cap_16384_no_eviction", |b| {
+        bench_push::<16384>(b, iterations)
+    });
+
+    group.finish();
+}
+
+/// Run `count` pushes against a consumer on another thread.
+///
+/// With eviction the consumer may see fewer than `count` items, so it stops
+/// once the producer is done and the ring is empty.
+fn run_concurrent<const N: usize>(
+    producer: &mut SpscProducer<u64, N, DropSpout>,
+    consumer: SpscConsumer<u64, N>,
+    count: u64,
+) -> SpscConsumer<u64, N> {
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        let handle = s.spawn(|| {
+            let mut consumer = consumer;
+            loop {
+                match consumer.pop() {
+                    Some(item) => {
+                        black_box(item);
+                    }
+                    None if done.load(Ordering::Acquire) && consumer.is_empty() => break,
+                    None => thread::yield_now(),
+                }
+            }
+            consumer
+        });
+        for i in 0..count {
+            producer.push(black_box(i));
+        }
+        done.store(true, Ordering::Release);
+        handle.join().unwrap()
+    })
+}
+
+/// Producer and live consumer on separate threads.
+fn concurrent_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc/concurrent");
+    let iterations = 100_000u64;
+    group.throughput(Throughput::Elements(iterations));
+
+    for capacity in [64, 1024] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(capacity),
+            &capacity,
+            |b, &cap| match cap {
+                64 => {
+                    let (mut producer, consumer) = SpscRing::<u64, 64>::new();
+                    let mut consumer = Some(consumer);
+                    b.iter(|| {
+                        producer.flush();
+                        let c = consumer.take().unwrap();
+                        consumer = Some(run_concurrent(&mut producer, c, iterations));
+                    });
+                }
+                1024 => {
+                    let (mut producer, consumer) = SpscRing::<u64, 1024>::new();
+                    let mut consumer = Some(consumer);
+                    b.iter(|| {
+                        producer.flush();
+                        let c = consumer.take().unwrap();
+                        consumer = Some(run_concurrent(&mut producer, c, iterations));
+                    });
+                }
+                _ => unreachable!(),
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    throughput_benches,
+    push_drop_sink,
+    push_collect_sink,
+    pop_throughput,
+    push_pop_interleaved,
+    item_size_impact,
+    concurrent_throughput,
+);
+
+criterion_group!(
+    latency_benches,
+    push_latency_not_full,
+    push_latency_full,
+    pop_latency,
+    drain_latency,
+);
+
+criterion_group!(
+    comparison_benches,
+    vs_spill_ring_push,
+    vs_spill_ring_pop,
+    vs_spill_ring_interleaved,
+    cache_effects,
+    eviction_overhead,
+);
+
+criterion_main!(throughput_benches, latency_benches, comparison_benches);
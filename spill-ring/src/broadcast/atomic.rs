@@ -0,0 +1,454 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use super::{Lagged, ReaderId, SlowReader};
+use crate::{index::CachePadded, ring::MAX_CAPACITY, spsc::backoff};
+use spout::{DropSpout, Spout};
+
+/// Stamp of a slot whose item is being spilled.
+const INVALID: usize = usize::MAX;
+
+/// One slot with a position stamp and a count of readers cloning from it.
+///
+/// A reader pins the slot (increments `readers`), then checks that the
+/// stamp still names the position it wants. The producer spilling a slot
+/// first moves `head` past it and invalidates the stamp, then waits for
+/// the pin count to drop to zero before moving the item out. With all four
+/// operations `SeqCst`, either the reader sees the invalid stamp and backs
+/// off, or the producer sees the pin and waits.
+struct Slot<T> {
+    stamp: AtomicUsize,
+    readers: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A reader's pin on a slot, released on drop so that a panicking
+/// `T::clone` cannot leave the producer waiting on the slot forever.
+struct SlotPin<'a>(&'a AtomicUsize);
+
+impl<'a> SlotPin<'a> {
+    #[inline]
+    fn new(readers: &'a AtomicUsize) -> Self {
+        readers.fetch_add(1, Ordering::SeqCst);
+        Self(readers)
+    }
+}
+
+impl Drop for SlotPin<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Buffer and indices shared by the producer and its readers.
+struct Shared<T, const N: usize> {
+    /// Position of the oldest item. Only written by the producer.
+    head: CachePadded<AtomicUsize>,
+    /// Position of the next push. Only written by the producer.
+    tail: CachePadded<AtomicUsize>,
+    producer_closed: AtomicBool,
+    slots: Box<[Slot<T>]>,
+}
+
+// Readers clone items in place from several threads at once.
+unsafe impl<T: Send + Sync, const N: usize> Send for Shared<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for Shared<T, N> {}
+
+impl<T, const N: usize> Shared<T, N> {
+    fn new() -> Self {
+        Self {
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+            producer_closed: AtomicBool::new(false),
+            slots: (0..N)
+                .map(|_| Slot {
+                    stamp: AtomicUsize::new(INVALID),
+                    readers: AtomicUsize::new(0),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+        }
+    }
+
+    #[inline]
+    fn slot(&self, pos: usize) -> &Slot<T> {
+        &self.slots[pos & (N - 1)]
+    }
+}
+
+impl<T, const N: usize> Drop for Shared<T, N> {
+    fn drop(&mut self) {
+        let head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        let mut pos = head;
+        while pos != tail {
+            let slot = &mut self.slots[pos & (N - 1)];
+            // SAFETY: every handle is gone, and `head..tail` are initialized.
+            unsafe { slot.value.get_mut().assume_init_drop() };
+            pos = pos.wrapping_add(1);
+        }
+    }
+}
+
+/// Per-reader state, shared between a reader and the producer.
+struct ReaderState {
+    id: usize,
+    /// Next position this reader will read. Only written by the reader.
+    cursor: CachePadded<AtomicUsize>,
+    dropped: AtomicBool,
+}
+
+/// Lock-free broadcast ring: one producer, any number of readers.
+///
+/// [`AtomicBroadcast::new`] and [`AtomicBroadcast::with_sink`] return a
+/// [`BroadcastProducer`]; each call to
+/// [`BroadcastProducer::subscribe`] adds a [`BroadcastReader`] with its own
+/// cursor that can be sent to another thread. Readers clone items in place,
+/// so `T` must be `Clone + Send + Sync` to cross threads.
+///
+/// `N` must be a power of two no larger than 2^20, as for
+/// [`SpillRing`](crate::SpillRing).
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::AtomicBroadcast;
+/// use std::thread;
+///
+/// let mut producer = AtomicBroadcast::<u64, 1024>::new();
+/// let readers: Vec<_> = (0..2).map(|_| producer.subscribe()).collect();
+///
+/// for i in 0..100 {
+///     producer.push(i);
+/// }
+/// drop(producer);
+///
+/// let sums: Vec<u64> = readers
+///     .into_iter()
+///     .map(|mut reader| {
+///         thread::spawn(move || {
+///             let mut sum = 0;
+///             while let Ok(Some(v)) = reader.recv() {
+///                 sum += v;
+///             }
+///             sum
+///         })
+///     })
+///     .map(|h| h.join().unwrap())
+///     .collect();
+/// assert_eq!(sums, [4950, 4950]);
+/// ```
+pub struct AtomicBroadcast<T, const N: usize> {
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T, const N: usize> AtomicBroadcast<T, N> {
+    /// Create a ring whose spilled items are dropped.
+    #[allow(clippy::new_ret_no_self)]
+    #[must_use]
+    pub fn new() -> BroadcastProducer<T, N> {
+        Self::with_sink(DropSpout)
+    }
+
+    /// Create a ring whose spilled items are sent to `sink`.
+    ///
+    /// The sink belongs to the producer.
+    #[must_use]
+    pub fn with_sink<S: Spout<T, Error = core::convert::Infallible>>(
+        sink: S,
+    ) -> BroadcastProducer<T, N, S> {
+        const { assert!(N > 0, "capacity must be > 0") };
+        const { assert!(N.is_power_of_two(), "capacity must be power of two") };
+        const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+
+        BroadcastProducer {
+            shared: Arc::new(Shared::new()),
+            head: 0,
+            tail: 0,
+            readers: Vec::new(),
+            next_id: 0,
+            sink,
+        }
+    }
+}
+
+/// Producer half of an [`AtomicBroadcast`].
+///
+/// When dropped, buffered items stay in the ring for the readers, and the
+/// sink is flushed.
+pub struct BroadcastProducer<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+> {
+    shared: Arc<Shared<T, N>>,
+    /// Local copies of the shared indices; only this half writes them.
+    head: usize,
+    tail: usize,
+    readers: Vec<Arc<ReaderState>>,
+    next_id: usize,
+    sink: S,
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> BroadcastProducer<T, N, S> {
+    /// Add a reader. It sees items pushed from now on.
+    pub fn subscribe(&mut self) -> BroadcastReader<T, N> {
+        let state = Arc::new(ReaderState {
+            id: self.next_id,
+            cursor: CachePadded(AtomicUsize::new(self.tail)),
+            dropped: AtomicBool::new(false),
+        });
+        self.next_id += 1;
+        self.readers.push(Arc::clone(&state));
+        BroadcastReader {
+            shared: Arc::clone(&self.shared),
+            state,
+        }
+    }
+
+    /// Number of live readers.
+    #[must_use]
+    pub fn num_readers(&self) -> usize {
+        self.readers
+            .iter()
+            .filter(|r| !r.dropped.load(Ordering::Acquire))
+            .count()
+    }
+
+    /// Push an item. If a reader has not read the oldest slot and the ring
+    /// is full, that slot is spilled to the sink and lagging readers skip it.
+    ///
+    /// Waits briefly if a reader is in the middle of cloning the spilled
+    /// item.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        if self.tail.wrapping_sub(self.head) >= N {
+            self.release_read();
+            if self.tail.wrapping_sub(self.head) >= N {
+                self.spill_oldest();
+            }
+        }
+        self.write(item);
+    }
+
+    /// Push an item, or report the slowest reader if the ring is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlowReader`] with the item if a reader has not read the
+    /// oldest slot and the ring is full.
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), SlowReader<T>> {
+        if self.tail.wrapping_sub(self.head) >= N {
+            self.release_read();
+            if self.tail.wrapping_sub(self.head) >= N {
+                let (reader, pos) = self.slowest().expect("full ring has a reader");
+                return Err(SlowReader {
+                    item,
+                    reader,
+                    unread: self.tail.wrapping_sub(pos),
+                });
+            }
+        }
+        self.write(item);
+        Ok(())
+    }
+
+    /// Number of items held in the ring, including items every reader has
+    /// read but that have not been released yet.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.wrapping_sub(self.head)
+    }
+
+    /// True if the ring holds no items.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Reference to the spout.
+    #[inline]
+    #[must_use]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable reference to the spout.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// The live reader furthest behind, and its position. Forgets readers
+    /// that have been dropped.
+    fn slowest(&mut self) -> Option<(ReaderId, usize)> {
+        self.readers.retain(|r| !r.dropped.load(Ordering::Acquire));
+        let tail = self.tail;
+        self.readers
+            .iter()
+            .map(|r| (ReaderId(r.id), r.cursor.load(Ordering::Acquire)))
+            .max_by_key(|&(_, pos)| tail.wrapping_sub(pos))
+    }
+
+    /// Drop items every reader has moved past.
+    fn release_read(&mut self) {
+        let min = self.slowest().map_or(self.tail, |(_, pos)| pos);
+        // A reader that has not noticed a spill yet sits behind `head`;
+        // it has read none of the items left, so there is nothing to free.
+        if (min.wrapping_sub(self.head) as isize) <= 0 {
+            return;
+        }
+        let start = self.head;
+        self.head = min;
+        self.shared.head.store(min, Ordering::SeqCst);
+        let mut pos = start;
+        while pos != min {
+            // SAFETY: every reader's cursor is past `pos`, so none will
+            // touch it again, and `head..tail` were initialized.
+            unsafe { (*self.shared.slot(pos).value.get()).assume_init_drop() };
+            pos = pos.wrapping_add(1);
+        }
+    }
+
+    /// Spill the oldest item. Readers that had not read it notice on their
+    /// next read and skip ahead.
+    fn spill_oldest(&mut self) {
+        let pos = self.head;
+        self.head = pos.wrapping_add(1);
+        self.shared.head.store(self.head, Ordering::SeqCst);
+        let slot = self.shared.slot(pos);
+        slot.stamp.store(INVALID, Ordering::SeqCst);
+        let mut step = 0;
+        while slot.readers.load(Ordering::SeqCst) != 0 {
+            backoff(&mut step);
+        }
+        // SAFETY: the stamp no longer matches any position, and no reader
+        // is pinned, so nobody else will read this slot until it is
+        // rewritten.
+        let item = unsafe { (*slot.value.get()).assume_init_read() };
+        let _ = self.sink.send(item);
+    }
+
+    #[inline]
+    fn write(&mut self, item: T) {
+        let tail = self.tail;
+        let slot = self.shared.slot(tail);
+        // SAFETY: `tail - head < N`, so the slot's previous item has been
+        // released or spilled, and no reader will look at it until the
+        // stamp names `tail`.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.stamp.store(tail, Ordering::Release);
+        self.tail = tail.wrapping_add(1);
+        self.shared.tail.store(self.tail, Ordering::Release);
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
+    for BroadcastProducer<T, N, S>
+{
+    fn drop(&mut self) {
+        self.shared.producer_closed.store(true, Ordering::Release);
+        let _ = self.sink.flush();
+    }
+}
+
+/// One reader of an [`AtomicBroadcast`].
+///
+/// Created by [`BroadcastProducer::subscribe`]. Dropping it stops the
+/// ring from holding items on its behalf.
+pub struct BroadcastReader<T, const N: usize> {
+    shared: Arc<Shared<T, N>>,
+    state: Arc<ReaderState>,
+}
+
+impl<T: Clone, const N: usize> BroadcastReader<T, N> {
+    /// Read the next item.
+    ///
+    /// Returns `Ok(None)` when this reader has caught up with the producer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lagged`] once after items this reader had not read were
+    /// spilled.
+    pub fn recv(&mut self) -> Result<Option<T>, Lagged> {
+        let pos = self.state.cursor.load(Ordering::Relaxed);
+        if pos == self.shared.tail.load(Ordering::Acquire) {
+            return Ok(None);
+        }
+        loop {
+            let head = self.shared.head.load(Ordering::SeqCst);
+            let behind = head.wrapping_sub(pos);
+            if behind as isize > 0 {
+                self.state.cursor.store(head, Ordering::Release);
+                return Err(Lagged { missed: behind });
+            }
+            let slot = self.shared.slot(pos);
+            let pin = SlotPin::new(&slot.readers);
+            if slot.stamp.load(Ordering::SeqCst) == pos {
+                // SAFETY: the slot is pinned and its stamp names `pos`, so
+                // the producer will not move the item out until we unpin.
+                let item = unsafe { (*slot.value.get()).assume_init_ref() }.clone();
+                drop(pin);
+                self.state
+                    .cursor
+                    .store(pos.wrapping_add(1), Ordering::Release);
+                return Ok(Some(item));
+            }
+            // Spilled under us; `head` has moved past `pos`.
+        }
+    }
+}
+
+impl<T, const N: usize> BroadcastReader<T, N> {
+    /// This reader's id, as reported in [`SlowReader`].
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> ReaderId {
+        ReaderId(self.state.id)
+    }
+
+    /// Number of items this reader has yet to read, including any that
+    /// will be reported as missed.
+    #[must_use]
+    pub fn unread(&self) -> usize {
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(self.state.cursor.load(Ordering::Relaxed))
+    }
+
+    /// True if the producer has been dropped.
+    #[inline]
+    #[must_use]
+    pub fn is_producer_closed(&self) -> bool {
+        self.shared.producer_closed.load(Ordering::Acquire)
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Drop for BroadcastReader<T, N> {
+    fn drop(&mut self) {
+        self.state.dropped.store(true, Ordering::Release);
+    }
+}
@@ -0,0 +1,426 @@
+//! Broadcast (single-producer, multi-reader) ring buffers.
+//!
+//! Every reader sees every item: reads clone, and each reader keeps its
+//! own cursor, disruptor style. Slots are freed once all readers have
+//! moved past them. When the ring is full because a reader is behind, the
+//! producer has two options:
+//!
+//! - `push` spills the oldest slot to the sink. Readers that had not read
+//!   it skip ahead and get [`Lagged`] with the number of missed items on
+//!   their next read.
+//! - `try_push` leaves the ring alone and returns [`SlowReader`], naming
+//!   the reader that holds it up.
+//!
+//! [`BroadcastRing`] is single-threaded; readers are [`ReaderId`]s into it.
+//! [`AtomicBroadcast`] splits into a [`BroadcastProducer`] and any number
+//! of [`BroadcastReader`]s that can live on other threads.
+//!
+//! # Example
+//!
+//! ```
+//! use spill_ring::{BroadcastRing, Lagged};
+//! use spout::CollectSpout;
+//!
+//! let mut ring = BroadcastRing::<u32, 4, _>::with_sink(CollectSpout::new());
+//! let dashboard = ring.subscribe();
+//! let persister = ring.subscribe();
+//!
+//! for i in 0..4 {
+//!     ring.push(i);
+//! }
+//! assert_eq!(ring.recv(dashboard), Ok(Some(0)));
+//! assert_eq!(ring.recv(persister), Ok(Some(0)));
+//!
+//! // Both readers are behind; 1 is spilled and they skip it.
+//! ring.push(4);
+//! ring.push(5);
+//! assert_eq!(ring.sink().items(), [1]);
+//! assert_eq!(ring.recv(dashboard), Err(Lagged { missed: 1 }));
+//! assert_eq!(ring.recv(dashboard), Ok(Some(2)));
+//! ```
+
+extern crate alloc;
+
+mod atomic;
+
+pub use atomic::{AtomicBroadcast, BroadcastProducer, BroadcastReader};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ring::{MAX_CAPACITY, Slot};
+use spout::{DropSpout, Spout};
+
+/// Identifies one reader of a broadcast ring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ReaderId(pub(crate) usize);
+
+impl ReaderId {
+    /// Numeric id, unique within one ring.
+    #[inline]
+    #[must_use]
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+/// Returned by a read when the producer spilled items this reader had not
+/// read yet.
+///
+/// The reader's cursor has already moved to the oldest item still in the
+/// ring; the next read continues from there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged {
+    /// Number of items this reader skipped.
+    pub missed: usize,
+}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reader lagged and missed {} items", self.missed)
+    }
+}
+
+impl core::error::Error for Lagged {}
+
+/// Returned by `try_push` when a reader has not read the oldest slot.
+///
+/// The item is handed back unchanged.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SlowReader<T> {
+    /// The item that was not pushed.
+    pub item: T,
+    /// The slowest reader.
+    pub reader: ReaderId,
+    /// Items that reader has yet to read.
+    pub unread: usize,
+}
+
+impl<T> SlowReader<T> {
+    /// Extract the item that failed to push.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T> fmt::Debug for SlowReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlowReader")
+            .field("reader", &self.reader)
+            .field("unread", &self.unread)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SlowReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "reader {} is {} items behind",
+            self.reader.0, self.unread
+        )
+    }
+}
+
+impl<T> core::error::Error for SlowReader<T> {}
+
+/// Per-reader state of a [`BroadcastRing`].
+struct Cursor {
+    pos: usize,
+    missed: usize,
+}
+
+/// Single-threaded broadcast ring.
+///
+/// The producer and all readers go through `&mut self`; readers are
+/// identified by the [`ReaderId`] returned from
+/// [`subscribe`](Self::subscribe). With no readers, old items are dropped
+/// as soon as room is needed.
+///
+/// On drop, items some reader has not read yet are flushed to the sink.
+pub struct BroadcastRing<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+> {
+    /// Oldest live position.
+    head: usize,
+    /// Next position to write.
+    tail: usize,
+    buffer: [Slot<T>; N],
+    readers: Vec<Option<Cursor>>,
+    sink: S,
+}
+
+impl<T, const N: usize> BroadcastRing<T, N, DropSpout> {
+    /// Create a ring whose spilled items are dropped.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_sink(DropSpout)
+    }
+}
+
+impl<T, const N: usize> Default for BroadcastRing<T, N, DropSpout> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> BroadcastRing<T, N, S> {
+    /// Create a ring whose spilled items are sent to `sink`.
+    #[must_use]
+    pub fn with_sink(sink: S) -> Self {
+        const { assert!(N > 0, "capacity must be > 0") };
+        const { assert!(N.is_power_of_two(), "capacity must be power of two") };
+        const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+
+        Self {
+            head: 0,
+            tail: 0,
+            buffer: [const { Slot::new() }; N],
+            readers: Vec::new(),
+            sink,
+        }
+    }
+
+    /// Add a reader. It sees items pushed from now on.
+    pub fn subscribe(&mut self) -> ReaderId {
+        let cursor = Cursor {
+            pos: self.tail,
+            missed: 0,
+        };
+        match self.readers.iter().position(Option::is_none) {
+            Some(idx) => {
+                self.readers[idx] = Some(cursor);
+                ReaderId(idx)
+            }
+            None => {
+                self.readers.push(Some(cursor));
+                ReaderId(self.readers.len() - 1)
+            }
+        }
+    }
+
+    /// Remove a reader. It no longer holds items in the ring, and its id
+    /// may be reused by a later [`subscribe`](Self::subscribe).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not subscribed.
+    pub fn unsubscribe(&mut self, reader: ReaderId) {
+        assert!(
+            self.readers.get(reader.0).is_some_and(Option::is_some),
+            "reader is not subscribed"
+        );
+        self.readers[reader.0] = None;
+    }
+
+    /// Number of subscribed readers.
+    #[must_use]
+    pub fn num_readers(&self) -> usize {
+        self.readers.iter().flatten().count()
+    }
+
+    /// Push an item. If a reader has not read the oldest slot and the ring
+    /// is full, that slot is spilled to the sink and lagging readers skip it.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        if self.tail.wrapping_sub(self.head) >= N {
+            self.release_read();
+            if self.tail.wrapping_sub(self.head) >= N {
+                self.spill_oldest();
+            }
+        }
+        self.write(item);
+    }
+
+    /// Push an item, or report the slowest reader if the ring is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlowReader`] with the item if a reader has not read the
+    /// oldest slot and the ring is full.
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), SlowReader<T>> {
+        if self.tail.wrapping_sub(self.head) >= N {
+            self.release_read();
+            if self.tail.wrapping_sub(self.head) >= N {
+                let (reader, pos) = self.slowest().expect("full ring has a reader");
+                return Err(SlowReader {
+                    item,
+                    reader,
+                    unread: self.tail.wrapping_sub(pos),
+                });
+            }
+        }
+        self.write(item);
+        Ok(())
+    }
+
+    /// Read the next item for `reader`.
+    ///
+    /// Returns `Ok(None)` when the reader has caught up.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lagged`] once after items this reader had not read were
+    /// spilled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not subscribed.
+    pub fn recv(&mut self, reader: ReaderId) -> Result<Option<T>, Lagged>
+    where
+        T: Clone,
+    {
+        let tail = self.tail;
+        let cursor = self.cursor_mut(reader);
+        if cursor.missed > 0 {
+            let missed = core::mem::take(&mut cursor.missed);
+            return Err(Lagged { missed });
+        }
+        if cursor.pos == tail {
+            return Ok(None);
+        }
+        let pos = cursor.pos;
+        cursor.pos = pos.wrapping_add(1);
+        Ok(Some(unsafe { self.slot_ref(pos) }.clone()))
+    }
+
+    /// The next item for `reader`, without advancing its cursor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not subscribed.
+    #[must_use]
+    pub fn peek(&self, reader: ReaderId) -> Option<&T> {
+        let cursor = self.cursor(reader);
+        if cursor.pos == self.tail {
+            return None;
+        }
+        Some(unsafe { self.slot_ref(cursor.pos) })
+    }
+
+    /// Number of items `reader` has yet to read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not subscribed.
+    #[must_use]
+    pub fn unread(&self, reader: ReaderId) -> usize {
+        self.tail.wrapping_sub(self.cursor(reader).pos)
+    }
+
+    /// Number of items held in the ring.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.wrapping_sub(self.head)
+    }
+
+    /// True if the ring holds no items.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Reference to the spout.
+    #[inline]
+    #[must_use]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable reference to the spout.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn cursor(&self, reader: ReaderId) -> &Cursor {
+        self.readers
+            .get(reader.0)
+            .and_then(Option::as_ref)
+            .expect("reader is not subscribed")
+    }
+
+    fn cursor_mut(&mut self, reader: ReaderId) -> &mut Cursor {
+        self.readers
+            .get_mut(reader.0)
+            .and_then(Option::as_mut)
+            .expect("reader is not subscribed")
+    }
+
+    /// The reader furthest behind, and its position.
+    fn slowest(&self) -> Option<(ReaderId, usize)> {
+        let tail = self.tail;
+        self.readers
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, c)| c.as_ref().map(|c| (ReaderId(idx), c.pos)))
+            .max_by_key(|&(_, pos)| tail.wrapping_sub(pos))
+    }
+
+    /// Drop items every reader has moved past.
+    fn release_read(&mut self) {
+        let min = self.slowest().map_or(self.tail, |(_, pos)| pos);
+        while self.head != min {
+            let idx = self.head & (N - 1);
+            self.head = self.head.wrapping_add(1);
+            unsafe { (*self.buffer[idx].data.get()).assume_init_drop() };
+        }
+    }
+
+    /// Spill the oldest item, moving readers that had not read it past it.
+    fn spill_oldest(&mut self) {
+        let pos = self.head;
+        self.head = pos.wrapping_add(1);
+        for cursor in self.readers.iter_mut().flatten() {
+            if cursor.pos == pos {
+                cursor.pos = self.head;
+                cursor.missed += 1;
+            }
+        }
+        let item = unsafe { (*self.buffer[pos & (N - 1)].data.get()).assume_init_read() };
+        let _ = self.sink.send(item);
+    }
+
+    #[inline]
+    fn write(&mut self, item: T) {
+        let idx = self.tail & (N - 1);
+        unsafe { (*self.buffer[idx].data.get()).write(item) };
+        self.tail = self.tail.wrapping_add(1);
+    }
+
+    /// # Safety
+    /// `pos` must be in `head..tail`.
+    #[inline]
+    unsafe fn slot_ref(&self, pos: usize) -> &T {
+        unsafe { (*self.buffer[pos & (N - 1)].data.get()).assume_init_ref() }
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
+    for BroadcastRing<T, N, S>
+{
+    fn drop(&mut self) {
+        self.release_read();
+        while self.head != self.tail {
+            let idx = self.head & (N - 1);
+            self.head = self.head.wrapping_add(1);
+            let item = unsafe { (*self.buffer[idx].data.get()).assume_init_read() };
+            let _ = self.sink.send(item);
+        }
+        let _ = self.sink.flush();
+    }
+}
@@ -2,9 +2,11 @@
 
 use core::marker::PhantomData;
 
-use spout::{DropSpout, Spout};
+use spout::{Clock, DropSpout, Spout};
 
 use crate::SpillRing;
+use crate::policy::{OverflowPolicy, SpillOldest};
+use crate::stats::{NoStats, StatsLayer};
 
 /// Builder for constructing a [`SpillRing`].
 ///
@@ -14,22 +16,34 @@ use crate::SpillRing;
 /// # Example
 ///
 /// ```
-/// use spill_ring::SpillRing;
+/// use spill_ring::{Counters, SpillBatch, SpillRing};
 ///
 /// // Default: warmed, DropSpout
 /// let ring = SpillRing::<u64, 256>::builder().build();
 ///
 /// // Cold (no cache warming)
 /// let ring = SpillRing::<u64, 256>::builder().cold().build();
+///
+/// // Evict 16 items per sink call when full
+/// let ring = SpillRing::<u64, 256>::builder()
+///     .policy(SpillBatch::<16>)
+///     .build();
+///
+/// // Count pushes, evictions and peak occupancy
+/// let ring = SpillRing::<u64, 256>::builder()
+///     .stats::<Counters>()
+///     .build();
 /// ```
 pub struct SpillRingBuilder<
     T,
     const N: usize,
     S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    P: OverflowPolicy = SpillOldest,
+    M: StatsLayer = NoStats,
 > {
     sink: S,
     warm: bool,
-    _marker: PhantomData<T>,
+    _marker: PhantomData<(T, P, M)>,
 }
 
 impl<T, const N: usize> SpillRingBuilder<T, N, DropSpout> {
@@ -42,12 +56,19 @@ impl<T, const N: usize> SpillRingBuilder<T, N, DropSpout> {
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRingBuilder<T, N, S> {
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRingBuilder<T, N, S, P, M>
+{
     /// Set a custom spout for handling evicted items.
     pub fn sink<S2: Spout<T, Error = core::convert::Infallible>>(
         self,
         sink: S2,
-    ) -> SpillRingBuilder<T, N, S2> {
+    ) -> SpillRingBuilder<T, N, S2, P, M> {
         SpillRingBuilder {
             sink,
             warm: self.warm,
@@ -55,6 +76,30 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         }
     }
 
+    /// Set the overflow policy (default [`SpillOldest`]).
+    ///
+    /// See [`OverflowPolicy`] for the available policies.
+    pub fn policy<P2: OverflowPolicy>(self, _policy: P2) -> SpillRingBuilder<T, N, S, P2, M> {
+        SpillRingBuilder {
+            sink: self.sink,
+            warm: self.warm,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the stats layer (default [`NoStats`], which records nothing).
+    ///
+    /// Use [`Counters`](crate::Counters) to count pushes, pops,
+    /// evictions, flushes and peak occupancy, read back with
+    /// [`SpillRing::stats`].
+    pub fn stats<M2: StatsLayer>(self) -> SpillRingBuilder<T, N, S, P, M2> {
+        SpillRingBuilder {
+            sink: self.sink,
+            warm: self.warm,
+            _marker: PhantomData,
+        }
+    }
+
     /// Disable cache warming.
     ///
     /// By default, the builder warms the ring (touches all slots to pull them
@@ -66,11 +111,100 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
     }
 
     /// Build the [`SpillRing`].
-    pub fn build(self) -> SpillRing<T, N, S> {
+    pub fn build(self) -> SpillRing<T, N, S, P, M> {
+        SpillRing::from_sink(self.sink, self.warm)
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, P: OverflowPolicy>
+    SpillRingBuilder<T, N, S, P>
+{
+    /// Build a [`TimedSpillRing`](crate::TimedSpillRing) whose items are
+    /// stamped by `clock` and expire after `ttl` ticks.
+    pub fn build_timed<C: Clock>(self, clock: C, ttl: u64) -> crate::TimedSpillRing<T, N, C, S, P> {
+        crate::TimedSpillRing::from_ring(self.build(), clock, ttl)
+    }
+}
+
+/// Builder for constructing a [`DynSpillRing`](crate::DynSpillRing).
+///
+/// Created via [`DynSpillRing::builder()`](crate::DynSpillRing::builder).
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::DynSpillRing;
+///
+/// let ring = DynSpillRing::<u64>::builder(1000)
+///     .any_capacity()
+///     .cold()
+///     .build();
+/// assert_eq!(ring.capacity(), 1000);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct DynSpillRingBuilder<T, S: Spout<T, Error = core::convert::Infallible> = DropSpout> {
+    capacity: usize,
+    any_capacity: bool,
+    sink: S,
+    warm: bool,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DynSpillRingBuilder<T, DropSpout> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            any_capacity: false,
+            sink: DropSpout,
+            warm: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S: Spout<T, Error = core::convert::Infallible>> DynSpillRingBuilder<T, S> {
+    /// Set a custom spout for handling evicted items.
+    pub fn sink<S2: Spout<T, Error = core::convert::Infallible>>(
+        self,
+        sink: S2,
+    ) -> DynSpillRingBuilder<T, S2> {
+        DynSpillRingBuilder {
+            capacity: self.capacity,
+            any_capacity: self.any_capacity,
+            sink,
+            warm: self.warm,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allow capacities that are not a power of two, here and in later
+    /// calls to [`resize`](crate::DynSpillRing::resize).
+    ///
+    /// Power-of-two capacities index with a mask; others need an extra
+    /// comparison per access.
+    pub fn any_capacity(mut self) -> Self {
+        self.any_capacity = true;
+        self
+    }
+
+    /// Disable cache warming.
+    pub fn cold(mut self) -> Self {
+        self.warm = false;
+        self
+    }
+
+    /// Build the [`DynSpillRing`](crate::DynSpillRing).
+    ///
+    /// # Panics
+    /// Panics if the capacity is zero, or not a power of two without
+    /// [`any_capacity`](Self::any_capacity).
+    pub fn build(self) -> crate::DynSpillRing<T, S> {
+        let mut ring = crate::DynSpillRing::from_parts(self.capacity, self.any_capacity, self.sink);
         if self.warm {
-            SpillRing::with_sink(self.sink)
-        } else {
-            SpillRing::with_sink_cold(self.sink)
+            ring.warm();
         }
+        ring
     }
 }
@@ -0,0 +1,462 @@
+//! File-backed overflow tier.
+//!
+//! [`DiskSpill`] appends every spilled item to segment files in a
+//! directory. Combined with the ring that spills into it, it forms one
+//! ordered log: [`SpillRing::replay`] yields the items on disk, oldest
+//! first, and then the items still in the ring.
+//!
+//! Each record is a little-endian `u32` payload length followed by the
+//! item's [`ToBytes`] encoding. A segment is closed once it reaches the
+//! configured size; records never straddle two segments. Segment files
+//! are named by a zero-padded sequence number, so sorting names sorts them
+//! in write order.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bytecast::{BytesError, FromBytes, FromBytesExt, ToBytes};
+use spout::Spout;
+
+use crate::iter::SpillRingIter;
+use crate::policy::OverflowPolicy;
+use crate::ring::SpillRing;
+
+const SEGMENT_EXT: &str = "seg";
+const DEFAULT_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+/// Buffered bytes that trigger a write to the open segment.
+const WRITE_BUFFER: usize = 8 * 1024;
+
+/// Sink that appends items to size-capped segment files.
+///
+/// Rings require an infallible sink, so I/O errors do not propagate from
+/// `send`. The first error is kept for [`take_error`](Self::take_error),
+/// and items that could not be written are counted by
+/// [`failed`](Self::failed).
+///
+/// Records are buffered and written whole. If a write fails, the segment
+/// is cut back to its last complete record and closed, the buffered
+/// records move from [`written`](Self::written) to `failed`, and the next
+/// item starts a fresh segment.
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::{DiskSpill, SpillRing};
+///
+/// let dir = std::env::temp_dir().join(format!("spill-ring-doc-{}", std::process::id()));
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// let mut ring = SpillRing::<u32, 4, _>::with_sink(DiskSpill::open(&dir).unwrap());
+/// for i in 0..10 {
+///     ring.push(i);
+/// }
+///
+/// let log: Vec<u32> = ring.replay().map(Result::unwrap).collect();
+/// assert_eq!(log, (0..10).collect::<Vec<_>>());
+/// # drop(ring);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct DiskSpill<T> {
+    dir: PathBuf,
+    segment_size: u64,
+    /// Segment being appended to, opened on first write.
+    writer: Option<File>,
+    /// Sequence number of the next segment to open.
+    next_segment: u64,
+    /// Bytes of complete records in the open segment, buffered or not.
+    segment_len: u64,
+    /// Bytes of the open segment known to be on disk.
+    durable: u64,
+    /// Complete records not yet written to the open segment.
+    pending: Vec<u8>,
+    pending_records: u64,
+    /// Sequence numbers of every segment written, oldest first.
+    segments: Vec<u64>,
+    /// Reused encoding buffer.
+    buf: Vec<u8>,
+    written: u64,
+    failed: u64,
+    error: Option<io::Error>,
+    /// Bytes the next write gets through before failing.
+    #[cfg(test)]
+    fail_after: Option<usize>,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T> DiskSpill<T> {
+    /// Open (creating if needed) a spill directory.
+    ///
+    /// Segments already in `dir` are kept and come first in
+    /// [`replay`](Self::replay); new items go to a fresh segment after
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or listed.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == SEGMENT_EXT) {
+                if let Some(seq) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse().ok())
+                {
+                    segments.push(seq);
+                }
+            }
+        }
+        segments.sort_unstable();
+        let next_segment = segments.last().map_or(0, |&last| last + 1);
+        Ok(Self {
+            dir,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            writer: None,
+            next_segment,
+            segment_len: 0,
+            durable: 0,
+            pending: Vec::new(),
+            pending_records: 0,
+            segments,
+            buf: Vec::new(),
+            written: 0,
+            failed: 0,
+            error: None,
+            #[cfg(test)]
+            fail_after: None,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Close a segment once it reaches `bytes` (default 64 MiB).
+    ///
+    /// A segment always holds at least one record, so a record larger than
+    /// `bytes` gets a segment of its own.
+    #[must_use]
+    pub fn with_segment_size(mut self, bytes: u64) -> Self {
+        self.segment_size = bytes;
+        self
+    }
+
+    /// The spill directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Paths of all segment files, oldest first.
+    #[must_use]
+    pub fn segments(&self) -> Vec<PathBuf> {
+        self.segments
+            .iter()
+            .map(|&seq| self.segment_path(seq))
+            .collect()
+    }
+
+    /// Number of items written since this sink was opened.
+    #[must_use]
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Number of items lost to serialization or I/O errors.
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+
+    /// Take the first error since the last call, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flush buffered records and iterate over every item on disk, oldest
+    /// first.
+    ///
+    /// The iterator reads the segments that exist now; items spilled while
+    /// it runs are not included.
+    pub fn replay(&mut self) -> DiskReplay<T> {
+        self.write_pending();
+        DiskReplay {
+            segments: self.segments().into_iter(),
+            reader: None,
+            remaining: 0,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn segment_path(&self, seq: u64) -> PathBuf {
+        self.dir.join(format!("{seq:020}.{SEGMENT_EXT}"))
+    }
+
+    /// Make the next write fail after `bytes` bytes reach the segment.
+    #[cfg(test)]
+    pub(crate) fn fail_next_write_after(&mut self, bytes: usize) {
+        self.fail_after = Some(bytes);
+    }
+
+    /// Buffer one encoded record, opening a segment if none is open.
+    fn append(&mut self, len: u32) -> io::Result<()> {
+        if self.writer.is_none() {
+            let seq = self.next_segment;
+            let file = File::options()
+                .create_new(true)
+                .append(true)
+                .open(self.segment_path(seq))?;
+            self.next_segment += 1;
+            self.segments.push(seq);
+            self.segment_len = 0;
+            self.durable = 0;
+            self.writer = Some(file);
+        }
+        self.pending.extend_from_slice(&len.to_le_bytes());
+        self.pending.extend_from_slice(&self.buf);
+        self.pending_records += 1;
+        self.segment_len += 4 + u64::from(len);
+        Ok(())
+    }
+
+    /// Write buffered records once enough are pending, and close the
+    /// segment once it is full.
+    fn write_if_due(&mut self) {
+        let full = self.segment_len >= self.segment_size;
+        if full || self.pending.len() >= WRITE_BUFFER {
+            self.write_pending();
+        }
+        if full {
+            self.writer = None;
+        }
+    }
+
+    /// Write the buffered records to the open segment.
+    ///
+    /// On failure the segment is truncated to its last complete record, so
+    /// replay can read past it, and closed.
+    fn write_pending(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let Some(file) = &mut self.writer else {
+            return;
+        };
+        #[cfg(test)]
+        let result = match self.fail_after.take() {
+            Some(n) => file
+                .write_all(&self.pending[..n.min(self.pending.len())])
+                .and_then(|()| Err(io::Error::other("injected write failure"))),
+            None => file.write_all(&self.pending),
+        };
+        #[cfg(not(test))]
+        let result = file.write_all(&self.pending);
+        match result {
+            Ok(()) => self.durable += self.pending.len() as u64,
+            Err(e) => {
+                let _ = file.set_len(self.durable);
+                self.writer = None;
+                self.written -= self.pending_records;
+                self.failed += self.pending_records;
+                self.record_error(e);
+            }
+        }
+        self.pending.clear();
+        self.pending_records = 0;
+    }
+
+    fn record_error(&mut self, e: io::Error) {
+        self.error.get_or_insert(e);
+    }
+}
+
+/// Serialize `item` into `buf`, growing it as needed.
+fn encode<T: ToBytes>(item: &T, buf: &mut Vec<u8>) -> Result<usize, BytesError> {
+    let hint = item.byte_len().or(T::MAX_SIZE).unwrap_or(64);
+    buf.clear();
+    buf.resize(hint, 0);
+    loop {
+        match item.to_bytes(buf) {
+            Ok(n) => return Ok(n),
+            Err(BytesError::BufferTooSmall { needed, .. }) if needed > buf.len() => {
+                buf.resize(needed, 0);
+            }
+            Err(BytesError::BufferTooSmall { .. }) => {
+                let len = buf.len();
+                buf.resize((len * 2).max(64), 0);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn invalid(e: BytesError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl<T: ToBytes> Spout<T> for DiskSpill<T> {
+    type Error = core::convert::Infallible;
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let result = encode(&item, &mut self.buf)
+            .and_then(|n| {
+                u32::try_from(n).map_err(|_| BytesError::Custom {
+                    message: "record too large",
+                })
+            })
+            .map_err(invalid)
+            .and_then(|len| {
+                self.buf.truncate(len as usize);
+                self.append(len)
+            });
+        match result {
+            Ok(()) => {
+                self.written += 1;
+                self.write_if_due();
+            }
+            Err(e) => {
+                self.failed += 1;
+                self.record_error(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.write_pending();
+        Ok(())
+    }
+}
+
+impl<T> Drop for DiskSpill<T> {
+    fn drop(&mut self) {
+        self.write_pending();
+    }
+}
+
+/// Iterator over the items in a [`DiskSpill`]'s segments, oldest first.
+///
+/// Yields an error for a record that cannot be read or decoded, such as
+/// one cut short by a crash, and then stops.
+pub struct DiskReplay<T> {
+    segments: alloc::vec::IntoIter<PathBuf>,
+    reader: Option<BufReader<File>>,
+    /// Bytes left unread in the open segment.
+    remaining: u64,
+    buf: Vec<u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: FromBytes> DiskReplay<T> {
+    /// Read the next record, moving on to later segments as each one ends.
+    fn read_record(&mut self) -> io::Result<Option<T>> {
+        loop {
+            let reader = match &mut self.reader {
+                Some(reader) => reader,
+                None => match self.segments.next() {
+                    Some(path) => {
+                        let file = File::open(path)?;
+                        self.remaining = file.metadata()?.len();
+                        self.reader.insert(BufReader::new(file))
+                    }
+                    None => return Ok(None),
+                },
+            };
+            let mut len = [0u8; 4];
+            if !read_prefix(reader, &mut len)? {
+                self.reader = None;
+                continue;
+            }
+            self.remaining = self.remaining.saturating_sub(4);
+            // A corrupt length must not size the buffer.
+            let len = u64::from(u32::from_le_bytes(len));
+            if len > self.remaining {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "record length exceeds segment",
+                ));
+            }
+            self.remaining -= len;
+            self.buf.resize(len as usize, 0);
+            reader.read_exact(&mut self.buf)?;
+            return T::from_bytes_exact(&self.buf).map(Some).map_err(invalid);
+        }
+    }
+}
+
+/// Fill `len` from `reader`. Returns `false` at a clean end of segment;
+/// a prefix cut short is an error.
+fn read_prefix(reader: &mut impl Read, len: &mut [u8; 4]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < len.len() {
+        match reader.read(&mut len[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "record length cut short",
+                ));
+            }
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+impl<T: FromBytes> Iterator for DiskReplay<T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_record() {
+            Ok(item) => item.map(Ok),
+            Err(e) => {
+                // Don't resume past a bad record.
+                self.reader = None;
+                self.segments = Vec::new().into_iter();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`SpillRing::replay`].
+pub struct Replay<'a, T: ToBytes, const N: usize, P: OverflowPolicy> {
+    disk: DiskReplay<T>,
+    ring: SpillRingIter<'a, T, N, DiskSpill<T>, P>,
+}
+
+impl<T: ToBytes + FromBytes + Clone, const N: usize, P: OverflowPolicy> Iterator
+    for Replay<'_, T, N, P>
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.disk
+            .next()
+            .or_else(|| self.ring.next().cloned().map(Ok))
+    }
+}
+
+impl<T: ToBytes, const N: usize, P: OverflowPolicy> SpillRing<T, N, DiskSpill<T>, P> {
+    /// Iterate over the whole log: every item spilled to disk, oldest
+    /// first, then the items still in the ring. Neither is consumed.
+    ///
+    /// Pending records are flushed to disk first.
+    pub fn replay(&mut self) -> Replay<'_, T, N, P>
+    where
+        T: FromBytes + Clone,
+    {
+        let disk = self.sink_mut().replay();
+        Replay {
+            disk,
+            ring: self.iter(),
+        }
+    }
+}
@@ -0,0 +1,622 @@
+//! Heap-allocated ring buffer with a capacity chosen at runtime.
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+use crate::{
+    index::{CellIndex, SpoutCell},
+    ring::Slot,
+    traits::{RingConsumer, RingInfo, RingProducer},
+};
+use spout::{DropSpout, Source, Spout};
+
+/// Ring buffer that spills evicted items to a spout, with a runtime capacity.
+///
+/// Behaves like [`SpillRing`](crate::SpillRing) — pushing into a full ring
+/// evicts the oldest item to the sink, and dropping the ring flushes what
+/// is left — but the slots live on the heap and the capacity is picked at
+/// construction instead of as a const generic. Use it when the size comes
+/// from configuration.
+///
+/// Capacities must be powers of two unless the ring is built with
+/// [`any_capacity`](crate::DynSpillRingBuilder::any_capacity). There is no
+/// upper bound other than available memory.
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::DynSpillRing;
+/// use spout::CollectSpout;
+///
+/// let mut ring = DynSpillRing::builder(3)
+///     .any_capacity()
+///     .sink(CollectSpout::new())
+///     .build();
+/// ring.extend(1..=5);
+///
+/// assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+/// assert_eq!(ring.sink().items(), vec![1, 2]);
+/// ```
+pub struct DynSpillRing<T, S: Spout<T, Error = core::convert::Infallible> = DropSpout> {
+    /// Physical index of the oldest item.
+    head: CellIndex,
+    len: CellIndex,
+    buffer: Box<[Slot<T>]>,
+    any_capacity: bool,
+    sink: SpoutCell<S>,
+}
+
+unsafe impl<T: Send, S: Spout<T, Error = core::convert::Infallible> + Send> Send
+    for DynSpillRing<T, S>
+{
+}
+
+/// Allocate `capacity` uninitialized slots.
+fn alloc_slots<T>(capacity: usize) -> Box<[Slot<T>]> {
+    (0..capacity).map(|_| Slot::new()).collect()
+}
+
+fn check_capacity(capacity: usize, any_capacity: bool) {
+    assert!(capacity > 0, "capacity must be > 0");
+    assert!(
+        any_capacity || capacity.is_power_of_two(),
+        "capacity must be power of two"
+    );
+}
+
+impl<T> DynSpillRing<T, DropSpout> {
+    /// Create a builder for a ring with `capacity` slots.
+    pub fn builder(capacity: usize) -> crate::builder::DynSpillRingBuilder<T> {
+        crate::builder::DynSpillRingBuilder::new(capacity)
+    }
+
+    /// Create a new ring buffer with pre-warmed cache (evicted items are dropped).
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero or not a power of two.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_sink(capacity, DropSpout)
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> DynSpillRing<T, S> {
+    /// Create a new ring buffer with pre-warmed cache and a custom spout.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero or not a power of two.
+    #[must_use]
+    pub fn with_sink(capacity: usize, sink: S) -> Self {
+        let mut ring = Self::with_sink_cold(capacity, sink);
+        ring.warm();
+        ring
+    }
+
+    /// Create a new ring buffer with a custom spout, without cache warming.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero or not a power of two.
+    #[must_use]
+    pub fn with_sink_cold(capacity: usize, sink: S) -> Self {
+        Self::from_parts(capacity, false, sink)
+    }
+
+    pub(crate) fn from_parts(capacity: usize, any_capacity: bool, sink: S) -> Self {
+        check_capacity(capacity, any_capacity);
+        Self {
+            head: CellIndex::new(0),
+            len: CellIndex::new(0),
+            buffer: alloc_slots(capacity),
+            any_capacity,
+            sink: SpoutCell::new(sink),
+        }
+    }
+
+    /// Bring all ring slots into L1/L2 cache.
+    pub(crate) fn warm(&mut self) {
+        for slot in self.buffer.iter_mut() {
+            unsafe {
+                let ptr = slot.data.get_mut() as *mut MaybeUninit<T> as *mut u8;
+                core::ptr::write_bytes(ptr, 0, core::mem::size_of::<MaybeUninit<T>>());
+            }
+        }
+        self.head.store_mut(0);
+        self.len.store_mut(0);
+    }
+
+    /// Physical slot for logical position `head + offset`.
+    ///
+    /// Both `head` and `offset` are below the capacity, so one subtraction
+    /// is enough when the capacity is not a power of two.
+    #[inline]
+    fn slot_index(&self, head: usize, offset: usize) -> usize {
+        let cap = self.buffer.len();
+        let pos = head + offset;
+        if cap.is_power_of_two() {
+            pos & (cap - 1)
+        } else if pos >= cap {
+            pos - cap
+        } else {
+            pos
+        }
+    }
+
+    /// Move the item at `idx` out of the buffer.
+    ///
+    /// # Safety
+    /// The slot must be initialized and must not be read again until it is
+    /// rewritten.
+    #[inline]
+    unsafe fn take(&self, idx: usize) -> T {
+        unsafe { (*self.buffer[idx].data.get()).assume_init_read() }
+    }
+
+    /// Push an item. If full, evicts oldest to spout.
+    ///
+    /// Uses interior mutability (`Cell`). Not thread-safe.
+    #[inline]
+    pub fn push(&self, item: T) {
+        let mut head = self.head.load();
+        let mut len = self.len.load();
+
+        if len == self.buffer.len() {
+            let evicted = unsafe { self.take(head) };
+            head = self.slot_index(head, 1);
+            len -= 1;
+            self.head.store(head);
+            self.len.store(len);
+            let _ = unsafe { self.sink.get_mut_unchecked().send(evicted) };
+        }
+
+        let idx = self.slot_index(head, len);
+        unsafe { (*self.buffer[idx].data.get()).write(item) };
+        self.len.store(len + 1);
+    }
+
+    /// Push an item with exclusive access (no `Cell` overhead).
+    #[inline]
+    pub fn push_mut(&mut self, item: T) {
+        let mut head = self.head.load_mut();
+        let mut len = self.len.load_mut();
+
+        if len == self.buffer.len() {
+            let evicted = unsafe { self.take(head) };
+            head = self.slot_index(head, 1);
+            len -= 1;
+            self.head.store_mut(head);
+            self.len.store_mut(len);
+            let _ = self.sink.get_mut().send(evicted);
+        }
+
+        let idx = self.slot_index(head, len);
+        unsafe { (*self.buffer[idx].data.get()).write(item) };
+        self.len.store_mut(len + 1);
+    }
+
+    /// Pop the oldest item.
+    ///
+    /// Uses interior mutability (`Cell`). Not thread-safe.
+    #[inline]
+    #[must_use]
+    pub fn pop(&self) -> Option<T> {
+        let head = self.head.load();
+        let len = self.len.load();
+        if len == 0 {
+            return None;
+        }
+
+        let item = unsafe { self.take(head) };
+        self.head.store(self.slot_index(head, 1));
+        self.len.store(len - 1);
+        Some(item)
+    }
+
+    /// Pop the oldest item with exclusive access (no `Cell` overhead).
+    #[inline]
+    #[must_use]
+    pub fn pop_mut(&mut self) -> Option<T> {
+        let head = self.head.load_mut();
+        let len = self.len.load_mut();
+        if len == 0 {
+            return None;
+        }
+
+        let item = unsafe { self.take(head) };
+        self.head.store_mut(self.slot_index(head, 1));
+        self.len.store_mut(len - 1);
+        Some(item)
+    }
+
+    /// Bulk-push a slice of `Copy` items.
+    ///
+    /// Items that overflow the ring are evicted to the spout, oldest first.
+    /// If the slice is larger than the ring capacity, the leading excess
+    /// goes directly to the spout without touching the buffer.
+    #[inline]
+    pub fn push_slice(&mut self, items: &[T])
+    where
+        T: Copy,
+    {
+        let cap = self.buffer.len();
+        let keep = if items.len() > cap {
+            self.flush();
+            let excess = items.len() - cap;
+            let _ = self
+                .sink
+                .get_mut()
+                .send_all(items[..excess].iter().copied());
+            &items[excess..]
+        } else {
+            items
+        };
+        for &item in keep {
+            self.push_mut(item);
+        }
+    }
+
+    /// Bulk-extend from a slice. Equivalent to `push_slice`.
+    #[inline]
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Copy,
+    {
+        self.push_slice(items);
+    }
+
+    /// Bulk-pop up to `buf.len()` items into a slice. Returns the count popped.
+    #[inline]
+    pub fn pop_slice(&mut self, buf: &mut [MaybeUninit<T>]) -> usize
+    where
+        T: Copy,
+    {
+        let (a, b) = self.as_slices();
+        let count = buf.len().min(a.len() + b.len());
+        let from_a = count.min(a.len());
+        for (dst, src) in buf
+            .iter_mut()
+            .zip(a[..from_a].iter().chain(&b[..count - from_a]))
+        {
+            dst.write(*src);
+        }
+
+        let head = self.head.load_mut();
+        let len = self.len.load_mut();
+        self.head.store_mut(self.slot_index(head, count));
+        self.len.store_mut(len - count);
+        count
+    }
+
+    /// Push an item then flush all to spout.
+    #[inline]
+    pub fn push_and_flush(&mut self, item: T) {
+        self.push_mut(item);
+        self.flush();
+    }
+
+    /// Flush all items to spout. Returns count flushed.
+    ///
+    /// Panic-safe: each item is popped before it is sent, so a panic in the
+    /// spout will not cause double-reads during drop.
+    #[inline]
+    pub fn flush(&mut self) -> usize {
+        let count = self.len.load_mut();
+        for _ in 0..count {
+            let item = self.pop_mut().expect("count items buffered");
+            let _ = self.sink.get_mut().send(item);
+        }
+        count
+    }
+
+    /// Change the capacity, spilling the oldest items to the spout if more
+    /// than `capacity` are buffered.
+    ///
+    /// Remaining items keep their order. Reallocates even when shrinking.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero, or not a power of two and the ring was
+    /// not built with [`any_capacity`](crate::DynSpillRingBuilder::any_capacity).
+    pub fn resize(&mut self, capacity: usize) {
+        check_capacity(capacity, self.any_capacity);
+
+        while self.len.load_mut() > capacity {
+            let item = self.pop_mut().expect("len > capacity");
+            let _ = self.sink.get_mut().send(item);
+        }
+
+        let mut new = alloc_slots::<T>(capacity);
+        let (a, b) = self.as_slices();
+        unsafe {
+            let dst = new.as_mut_ptr() as *mut T;
+            core::ptr::copy_nonoverlapping(a.as_ptr(), dst, a.len());
+            core::ptr::copy_nonoverlapping(b.as_ptr(), dst.add(a.len()), b.len());
+        }
+        // The old slots are `MaybeUninit`, so dropping them does not drop
+        // the moved items.
+        self.buffer = new;
+        self.head.store_mut(0);
+    }
+
+    /// Number of items in buffer.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.load()
+    }
+
+    /// True if empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// True if full.
+    #[inline]
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.buffer.len()
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Clear all items from the buffer, flushing them to the spout.
+    pub fn clear(&mut self) {
+        self.flush();
+    }
+
+    /// The buffered items as two slices, oldest first.
+    ///
+    /// The second slice is empty unless the items wrap around the end of
+    /// the buffer.
+    #[inline]
+    #[must_use]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head = self.head.load();
+        let len = self.len.load();
+        let first = len.min(self.buffer.len() - head);
+        let base = self.buffer.as_ptr() as *const T;
+        // SAFETY: `Slot<T>` is a transparent wrapper over `MaybeUninit<T>`,
+        // and the `len` slots starting at `head` (wrapping) are initialized.
+        unsafe {
+            (
+                core::slice::from_raw_parts(base.add(head), first),
+                core::slice::from_raw_parts(base, len - first),
+            )
+        }
+    }
+
+    /// The buffered items as two mutable slices, oldest first.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let head = self.head.load_mut();
+        let len = self.len.load_mut();
+        let first = len.min(self.buffer.len() - head);
+        let base = self.buffer.as_mut_ptr() as *mut T;
+        // SAFETY: as in `as_slices`; the two ranges do not overlap.
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(base.add(head), first),
+                core::slice::from_raw_parts_mut(base, len - first),
+            )
+        }
+    }
+
+    /// Peek at the oldest item.
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Peek at the newest item.
+    #[inline]
+    #[must_use]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Get item by index (0 = oldest).
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        let idx = self.slot_index(self.head.load(), index);
+        Some(unsafe { (*self.buffer[idx].data.get()).assume_init_ref() })
+    }
+
+    /// Iterate oldest to newest.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator {
+        let (a, b) = self.as_slices();
+        Iter {
+            inner: a.iter().chain(b),
+            len: a.len() + b.len(),
+        }
+    }
+
+    /// Iterate mutably, oldest to newest.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut T> + DoubleEndedIterator {
+        let (a, b) = self.as_mut_slices();
+        let len = a.len() + b.len();
+        Iter {
+            inner: a.iter_mut().chain(b),
+            len,
+        }
+    }
+
+    /// Shared reference to the spout.
+    #[inline]
+    #[must_use]
+    pub fn sink_ref(&self) -> &S {
+        // SAFETY: DynSpillRing is !Sync, so &self proves single-context
+        // access. No &mut S alias can exist because that would require
+        // &mut self.
+        unsafe { self.sink.get_ref() }
+    }
+
+    /// Reference to the spout.
+    #[inline]
+    #[must_use]
+    pub fn sink(&mut self) -> &S {
+        self.sink.get_mut()
+    }
+
+    /// Mutable reference to the spout.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        self.sink.get_mut()
+    }
+
+    /// Drain all items from the ring, returning an iterator.
+    /// Items are removed oldest to newest.
+    #[inline]
+    pub fn drain(&mut self) -> DynDrain<'_, T, S> {
+        DynDrain { ring: self }
+    }
+}
+
+/// Chained slice iterator with an exact length.
+struct Iter<I> {
+    inner: I,
+    len: usize,
+}
+
+impl<I: Iterator> Iterator for Iter<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.inner.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Iter<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        let item = self.inner.next_back()?;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for Iter<I> {}
+
+/// Draining iterator over a [`DynSpillRing`].
+pub struct DynDrain<'a, T, S: Spout<T, Error = core::convert::Infallible>> {
+    ring: &'a mut DynSpillRing<T, S>,
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> Iterator for DynDrain<'_, T, S> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.ring.pop_mut()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> ExactSizeIterator for DynDrain<'_, T, S> {}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> core::iter::Extend<T>
+    for DynSpillRing<T, S>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_mut(item);
+        }
+    }
+}
+
+/// DynSpillRing can act as a Spout, enabling ring chaining.
+impl<T, S: Spout<T, Error = core::convert::Infallible>> Spout<T> for DynSpillRing<T, S> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.push_mut(item);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        DynSpillRing::flush(self);
+        Ok(())
+    }
+}
+
+/// DynSpillRing can act as a Source, draining oldest to newest.
+///
+/// The source is exhausted when the ring is empty.
+impl<T, S: Spout<T, Error = core::convert::Infallible>> Source<T> for DynSpillRing<T, S> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(self.pop_mut())
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> Drop for DynSpillRing<T, S> {
+    fn drop(&mut self) {
+        self.flush();
+        let _ = self.sink.get_mut().flush();
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> RingInfo for DynSpillRing<T, S> {
+    #[inline]
+    fn len(&self) -> usize {
+        DynSpillRing::len(self)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        DynSpillRing::capacity(self)
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> RingProducer<T> for DynSpillRing<T, S> {
+    #[inline]
+    fn try_push(&mut self, item: T) -> Result<(), crate::PushError<T>> {
+        if self.is_full() {
+            return Err(crate::PushError::Full(item));
+        }
+        self.push_mut(item);
+        Ok(())
+    }
+}
+
+impl<T, S: Spout<T, Error = core::convert::Infallible>> RingConsumer<T> for DynSpillRing<T, S> {
+    #[inline]
+    fn try_pop(&mut self) -> Option<T> {
+        self.pop_mut()
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<&T> {
+        DynSpillRing::peek(self)
+    }
+}
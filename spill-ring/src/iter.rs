@@ -1,20 +1,35 @@
 //! Iterators for SpillRing.
 
+use crate::policy::{OverflowPolicy, SpillOldest};
 use crate::ring::SpillRing;
+use crate::stats::{NoStats, StatsLayer};
 use spout::Spout;
 
 /// Immutable iterator.
-pub struct SpillRingIter<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> {
-    ring: &'a SpillRing<T, N, S>,
+pub struct SpillRingIter<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy = SpillOldest,
+    M: StatsLayer = NoStats,
+> {
+    ring: &'a SpillRing<T, N, S, P, M>,
     pos: usize,
     len: usize,
     head: usize,
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>>
-    SpillRingIter<'a, T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRingIter<'a, T, N, S, P, M>
 {
-    pub(crate) fn new(ring: &'a SpillRing<T, N, S>) -> Self {
+    pub(crate) fn new(ring: &'a SpillRing<T, N, S, P, M>) -> Self {
         Self {
             ring,
             pos: 0,
@@ -24,8 +39,14 @@ impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>>
     }
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iterator
-    for SpillRingIter<'a, T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Iterator for SpillRingIter<'a, T, N, S, P, M>
 {
     type Item = &'a T;
 
@@ -59,27 +80,50 @@ impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iter
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> ExactSizeIterator
-    for SpillRingIter<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> ExactSizeIterator for SpillRingIter<'_, T, N, S, P, M>
 {
 }
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> core::iter::FusedIterator
-    for SpillRingIter<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> core::iter::FusedIterator for SpillRingIter<'_, T, N, S, P, M>
 {
 }
 
 /// Mutable iterator.
-pub struct SpillRingIterMut<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> {
-    ring: &'a SpillRing<T, N, S>,
+pub struct SpillRingIterMut<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy = SpillOldest,
+    M: StatsLayer = NoStats,
+> {
+    ring: &'a SpillRing<T, N, S, P, M>,
     pos: usize,
     len: usize,
     head: usize,
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>>
-    SpillRingIterMut<'a, T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRingIterMut<'a, T, N, S, P, M>
 {
-    pub(crate) fn new(ring: &'a mut SpillRing<T, N, S>) -> Self {
+    pub(crate) fn new(ring: &'a mut SpillRing<T, N, S, P, M>) -> Self {
         let len = ring.len();
         let head = ring.head.load();
         Self {
@@ -91,8 +135,14 @@ impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>>
     }
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iterator
-    for SpillRingIterMut<'a, T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Iterator for SpillRingIterMut<'a, T, N, S, P, M>
 {
     type Item = &'a mut T;
 
@@ -116,20 +166,36 @@ impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iter
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> ExactSizeIterator
-    for SpillRingIterMut<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> ExactSizeIterator for SpillRingIterMut<'_, T, N, S, P, M>
 {
 }
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> core::iter::FusedIterator
-    for SpillRingIterMut<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> core::iter::FusedIterator for SpillRingIterMut<'_, T, N, S, P, M>
 {
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> IntoIterator
-    for &'a SpillRing<T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> IntoIterator for &'a SpillRing<T, N, S, P, M>
 {
     type Item = &'a T;
-    type IntoIter = SpillRingIter<'a, T, N, S>;
+    type IntoIter = SpillRingIter<'a, T, N, S, P, M>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -137,11 +203,17 @@ impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Into
     }
 }
 
-impl<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> IntoIterator
-    for &'a mut SpillRing<T, N, S>
+impl<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> IntoIterator for &'a mut SpillRing<T, N, S, P, M>
 {
     type Item = &'a mut T;
-    type IntoIter = SpillRingIterMut<'a, T, N, S>;
+    type IntoIter = SpillRingIterMut<'a, T, N, S, P, M>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -3,25 +3,60 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+mod broadcast;
 mod builder;
+#[cfg(all(feature = "std", feature = "bytecast"))]
+mod disk;
+#[cfg(feature = "alloc")]
+mod dyn_ring;
 mod error;
 mod index;
 mod iter;
 #[cfg(feature = "alloc")]
 mod mpsc;
+mod policy;
 mod read;
 mod ring;
+#[cfg(all(feature = "shm", target_os = "linux"))]
+mod shm;
+#[cfg(feature = "bytecast")]
+mod snapshot;
+#[cfg(feature = "alloc")]
+mod spsc;
+mod stats;
 mod traits;
+mod ttl;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "alloc")]
+pub use broadcast::{
+    AtomicBroadcast, BroadcastProducer, BroadcastReader, BroadcastRing, Lagged, ReaderId,
+    SlowReader,
+};
+#[cfg(feature = "alloc")]
+pub use builder::DynSpillRingBuilder;
 pub use builder::SpillRingBuilder;
+#[cfg(all(feature = "std", feature = "bytecast"))]
+pub use disk::{DiskReplay, DiskSpill, Replay};
+#[cfg(feature = "alloc")]
+pub use dyn_ring::{DynDrain, DynSpillRing};
 pub use error::PushError;
 pub use iter::{SpillRingIter, SpillRingIterMut};
 #[cfg(feature = "alloc")]
 pub use mpsc::{Consumer, MpscRing, Producer, collect};
 #[cfg(feature = "std")]
-pub use mpsc::{PoolBuilder, WorkerPool};
+pub use mpsc::{PanicPayload, PanicPolicy, PoolBuilder, PoolError, WorkerPool};
+pub use policy::{OverflowPolicy, Overwrite, SpillBatch, SpillNewest, SpillOldest};
 pub use ring::{Drain, SpillRing};
-pub use traits::{RingConsumer, RingInfo, RingProducer, RingTrait};
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub use shm::{ShmConsumer, ShmDrain, ShmProducer, ShmRing};
+#[cfg(feature = "bytecast")]
+pub use snapshot::SnapshotInfo;
+#[cfg(feature = "alloc")]
+pub use spsc::{SpscConsumer, SpscDrain, SpscProducer, SpscRing};
+pub use stats::{Counters, EnabledStats, NoStats, RingStats, StatsLayer};
+pub use traits::{RingConsumer, RingInfo, RingProducer, RingStatsInfo, RingTrait};
+pub use ttl::{DrainOlderThan, TimedSpillRing};
@@ -1,7 +1,12 @@
 extern crate alloc;
 
 use crate::SpillRing;
+use crate::policy::SpillOldest;
+use crate::stats::{EnabledStats, NoStats, RingStats, StatsLayer};
+use crate::traits::RingStatsInfo;
+use alloc::collections::BinaryHeap;
 use alloc::vec::Vec;
+use core::cmp::Reverse;
 use spout::Spout;
 
 use super::Producer;
@@ -14,19 +19,27 @@ pub struct Consumer<
     T,
     const N: usize,
     S: Spout<T, Error = core::convert::Infallible> = spout::DropSpout,
+    M: StatsLayer = NoStats,
 > {
-    rings: Vec<SpillRing<T, N, S>>,
+    rings: Vec<SpillRing<T, N, S, SpillOldest, M>>,
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Consumer<T, N, S> {
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: StatsLayer>
+    Consumer<T, N, S, M>
+{
     pub(crate) fn new() -> Self {
         Self { rings: Vec::new() }
     }
 
-    pub(crate) fn add_ring(&mut self, ring: SpillRing<T, N, S>) {
+    pub(crate) fn add_ring(&mut self, ring: SpillRing<T, N, S, SpillOldest, M>) {
         self.rings.push(ring);
     }
 
+    #[cfg(feature = "bytecast")]
+    pub(crate) fn rings(&self) -> &[SpillRing<T, N, S, SpillOldest, M>] {
+        &self.rings
+    }
+
     /// Drain all items from all rings into a sink.
     ///
     /// Items are drained in producer order, then FIFO within each producer.
@@ -40,6 +53,91 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Consumer
         let _ = sink.flush();
     }
 
+    /// Drain all items from all rings into a sink in global key order.
+    ///
+    /// Performs a heap-based k-way merge: each ring is FIFO, so if keys
+    /// increase within every producer (timestamps, sequence numbers), the
+    /// output is sorted by `key_fn` across producers. Equal keys keep
+    /// producer order. `key_fn` is called once per item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spill_ring::{MpscRing, collect};
+    /// use spout::CollectSpout;
+    ///
+    /// let (producers, mut consumer) = MpscRing::<(u64, char), 8>::with_consumer(2);
+    /// producers[0].push((1, 'a'));
+    /// producers[0].push((4, 'a'));
+    /// producers[1].push((2, 'b'));
+    /// producers[1].push((3, 'b'));
+    /// collect(producers, &mut consumer);
+    ///
+    /// let mut sink = CollectSpout::new();
+    /// consumer.drain_merged_by(|&(ts, _)| ts, &mut sink);
+    /// assert_eq!(sink.items(), [(1, 'a'), (2, 'b'), (3, 'b'), (4, 'a')]);
+    /// ```
+    pub fn drain_merged_by<K, Spout2>(&mut self, key_fn: impl FnMut(&T) -> K, sink: &mut Spout2)
+    where
+        K: Ord,
+        Spout2: Spout<T, Error = core::convert::Infallible>,
+    {
+        let sources = self.rings.iter_mut().map(|ring| ring.drain());
+        merge_by(sources, key_fn, sink);
+        let _ = sink.flush();
+    }
+
+    /// Like [`drain_merged_by`](Self::drain_merged_by), also merging in
+    /// items that were spilled out of the rings earlier.
+    ///
+    /// Each element of `spilled` is one sorted stream, typically the
+    /// collected sink output of one producer. Streams and rings must each
+    /// be sorted by `key_fn`; the output is then sorted as a whole. On
+    /// equal keys, spilled items come first (they left the ring earlier),
+    /// in the order the streams were given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spill_ring::{MpscRing, collect};
+    /// use spout::CollectSpout;
+    ///
+    /// let (producers, mut consumer) = MpscRing::<u64, 2>::with_consumer(2);
+    /// for ts in [1, 4, 6] {
+    ///     producers[0].push(ts); // 1 is evicted
+    /// }
+    /// for ts in [2, 3, 5] {
+    ///     producers[1].push(ts); // 2 is evicted
+    /// }
+    /// collect(producers, &mut consumer);
+    ///
+    /// let spilled = [vec![1], vec![2]];
+    /// let mut sink = CollectSpout::new();
+    /// consumer.drain_merged_with_by(spilled, |&ts| ts, &mut sink);
+    /// assert_eq!(sink.items(), [1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn drain_merged_with_by<K, I, Spout2>(
+        &mut self,
+        spilled: I,
+        key_fn: impl FnMut(&T) -> K,
+        sink: &mut Spout2,
+    ) where
+        K: Ord,
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = T>,
+        Spout2: Spout<T, Error = core::convert::Infallible>,
+    {
+        let spilled = spilled
+            .into_iter()
+            .map(|stream| Either::Left(stream.into_iter()));
+        let rings = self
+            .rings
+            .iter_mut()
+            .map(|ring| Either::Right(ring.drain()));
+        merge_by(spilled.chain(rings), key_fn, sink);
+        let _ = sink.flush();
+    }
+
     /// Get the number of producers/rings.
     pub fn num_producers(&self) -> usize {
         self.rings.len()
@@ -56,12 +154,89 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Consumer
     }
 }
 
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: EnabledStats>
+    Consumer<T, N, S, M>
+{
+    /// Usage counters summed across all rings.
+    ///
+    /// Counts add up; `peak_len` is the highest of any one ring. Only
+    /// available for consumers created with a stats layer such as
+    /// [`Counters`](crate::Counters).
+    pub fn stats(&self) -> RingStats {
+        self.rings.iter().map(|r| r.stats()).sum()
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: EnabledStats>
+    RingStatsInfo for Consumer<T, N, S, M>
+{
+    fn stats(&self) -> RingStats {
+        Consumer::stats(self)
+    }
+}
+
+/// One of two iterator types, so spilled streams and ring drains can share
+/// a merge.
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<T, L: Iterator<Item = T>, R: Iterator<Item = T>> Iterator for Either<L, R> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match self {
+            Either::Left(it) => it.next(),
+            Either::Right(it) => it.next(),
+        }
+    }
+}
+
+/// K-way merge of sorted `sources` into `sink`.
+///
+/// The heap holds one `(key, source index)` entry per non-empty source;
+/// the index breaks ties so equal keys come out in source order. The
+/// matching item waits in `heads` until its entry is popped.
+fn merge_by<T, K, I, Spout2>(
+    sources: impl Iterator<Item = I>,
+    mut key_fn: impl FnMut(&T) -> K,
+    sink: &mut Spout2,
+) where
+    K: Ord,
+    I: Iterator<Item = T>,
+    Spout2: Spout<T, Error = core::convert::Infallible>,
+{
+    let mut sources: Vec<I> = sources.collect();
+    let mut heads: Vec<Option<T>> = Vec::with_capacity(sources.len());
+    let mut heap = BinaryHeap::with_capacity(sources.len());
+
+    for (idx, source) in sources.iter_mut().enumerate() {
+        let head = source.next();
+        if let Some(item) = &head {
+            heap.push(Reverse((key_fn(item), idx)));
+        }
+        heads.push(head);
+    }
+
+    while let Some(Reverse((_, idx))) = heap.pop() {
+        let next = sources[idx].next();
+        if let Some(item) = &next {
+            heap.push(Reverse((key_fn(item), idx)));
+        }
+        if let Some(item) = core::mem::replace(&mut heads[idx], next) {
+            let _ = sink.send(item);
+        }
+    }
+}
+
 /// Collect producers back into a consumer for draining.
 ///
 /// This is a helper to reunite producers with their consumer after threads complete.
-pub fn collect<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>>(
-    producers: impl IntoIterator<Item = Producer<T, N, S>>,
-    consumer: &mut Consumer<T, N, S>,
+pub fn collect<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: StatsLayer>(
+    producers: impl IntoIterator<Item = Producer<T, N, S, M>>,
+    consumer: &mut Consumer<T, N, S, M>,
 ) {
     for producer in producers {
         consumer.add_ring(producer.into_ring());
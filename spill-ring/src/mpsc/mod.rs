@@ -40,21 +40,30 @@ mod pool;
 
 pub use consumer::{Consumer, collect};
 #[cfg(feature = "std")]
-pub use pool::{PoolBuilder, WorkerPool};
+pub use pool::{PanicPayload, PanicPolicy, PoolBuilder, PoolError, WorkerPool};
 pub use producer::Producer;
 
+use crate::stats::{NoStats, StatsLayer};
 use alloc::vec::Vec;
 use spout::{DropSpout, Spout};
 
+/// Producers paired with the consumer that collects them.
+type Handles<T, const N: usize, S, M> = (Vec<Producer<T, N, S, M>>, Consumer<T, N, S, M>);
+
 /// Zero-overhead MPSC ring buffer.
 ///
 /// Creates independent producers that each own a [`SpillRing`](crate::SpillRing)
 /// running at full speed. No shared state, no contention on the hot path.
-pub struct MpscRing<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> = DropSpout> {
-    _marker: core::marker::PhantomData<(T, S)>,
+pub struct MpscRing<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    M: StatsLayer = NoStats,
+> {
+    _marker: core::marker::PhantomData<(T, S, M)>,
 }
 
-impl<T, const N: usize> MpscRing<T, N, DropSpout> {
+impl<T, const N: usize, M: StatsLayer> MpscRing<T, N, DropSpout, M> {
     /// Create producers with default `DropSpout` (items dropped on overflow).
     ///
     /// Each producer owns its own ring running at full speed.
@@ -80,7 +89,7 @@ impl<T, const N: usize> MpscRing<T, N, DropSpout> {
     /// });
     /// ```
     #[allow(clippy::new_ret_no_self)]
-    pub fn new(num_producers: usize) -> Vec<Producer<T, N>> {
+    pub fn new(num_producers: usize) -> Vec<Producer<T, N, DropSpout, M>> {
         (0..num_producers).map(|_| Producer::new()).collect()
     }
 
@@ -117,7 +126,7 @@ impl<T, const N: usize> MpscRing<T, N, DropSpout> {
     /// let mut sink = CollectSpout::new();
     /// consumer.drain(&mut sink);
     /// ```
-    pub fn with_consumer(num_producers: usize) -> (Vec<Producer<T, N>>, Consumer<T, N>) {
+    pub fn with_consumer(num_producers: usize) -> Handles<T, N, DropSpout, M> {
         let producers = (0..num_producers).map(|_| Producer::new()).collect();
         (producers, Consumer::new())
     }
@@ -140,11 +149,11 @@ impl<T, const N: usize> MpscRing<T, N, DropSpout> {
     ///         }
     ///     });
     ///
-    /// pool.run(&10_000);
+    /// pool.run(&10_000).unwrap();
     /// let consumer = pool.into_consumer();
     /// ```
     #[cfg(feature = "std")]
-    pub fn pool(num_workers: usize) -> PoolBuilder<T, N, DropSpout>
+    pub fn pool(num_workers: usize) -> PoolBuilder<T, N, DropSpout, M>
     where
         T: Send + 'static,
     {
@@ -152,7 +161,9 @@ impl<T, const N: usize> MpscRing<T, N, DropSpout> {
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> + Clone> MpscRing<T, N, S> {
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> + Clone, M: StatsLayer>
+    MpscRing<T, N, S, M>
+{
     /// Create producers with a shared sink for handling evictions.
     ///
     /// Each producer gets a clone of the sink. Items overflow to the sink
@@ -173,7 +184,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> + Clone>
     ///     // Items flush to spout on drop
     /// }
     /// ```
-    pub fn with_sink(num_producers: usize, sink: S) -> Vec<Producer<T, N, S>> {
+    pub fn with_sink(num_producers: usize, sink: S) -> Vec<Producer<T, N, S, M>> {
         (0..num_producers)
             .map(|_| Producer::with_sink(sink.clone()))
             .collect()
@@ -186,7 +197,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> + Clone>
     /// [`spawn()`](PoolBuilder::spawn) to provide the work function and start
     /// the pool.
     #[cfg(feature = "std")]
-    pub fn pool_with_sink(num_workers: usize, sink: S) -> PoolBuilder<T, N, S>
+    pub fn pool_with_sink(num_workers: usize, sink: S) -> PoolBuilder<T, N, S, M>
     where
         T: Send + 'static,
         S: Send + 'static,
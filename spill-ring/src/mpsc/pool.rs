@@ -1,12 +1,20 @@
 extern crate alloc;
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::any::Any;
+use core::cell::UnsafeCell;
+use core::fmt;
 use core::marker::PhantomData;
 use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
-use std::sync::Arc;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread;
 
 use crate::SpillRing;
+use crate::index::CachePadded;
+use crate::policy::SpillOldest;
+use crate::stats::{EnabledStats, NoStats, RingStats, StatsLayer};
 use spout::{DropSpout, Spout};
 
 use super::Consumer;
@@ -22,18 +30,41 @@ pub struct PoolBuilder<
     T,
     const N: usize,
     S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    M: StatsLayer = NoStats,
 > {
     num_workers: usize,
     sink: S,
-    _marker: PhantomData<T>,
+    config: PoolConfig,
+    _marker: PhantomData<(T, M)>,
 }
 
-impl<T: Send + 'static, const N: usize> PoolBuilder<T, N, DropSpout> {
+/// A worker's ring.
+type Ring<T, const N: usize, S, M> = SpillRing<T, N, S, SpillOldest, M>;
+
+/// Settings that survive a [`WorkerPool::resize`].
+#[derive(Clone, Copy, Default)]
+struct PoolConfig {
+    on_panic: PanicPolicy,
+    park_after: Option<u32>,
+}
+
+impl PoolConfig {
+    fn barrier(&self, num_threads: usize) -> SpinBarrier {
+        let barrier = SpinBarrier::new(num_threads);
+        match self.park_after {
+            Some(budget) => barrier.with_parking(budget),
+            None => barrier,
+        }
+    }
+}
+
+impl<T: Send + 'static, const N: usize, M: StatsLayer> PoolBuilder<T, N, DropSpout, M> {
     pub(crate) fn new(num_workers: usize) -> Self {
         assert!(num_workers > 0, "must have at least one worker");
         Self {
             num_workers,
             sink: DropSpout,
+            config: PoolConfig::default(),
             _marker: PhantomData,
         }
     }
@@ -43,22 +74,59 @@ impl<
     T: Send + 'static,
     const N: usize,
     S: Spout<T, Error = core::convert::Infallible> + Clone + Send + 'static,
-> PoolBuilder<T, N, S>
+    M: StatsLayer,
+> PoolBuilder<T, N, S, M>
 {
     pub(crate) fn with_sink(num_workers: usize, sink: S) -> Self {
         assert!(num_workers > 0, "must have at least one worker");
         Self {
             num_workers,
             sink,
+            config: PoolConfig::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Choose what happens after a work function panics (default
+    /// [`PanicPolicy::Poison`]).
+    ///
+    /// Either way, the panic is caught, the other workers finish the run,
+    /// and [`WorkerPool::run`] returns [`PoolError::WorkerPanicked`].
+    pub fn on_panic(mut self, policy: PanicPolicy) -> Self {
+        self.config.on_panic = policy;
+        self
+    }
+
+    /// Set the workers' stats layer (default [`NoStats`]).
+    ///
+    /// With [`Counters`](crate::Counters), [`WorkerPool::stats`] sums the
+    /// counters of every worker's ring.
+    pub fn stats<M2: StatsLayer>(self) -> PoolBuilder<T, N, S, M2> {
+        PoolBuilder {
+            num_workers: self.num_workers,
+            sink: self.sink,
+            config: self.config,
             _marker: PhantomData,
         }
     }
 
+    /// Park waiting threads after `spin_budget` spin/yield iterations.
+    ///
+    /// By default workers spin (then yield) between runs, which gives the
+    /// lowest wake-up latency but keeps every core busy while the pool is
+    /// idle. In adaptive mode an idle pool sleeps on a condvar instead;
+    /// the first run after a long pause pays a syscall to wake it.
+    pub fn adaptive(mut self, spin_budget: u32) -> Self {
+        self.config.park_after = Some(spin_budget);
+        self
+    }
+
     /// Spawn worker threads with the given work function.
     ///
     /// The work function is cloned once per thread at spawn time and
     /// monomorphized — no dynamic dispatch on the hot path. Each worker
-    /// owns its own pre-warmed [`SpillRing`].
+    /// owns its own pre-warmed [`SpillRing`]. Its return value is
+    /// available from [`WorkerPool::run_with_results`].
     ///
     /// All threads are spawned and cache-warmed before this returns.
     ///
@@ -74,18 +142,116 @@ impl<
     ///         }
     ///     });
     ///
-    /// pool.run(&100);
+    /// pool.run(&100).unwrap();
     /// let consumer = pool.into_consumer();
     /// ```
-    pub fn spawn<F, A>(self, work: F) -> WorkerPool<T, N, S, F, A>
+    pub fn spawn<F, A, R>(self, work: F) -> WorkerPool<T, N, S, F, A, R, M>
     where
-        F: Fn(&SpillRing<T, N, S>, usize, &A) + Send + Clone + 'static,
+        M: Send + 'static,
+        F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
         A: Sync + 'static,
+        R: Send + 'static,
     {
-        WorkerPool::start(self.num_workers, self.sink, work)
+        WorkerPool::start(self.num_workers, self.sink, self.config, work)
+    }
+}
+
+/// What a [`WorkerPool`] does after a worker's work function panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Refuse further runs. Later calls to [`WorkerPool::run`] return
+    /// [`PoolError::Poisoned`] without starting the workers. The rings,
+    /// including the panicking worker's, can still be collected with
+    /// [`WorkerPool::into_consumer`].
+    #[default]
+    Poison,
+    /// Give the panicking worker a fresh ring and keep going. The old ring
+    /// is dropped, which flushes its items to its sink.
+    Respawn,
+}
+
+/// Panic payload, as returned by [`std::panic::catch_unwind`].
+pub type PanicPayload = Box<dyn Any + Send + 'static>;
+
+/// Error returned by [`WorkerPool::run`].
+pub enum PoolError {
+    /// A worker's work function panicked during this run.
+    ///
+    /// If several workers panicked, this names the lowest worker id; the
+    /// other payloads are dropped.
+    WorkerPanicked {
+        /// Id of the worker that panicked.
+        worker_id: usize,
+        /// The value the work function panicked with.
+        payload: PanicPayload,
+    },
+    /// An earlier run panicked under [`PanicPolicy::Poison`]. The workers
+    /// were not started.
+    Poisoned {
+        /// Id of the worker whose panic poisoned the pool.
+        worker_id: usize,
+    },
+}
+
+impl PoolError {
+    /// Id of the worker that panicked.
+    #[must_use]
+    pub fn worker_id(&self) -> usize {
+        match self {
+            PoolError::WorkerPanicked { worker_id, .. } | PoolError::Poisoned { worker_id } => {
+                *worker_id
+            }
+        }
+    }
+
+    /// The panic message, if the payload is a string.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            PoolError::WorkerPanicked { payload, .. } => {
+                payload.downcast_ref::<&str>().copied().or_else(|| {
+                    payload
+                        .downcast_ref::<alloc::string::String>()
+                        .map(|s| s.as_str())
+                })
+            }
+            PoolError::Poisoned { .. } => None,
+        }
+    }
+}
+
+impl fmt::Debug for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::WorkerPanicked { worker_id, .. } => f
+                .debug_struct("WorkerPanicked")
+                .field("worker_id", worker_id)
+                .field("message", &self.message())
+                .finish_non_exhaustive(),
+            PoolError::Poisoned { worker_id } => f
+                .debug_struct("Poisoned")
+                .field("worker_id", worker_id)
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::WorkerPanicked { worker_id, .. } => match self.message() {
+                Some(msg) => write!(f, "worker {worker_id} panicked: {msg}"),
+                None => write!(f, "worker {worker_id} panicked"),
+            },
+            PoolError::Poisoned { worker_id } => {
+                write!(f, "worker pool poisoned by a panic in worker {worker_id}")
+            }
+        }
     }
 }
 
+impl core::error::Error for PoolError {}
+
 /// A pool of persistent threads, each owning a pre-warmed [`SpillRing`].
 ///
 /// Thread-per-core design: each thread owns its ring and executes work
@@ -94,119 +260,333 @@ impl<
 ///
 /// The work function `F` is monomorphized into each thread at spawn time.
 /// Per-invocation arguments `A` are passed by shared reference via atomic
-/// pointer — no boxing, no cloning, no channels.
-pub struct WorkerPool<T, const N: usize, S, F, A>
+/// pointer — no boxing, no cloning, no channels. Each worker's return
+/// value `R` is kept in a per-worker slot until collected.
+pub struct WorkerPool<T, const N: usize, S, F, A, R = (), M = NoStats>
 where
     S: Spout<T, Error = core::convert::Infallible>,
-    F: Fn(&SpillRing<T, N, S>, usize, &A) + Send + Clone + 'static,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
     A: Sync + 'static,
+    M: StatsLayer,
 {
-    num_workers: usize,
-    handles: Vec<Option<thread::JoinHandle<SpillRing<T, N, S>>>>,
-    args_ptr: Arc<AtomicPtr<A>>,
-    shutdown: Arc<AtomicBool>,
-    start_barrier: Arc<SpinBarrier>,
-    done_barrier: Arc<SpinBarrier>,
-    _marker: PhantomData<F>,
+    handles: Vec<Option<thread::JoinHandle<Ring<T, N, S, M>>>>,
+    shared: Arc<Shared<A, R>>,
+    sink: S,
+    work: F,
+    config: PoolConfig,
+    poisoned: Option<usize>,
+    /// Set between [`submit`](WorkerPool::submit) and
+    /// [`wait`](WorkerPool::wait). Owns the arguments the workers are reading.
+    submitted: Option<Box<A>>,
+}
+
+/// State shared by the pool and its workers. Rebuilt on resize.
+struct Shared<A, R> {
+    args_ptr: AtomicPtr<A>,
+    shutdown: AtomicBool,
+    ready: SpinBarrier,
+    start: SpinBarrier,
+    done: SpinBarrier,
+    panics: PanicSlots,
+    results: ResultSlots<R>,
+    stats: StatsSlots,
+}
+
+impl<A, R> Shared<A, R> {
+    fn new(num_workers: usize, config: &PoolConfig) -> Self {
+        Self {
+            args_ptr: AtomicPtr::new(core::ptr::null_mut()),
+            shutdown: AtomicBool::new(false),
+            ready: config.barrier(num_workers + 1),
+            start: config.barrier(num_workers + 1),
+            done: config.barrier(num_workers + 1),
+            panics: PanicSlots::new(num_workers),
+            results: ResultSlots::new(num_workers),
+            stats: StatsSlots::new(num_workers),
+        }
+    }
+}
+
+/// Panic payloads caught by workers, one slot per worker.
+///
+/// Slots are written by their worker between the start and done barriers
+/// and read by the main thread after the done barrier, so the mutexes are
+/// never contended. `any` keeps the happy path to a single load.
+struct PanicSlots {
+    any: AtomicBool,
+    slots: Vec<Mutex<Option<PanicPayload>>>,
+}
+
+impl PanicSlots {
+    fn new(num_workers: usize) -> Self {
+        Self {
+            any: AtomicBool::new(false),
+            slots: (0..num_workers).map(|_| Mutex::new(None)).collect(),
+        }
+    }
+
+    fn report(&self, worker_id: usize, payload: PanicPayload) {
+        *self.slots[worker_id]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(payload);
+        self.any.store(true, Ordering::Release);
+    }
+
+    /// Take the lowest-id payload, clearing every slot.
+    fn take(&self) -> Option<(usize, PanicPayload)> {
+        if !self.any.swap(false, Ordering::Acquire) {
+            return None;
+        }
+        let mut first = None;
+        for (worker_id, slot) in self.slots.iter().enumerate() {
+            let payload = slot.lock().unwrap_or_else(PoisonError::into_inner).take();
+            if let Some(payload) = payload {
+                first.get_or_insert((worker_id, payload));
+            }
+        }
+        first
+    }
+}
+
+/// Work function return values, one cache-padded slot per worker.
+///
+/// Same access pattern as [`PanicSlots`]: worker `i` writes slot `i`
+/// between the start and done barriers, the main thread reads every slot
+/// after the done barrier. The barriers order the accesses.
+struct ResultSlots<R> {
+    slots: Vec<CachePadded<UnsafeCell<Option<R>>>>,
+}
+
+// Safety: each slot has one writer (its worker) while a run is in flight
+// and one reader (the main thread) after it, separated by the done barrier.
+unsafe impl<R: Send> Sync for ResultSlots<R> {}
+
+impl<R> ResultSlots<R> {
+    fn new(num_workers: usize) -> Self {
+        Self {
+            slots: (0..num_workers)
+                .map(|_| CachePadded(UnsafeCell::new(None)))
+                .collect(),
+        }
+    }
+
+    /// # Safety
+    /// Only worker `worker_id` may call this, and only during a run.
+    #[inline]
+    unsafe fn put(&self, worker_id: usize, value: R) {
+        unsafe { *self.slots[worker_id].get() = Some(value) };
+    }
+
+    /// # Safety
+    /// Only the main thread may call this, and only between runs.
+    unsafe fn take_all(&self) -> Vec<Option<R>> {
+        self.slots
+            .iter()
+            .map(|slot| unsafe { (*slot.get()).take() })
+            .collect()
+    }
+
+    /// # Safety
+    /// Same as [`take_all`](Self::take_all).
+    #[inline]
+    unsafe fn clear(&self) {
+        if core::mem::needs_drop::<R>() {
+            for slot in &self.slots {
+                unsafe { *slot.get() = None };
+            }
+        }
+    }
+}
+
+/// Ring counters published by each worker, one cache-padded slot per
+/// worker.
+///
+/// Workers publish before the ready barrier and before each done barrier;
+/// the main thread reads between runs. Same ordering as [`ResultSlots`].
+/// Only used when the stats layer is enabled.
+struct StatsSlots {
+    slots: Vec<CachePadded<UnsafeCell<RingStats>>>,
 }
 
-/// Shared state cloned into each worker thread.
-struct WorkerCtx<A> {
-    args_ptr: Arc<AtomicPtr<A>>,
-    shutdown: Arc<AtomicBool>,
-    ready: Arc<SpinBarrier>,
-    start: Arc<SpinBarrier>,
-    done: Arc<SpinBarrier>,
+// Safety: see `ResultSlots`.
+unsafe impl Sync for StatsSlots {}
+
+impl StatsSlots {
+    fn new(num_workers: usize) -> Self {
+        Self {
+            slots: (0..num_workers)
+                .map(|_| CachePadded(UnsafeCell::new(RingStats::default())))
+                .collect(),
+        }
+    }
+
+    /// # Safety
+    /// Only worker `worker_id` may call this, and only before the ready
+    /// barrier or during a run.
+    #[inline]
+    unsafe fn publish(&self, worker_id: usize, stats: RingStats) {
+        unsafe { *self.slots[worker_id].get() = stats };
+    }
+
+    /// # Safety
+    /// Only the main thread may call this, and only between runs.
+    unsafe fn sum(&self) -> RingStats {
+        self.slots.iter().map(|slot| unsafe { *slot.get() }).sum()
+    }
 }
 
 /// Worker thread entry point. Runs until shutdown is signaled, then returns the ring.
-fn worker_loop<T, const N: usize, S, F, A>(
+///
+/// Takes over `ring` when resizing; otherwise builds (and warms) the ring
+/// on the worker thread. Panics in the work function are caught and
+/// reported so the worker always reaches the done barrier; otherwise `run`
+/// would wait on it forever.
+fn worker_loop<T, const N: usize, S, F, A, R, M>(
+    ring: Option<Ring<T, N, S, M>>,
     sink: S,
     worker_id: usize,
     work: F,
-    ctx: WorkerCtx<A>,
-) -> SpillRing<T, N, S>
+    on_panic: PanicPolicy,
+    shared: Arc<Shared<A, R>>,
+) -> Ring<T, N, S, M>
 where
-    S: Spout<T, Error = core::convert::Infallible>,
-    F: Fn(&SpillRing<T, N, S>, usize, &A),
+    S: Spout<T, Error = core::convert::Infallible> + Clone,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R,
+    M: StatsLayer,
 {
-    let ring = SpillRing::with_sink(sink);
-    ctx.ready.wait();
+    let mut ring = ring.unwrap_or_else(|| SpillRing::from_sink(sink.clone(), true));
+    if M::ENABLED {
+        // Safety: this worker owns slot `worker_id` until the ready barrier.
+        unsafe { shared.stats.publish(worker_id, ring.stats.snapshot()) };
+    }
+    shared.ready.wait();
 
     loop {
-        ctx.start.wait();
+        shared.start.wait();
 
-        if ctx.shutdown.load(Ordering::Relaxed) {
+        if shared.shutdown.load(Ordering::Relaxed) {
             break;
         }
 
         // Safety: main thread sets args_ptr before triggering start barrier,
-        // and args outlives run() which waits on done barrier before returning.
-        let args = unsafe { &*ctx.args_ptr.load(Ordering::Acquire) };
-        work(&ring, worker_id, args);
+        // and args outlives the run, which ends at the done barrier.
+        let args = unsafe { &*shared.args_ptr.load(Ordering::Acquire) };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| work(&ring, worker_id, args)));
+
+        match result {
+            // Safety: this worker owns slot `worker_id` during the run.
+            Ok(value) => unsafe { shared.results.put(worker_id, value) },
+            Err(payload) => {
+                shared.panics.report(worker_id, payload);
+                if on_panic == PanicPolicy::Respawn {
+                    let old =
+                        core::mem::replace(&mut ring, SpillRing::from_sink(sink.clone(), true));
+                    // Dropping flushes to the sink; a sink panic must not kill the worker.
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(old)));
+                }
+            }
+        }
+        if M::ENABLED {
+            // Safety: this worker owns slot `worker_id` during the run.
+            unsafe { shared.stats.publish(worker_id, ring.stats.snapshot()) };
+        }
 
-        ctx.done.wait();
+        shared.done.wait();
     }
 
     ring
 }
 
-impl<T, const N: usize, S, F, A> WorkerPool<T, N, S, F, A>
+impl<T, const N: usize, S, F, A, R, M> WorkerPool<T, N, S, F, A, R, M>
 where
     T: Send + 'static,
     S: Spout<T, Error = core::convert::Infallible> + Clone + Send + 'static,
-    F: Fn(&SpillRing<T, N, S>, usize, &A) + Send + Clone + 'static,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
     A: Sync + 'static,
+    R: Send + 'static,
+    M: StatsLayer + Send + 'static,
 {
-    fn start(num_workers: usize, sink: S, work: F) -> Self {
-        let ready = Arc::new(SpinBarrier::new(num_workers + 1));
-        let start = Arc::new(SpinBarrier::new(num_workers + 1));
-        let done = Arc::new(SpinBarrier::new(num_workers + 1));
-        let shutdown = Arc::new(AtomicBool::new(false));
-        let args_ptr: Arc<AtomicPtr<A>> = Arc::new(AtomicPtr::new(core::ptr::null_mut()));
-
-        let mut handles = Vec::with_capacity(num_workers);
-        for worker_id in 0..num_workers {
-            let ctx = WorkerCtx {
-                args_ptr: Arc::clone(&args_ptr),
-                shutdown: Arc::clone(&shutdown),
-                ready: Arc::clone(&ready),
-                start: Arc::clone(&start),
-                done: Arc::clone(&done),
-            };
-            let handle = thread::spawn({
-                let sink = sink.clone();
-                let work = work.clone();
-                move || worker_loop(sink, worker_id, work, ctx)
-            });
-            handles.push(Some(handle));
-        }
+    fn start(num_workers: usize, sink: S, config: PoolConfig, work: F) -> Self {
+        let mut pool = Self {
+            handles: Vec::new(),
+            shared: Arc::new(Shared::new(0, &config)),
+            sink,
+            work,
+            config,
+            poisoned: None,
+            submitted: None,
+        };
+        pool.launch((0..num_workers).map(|_| None).collect());
+        pool
+    }
+
+    /// Spawn one worker per entry, handing over existing rings.
+    fn launch(&mut self, rings: Vec<Option<Ring<T, N, S, M>>>) {
+        let shared = Arc::new(Shared::new(rings.len(), &self.config));
+
+        self.handles = rings
+            .into_iter()
+            .enumerate()
+            .map(|(worker_id, ring)| {
+                let sink = self.sink.clone();
+                let work = self.work.clone();
+                let on_panic = self.config.on_panic;
+                let shared = Arc::clone(&shared);
+                Some(thread::spawn(move || {
+                    worker_loop(ring, sink, worker_id, work, on_panic, shared)
+                }))
+            })
+            .collect();
 
         // Wait for all threads to be spawned and warmed
-        ready.wait();
+        shared.ready.wait();
+        self.shared = shared;
+    }
 
-        Self {
-            num_workers,
-            handles,
-            args_ptr,
-            shutdown,
-            start_barrier: start,
-            done_barrier: done,
-            _marker: PhantomData,
+    /// Change the number of workers.
+    ///
+    /// Waits for a [`submit`](Self::submit)ted run to finish (discarding
+    /// its results), then restarts the worker threads. Workers
+    /// `0..num_workers` keep their rings; new workers get fresh ones.
+    /// Retired workers' rings are dropped, which flushes their items to
+    /// their sinks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_workers` is zero, or with the first panic from a
+    /// sink while flushing retired rings. The pool is restarted first.
+    pub fn resize(&mut self, num_workers: usize) {
+        assert!(num_workers > 0, "must have at least one worker");
+        if num_workers == self.num_rings() {
+            return;
+        }
+
+        let mut rings = self.shutdown_and_join();
+        let retired = if rings.len() > num_workers {
+            drop_rings(rings.split_off(num_workers))
+        } else {
+            None
+        };
+        let mut rings: Vec<_> = rings.into_iter().map(Some).collect();
+        rings.resize_with(num_workers, || None);
+        self.launch(rings);
+        if let Some(payload) = retired {
+            panic::resume_unwind(payload);
         }
     }
 }
 
-impl<T, const N: usize, S, F, A> WorkerPool<T, N, S, F, A>
+impl<T, const N: usize, S, F, A, R, M> WorkerPool<T, N, S, F, A, R, M>
 where
     S: Spout<T, Error = core::convert::Infallible>,
-    F: Fn(&SpillRing<T, N, S>, usize, &A) + Send + Clone + 'static,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
     A: Sync + 'static,
+    M: StatsLayer,
 {
     /// Get the number of workers in the pool.
     #[inline]
     pub fn num_rings(&self) -> usize {
-        self.num_workers
+        self.handles.len()
     }
 
     /// Run the work function on all workers with the given arguments.
@@ -214,19 +594,160 @@ where
     /// Each worker receives a shared reference to `args`. Blocks until
     /// all workers complete. Takes `&mut self` to prevent overlapping
     /// invocations, which would deadlock on the internal barriers.
+    /// Return values of the work function are dropped; use
+    /// [`run_with_results`](Self::run_with_results) to keep them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::WorkerPanicked`] if a work function panicked;
+    /// the other workers still run to completion. What happens next is set
+    /// by [`PoolBuilder::on_panic`]. Returns [`PoolError::Poisoned`] without
+    /// running anything if an earlier panic poisoned the pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`submit`](Self::submit)ted run has not been waited for.
+    #[inline]
+    pub fn run(&mut self, args: &A) -> Result<(), PoolError> {
+        self.dispatch(args)?;
+        self.finish()?;
+        // Safety: the run has ended at the done barrier.
+        unsafe { self.shared.results.clear() };
+        Ok(())
+    }
+
+    /// Like [`run`](Self::run), but collects each worker's return value,
+    /// indexed by worker id.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`run`](Self::run). On a panic the other workers' results
+    /// are dropped.
+    pub fn run_with_results(&mut self, args: &A) -> Result<Vec<R>, PoolError> {
+        self.dispatch(args)?;
+        self.finish()?;
+        Ok(self.take_results())
+    }
+
+    /// Start a run without waiting for it to finish.
+    ///
+    /// The pool keeps `args` until [`wait`](Self::wait), so the calling
+    /// thread is free to do other work in the meantime. Dropping or
+    /// consuming the pool first waits for the run and discards its results.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Poisoned`] if an earlier panic poisoned the
+    /// pool; nothing is started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a previously submitted run has not been waited for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spill_ring::MpscRing;
+    ///
+    /// let mut pool = MpscRing::<u64, 64>::pool(2)
+    ///     .spawn(|ring, worker_id, count: &u64| {
+    ///         for i in 0..*count {
+    ///             ring.push(i);
+    ///         }
+    ///         worker_id * 10
+    ///     });
+    ///
+    /// pool.submit(8).unwrap();
+    /// // ... overlap other work here ...
+    /// assert_eq!(pool.wait().unwrap(), vec![0, 10]);
+    /// ```
+    pub fn submit(&mut self, args: A) -> Result<(), PoolError> {
+        let args = Box::new(args);
+        self.dispatch(&args)?;
+        self.submitted = Some(args);
+        Ok(())
+    }
+
+    /// Wait for the [`submit`](Self::submit)ted run and collect each
+    /// worker's return value, indexed by worker id.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`run`](Self::run).
+    ///
+    /// # Panics
+    ///
+    /// Panics if no run was submitted.
+    pub fn wait(&mut self) -> Result<Vec<R>, PoolError> {
+        assert!(self.submitted.is_some(), "no submitted run to wait for");
+        let outcome = self.finish();
+        self.submitted = None;
+        outcome?;
+        Ok(self.take_results())
+    }
+
+    /// True if a [`submit`](Self::submit)ted run has not been waited for.
+    #[inline]
+    pub fn is_submitted(&self) -> bool {
+        self.submitted.is_some()
+    }
+
+    /// True if an earlier panic poisoned the pool.
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.is_some()
+    }
+
+    /// Publish `args` and release the workers.
     #[inline]
-    pub fn run(&mut self, args: &A) {
+    fn dispatch(&mut self, args: &A) -> Result<(), PoolError> {
+        assert!(
+            self.submitted.is_none(),
+            "a submitted run must be waited for first"
+        );
+        if let Some(worker_id) = self.poisoned {
+            return Err(PoolError::Poisoned { worker_id });
+        }
+
         // Set args pointer before triggering start barrier
-        self.args_ptr
+        self.shared
+            .args_ptr
             .store(args as *const A as *mut A, Ordering::Release);
-        self.start_barrier.wait();
-        self.done_barrier.wait();
+        self.shared.start.wait();
+        Ok(())
+    }
+
+    /// Wait for the workers and surface any panic.
+    #[inline]
+    fn finish(&mut self) -> Result<(), PoolError> {
+        self.shared.done.wait();
+
+        match self.shared.panics.take() {
+            None => Ok(()),
+            Some((worker_id, payload)) => {
+                // Safety: the run has ended at the done barrier.
+                unsafe { self.shared.results.clear() };
+                if self.config.on_panic == PanicPolicy::Poison {
+                    self.poisoned = Some(worker_id);
+                }
+                Err(PoolError::WorkerPanicked { worker_id, payload })
+            }
+        }
+    }
+
+    fn take_results(&mut self) -> Vec<R> {
+        // Safety: the run has ended at the done barrier. Without a panic,
+        // every worker filled its slot.
+        unsafe { self.shared.results.take_all() }
+            .into_iter()
+            .map(|r| r.expect("worker finished without a result"))
+            .collect()
     }
 
     /// Convert the pool into a [`Consumer`] for draining all rings.
     ///
     /// Signals shutdown, joins all threads, and collects their rings.
-    pub fn into_consumer(mut self) -> Consumer<T, N, S> {
+    pub fn into_consumer(mut self) -> Consumer<T, N, S, M> {
         let rings = self.shutdown_and_join();
         let mut consumer = Consumer::new();
         for ring in rings {
@@ -236,26 +757,101 @@ where
     }
 
     /// Signal shutdown and join all worker threads, returning their rings.
-    fn shutdown_and_join(&mut self) -> Vec<SpillRing<T, N, S>> {
+    fn shutdown_and_join(&mut self) -> Vec<Ring<T, N, S, M>> {
         if !self.handles.iter().any(|h| h.is_some()) {
             return Vec::new();
         }
-        self.shutdown.store(true, Ordering::Relaxed);
-        self.start_barrier.wait();
+        if self.submitted.is_some() {
+            let _ = self.finish();
+            self.submitted = None;
+            // Safety: the run has ended at the done barrier.
+            unsafe { self.shared.results.clear() };
+        }
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        self.shared.start.wait();
+        // Work panics are caught in the worker, so a join error can only
+        // come from ring setup. Skip that ring rather than panic in drop.
         self.handles
             .iter_mut()
-            .filter_map(|h| h.take().map(|h| h.join().unwrap()))
+            .filter_map(|h| h.take().and_then(|h| h.join().ok()))
             .collect()
     }
 }
 
-impl<T, const N: usize, S, F, A> Drop for WorkerPool<T, N, S, F, A>
+impl<T, const N: usize, S, F, A, R, M> WorkerPool<T, N, S, F, A, R, M>
+where
+    S: Spout<T, Error = core::convert::Infallible>,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
+    A: Sync + 'static,
+    M: EnabledStats,
+{
+    /// Usage counters summed across every worker's ring, as of the end of
+    /// the last run.
+    ///
+    /// Counts add up; `peak_len` is the highest of any one ring. A ring
+    /// replaced under [`PanicPolicy::Respawn`] takes its counts with it.
+    /// Only available for pools built with a stats layer, see
+    /// [`PoolBuilder::stats`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`submit`](Self::submit)ted run has not been waited for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spill_ring::{Counters, MpscRing};
+    ///
+    /// let mut pool = MpscRing::<u64, 4>::pool(2)
+    ///     .stats::<Counters>()
+    ///     .spawn(|ring, _, count: &u64| {
+    ///         for i in 0..*count {
+    ///             ring.push(i);
+    ///         }
+    ///     });
+    ///
+    /// pool.run(&10).unwrap();
+    /// let stats = pool.stats();
+    /// assert_eq!(stats.pushes, 20);
+    /// assert_eq!(stats.evictions, 12);
+    /// ```
+    pub fn stats(&self) -> RingStats {
+        assert!(
+            self.submitted.is_none(),
+            "a submitted run must be waited for first"
+        );
+        // Safety: no run is in flight, and every worker published before
+        // the ready barrier.
+        unsafe { self.shared.stats.sum() }
+    }
+}
+
+/// Drop each ring, flushing it to its sink. A panicking sink does not stop
+/// the other rings from being flushed; the first panic is returned.
+fn drop_rings<I: IntoIterator>(rings: I) -> Option<Box<dyn Any + Send>> {
+    let mut payload = None;
+    for ring in rings {
+        if let Err(p) = panic::catch_unwind(AssertUnwindSafe(|| drop(ring))) {
+            payload.get_or_insert(p);
+        }
+    }
+    payload
+}
+
+impl<T, const N: usize, S, F, A, R, M> Drop for WorkerPool<T, N, S, F, A, R, M>
 where
     S: Spout<T, Error = core::convert::Infallible>,
-    F: Fn(&SpillRing<T, N, S>, usize, &A) + Send + Clone + 'static,
+    F: Fn(&Ring<T, N, S, M>, usize, &A) -> R + Send + Clone + 'static,
     A: Sync + 'static,
+    M: StatsLayer,
 {
     fn drop(&mut self) {
-        self.shutdown_and_join();
+        let rings = self.shutdown_and_join();
+        if let Some(payload) = drop_rings(rings) {
+            // Don't start a second panic while one is unwinding.
+            if !thread::panicking() {
+                panic::resume_unwind(payload);
+            }
+        }
     }
 }
@@ -1,15 +1,25 @@
 use crate::SpillRing;
+use crate::policy::SpillOldest;
+use crate::stats::{EnabledStats, NoStats, RingStats, StatsLayer};
+use crate::traits::RingStatsInfo;
 use spout::{DropSpout, Spout};
 
 /// A producer handle for an MPSC ring.
 ///
 /// Each producer owns its own [`SpillRing`] with zero contention.
 /// When dropped, remaining items stay in the ring for the consumer to drain.
-pub struct Producer<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> = DropSpout> {
-    ring: SpillRing<T, N, S>,
+pub struct Producer<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    M: StatsLayer = NoStats,
+> {
+    ring: SpillRing<T, N, S, SpillOldest, M>,
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Producer<T, N, S> {
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: StatsLayer>
+    Producer<T, N, S, M>
+{
     /// Push an item to this producer's ring.
     ///
     /// This is the hot path - runs at ~4.6 Gelem/s.
@@ -42,23 +52,45 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Producer
         self.ring.capacity()
     }
 
-    pub(crate) fn into_ring(self) -> SpillRing<T, N, S> {
+    pub(crate) fn into_ring(self) -> SpillRing<T, N, S, SpillOldest, M> {
         self.ring
     }
 }
 
-impl<T, const N: usize> Producer<T, N, DropSpout> {
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: EnabledStats>
+    Producer<T, N, S, M>
+{
+    /// Usage counters of this producer's ring.
+    ///
+    /// Only available for producers created with a stats layer such as
+    /// [`Counters`](crate::Counters).
+    #[inline]
+    pub fn stats(&self) -> RingStats {
+        self.ring.stats()
+    }
+}
+
+impl<T, const N: usize, M: StatsLayer> Producer<T, N, DropSpout, M> {
     pub(crate) fn new() -> Self {
-        Self {
-            ring: SpillRing::new(),
-        }
+        Self::with_sink(DropSpout)
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Producer<T, N, S> {
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: StatsLayer>
+    Producer<T, N, S, M>
+{
     pub(crate) fn with_sink(sink: S) -> Self {
         Self {
-            ring: SpillRing::with_sink(sink),
+            ring: SpillRing::from_sink(sink, true),
         }
     }
 }
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: EnabledStats>
+    RingStatsInfo for Producer<T, N, S, M>
+{
+    #[inline]
+    fn stats(&self) -> RingStats {
+        Producer::stats(self)
+    }
+}
@@ -0,0 +1,65 @@
+//! Overflow policies for [`SpillRing`](crate::SpillRing).
+//!
+//! A policy decides what happens when an item is pushed into a full ring.
+//! It is a type parameter, so the choice is resolved at compile time and
+//! the push hot path contains only the selected branch.
+
+/// Behaviour of a push into a full ring.
+///
+/// Implemented by zero-sized marker types. The constants are read in
+/// `const` context, so unused branches are compiled out.
+pub trait OverflowPolicy {
+    /// Number of oldest items removed to make room for the incoming item.
+    ///
+    /// `0` rejects the incoming item instead; it is then handled as an
+    /// eviction itself. Must not exceed the ring capacity.
+    const EVICT: usize;
+
+    /// Whether removed items are sent to the sink (`true`) or dropped.
+    const SPILL: bool;
+}
+
+/// Evict the oldest item to the sink. The default policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpillOldest;
+
+impl OverflowPolicy for SpillOldest {
+    const EVICT: usize = 1;
+    const SPILL: bool = true;
+}
+
+/// Send the incoming item straight to the sink, keeping the buffered items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpillNewest;
+
+impl OverflowPolicy for SpillNewest {
+    const EVICT: usize = 0;
+    const SPILL: bool = true;
+}
+
+/// Evict the `K` oldest items in a single [`send_all`](spout::Spout::send_all).
+///
+/// Amortises per-call sink cost at the price of freeing more room than
+/// strictly needed. `K` must be between 1 and the ring capacity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpillBatch<const K: usize>;
+
+impl<const K: usize> OverflowPolicy for SpillBatch<K> {
+    const EVICT: usize = {
+        assert!(K > 0, "batch size must be > 0");
+        K
+    };
+    const SPILL: bool = true;
+}
+
+/// Drop the oldest item without touching the sink.
+///
+/// The sink still receives items from [`flush`](crate::SpillRing::flush)
+/// and on drop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Overwrite;
+
+impl OverflowPolicy for Overwrite {
+    const EVICT: usize = 1;
+    const SPILL: bool = false;
+}
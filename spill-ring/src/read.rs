@@ -4,10 +4,19 @@
 //! producer can invalidate references.
 
 use crate::iter::SpillRingIter;
+use crate::policy::OverflowPolicy;
 use crate::ring::SpillRing;
+use crate::stats::StatsLayer;
 use spout::Spout;
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRing<T, N, S> {
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRing<T, N, S, P, M>
+{
     /// Peek at the oldest item.
     #[inline]
     #[must_use]
@@ -58,7 +67,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
 
     /// Iterate oldest to newest.
     #[inline]
-    pub fn iter(&self) -> SpillRingIter<'_, T, N, S> {
+    pub fn iter(&self) -> SpillRingIter<'_, T, N, S, P, M> {
         SpillRingIter::new(self)
     }
 }
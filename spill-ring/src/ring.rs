@@ -1,13 +1,15 @@
 //! Ring buffer with overflow spilling to a spout.
 
-use core::{cell::UnsafeCell, mem::MaybeUninit};
+use core::{cell::UnsafeCell, marker::PhantomData, mem::MaybeUninit};
 
 use crate::{
     index::{CellIndex, SpoutCell},
     iter::SpillRingIterMut,
-    traits::{RingConsumer, RingInfo, RingProducer},
+    policy::{OverflowPolicy, SpillOldest},
+    stats::{EnabledStats, NoStats, RingStats, StatsLayer},
+    traits::{RingConsumer, RingInfo, RingProducer, RingStatsInfo},
 };
-use spout::{DropSpout, Spout};
+use spout::{DropSpout, Source, Spout};
 
 /// Slot wrapper holding one item in the ring buffer.
 ///
@@ -34,17 +36,42 @@ pub(crate) const MAX_CAPACITY: usize = 1 << 20;
 ///
 /// Single-threaded ring using `Cell`-based indices. For multi-threaded use,
 /// see [`MpscRing`](crate::MpscRing).
+///
+/// The sink must be infallible. Wrap fallible sinks (files, sockets) in
+/// [`DeadLetterSpout`](spout::DeadLetterSpout) or
+/// [`ErrorCaptureSpout`](spout::ErrorCaptureSpout) so failures are
+/// recorded instead of silently dropped.
+///
+/// What happens on overflow is chosen by the policy `P` (see
+/// [`OverflowPolicy`]), set with [`SpillRingBuilder::policy`](crate::SpillRingBuilder::policy).
+/// The default, [`SpillOldest`], evicts the oldest item to the sink.
+///
+/// Usage counters are opt-in through the stats layer `M` (see
+/// [`StatsLayer`]), set with [`SpillRingBuilder::stats`](crate::SpillRingBuilder::stats).
+/// The default, [`NoStats`], adds no fields and no work.
 #[repr(C)]
-pub struct SpillRing<T, const N: usize, S: Spout<T, Error = core::convert::Infallible> = DropSpout>
-{
+pub struct SpillRing<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    P: OverflowPolicy = SpillOldest,
+    M: StatsLayer = NoStats,
+> {
     pub(crate) head: CellIndex,
     pub(crate) tail: CellIndex,
     pub(crate) buffer: [Slot<T>; N],
     sink: SpoutCell<S>,
+    pub(crate) stats: M,
+    _policy: PhantomData<P>,
 }
 
-unsafe impl<T: Send, const N: usize, S: Spout<T, Error = core::convert::Infallible> + Send> Send
-    for SpillRing<T, N, S>
+unsafe impl<
+    T: Send,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> + Send,
+    P: OverflowPolicy,
+    M: StatsLayer + Send,
+> Send for SpillRing<T, N, S, P, M>
 {
 }
 
@@ -88,6 +115,8 @@ impl<T, const N: usize> SpillRing<T, N, DropSpout> {
             tail: CellIndex::new(0),
             buffer: [const { Slot::new() }; N],
             sink: SpoutCell::new(DropSpout),
+            stats: NoStats,
+            _policy: PhantomData,
         }
     }
 }
@@ -96,24 +125,43 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
     /// Create a new ring buffer with pre-warmed cache and a custom spout.
     #[must_use]
     pub fn with_sink(sink: S) -> Self {
-        let mut ring = Self::with_sink_cold(sink);
-        ring.warm();
-        ring
+        Self::from_sink(sink, true)
     }
 
     /// Create a new ring buffer with a custom spout, without cache warming.
     #[must_use]
     pub fn with_sink_cold(sink: S) -> Self {
+        Self::from_sink(sink, false)
+    }
+}
+
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRing<T, N, S, P, M>
+{
+    /// Construct with any policy. Used by the builder.
+    pub(crate) fn from_sink(sink: S, warm: bool) -> Self {
         const { assert!(N > 0, "capacity must be > 0") };
         const { assert!(N.is_power_of_two(), "capacity must be power of two") };
         const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+        const { assert!(P::EVICT <= N, "eviction batch exceeds capacity") };
 
-        Self {
+        let mut ring = Self {
             head: CellIndex::new(0),
             tail: CellIndex::new(0),
             buffer: [const { Slot::new() }; N],
             sink: SpoutCell::new(sink),
+            stats: M::default(),
+            _policy: PhantomData,
+        };
+        if warm {
+            ring.warm();
         }
+        ring
     }
 
     /// Bring all ring slots into L1/L2 cache.
@@ -129,7 +177,8 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         self.tail.store_mut(0);
     }
 
-    /// Push an item. If full, evicts oldest to spout.
+    /// Push an item. If full, applies the overflow policy (by default,
+    /// evicts the oldest to the spout).
     ///
     /// Uses interior mutability (`Cell`). Not thread-safe.
     #[inline]
@@ -138,15 +187,24 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         let head = self.head.load();
 
         if tail.wrapping_sub(head) >= N {
-            let evict_idx = head & (N - 1);
-            let evicted = unsafe { (*self.buffer[evict_idx].data.get()).assume_init_read() };
-            self.head.store(head.wrapping_add(1));
-            let _ = unsafe { self.sink.get_mut_unchecked().send(evicted) };
+            // SAFETY: SpillRing is !Sync and no sink borrow outlives a call.
+            let sink = unsafe { self.sink.get_mut_unchecked() };
+            if P::EVICT == 0 {
+                self.stats.record_push(1, N);
+                self.stats.record_evict(1);
+                Self::reject(sink, item);
+                return;
+            }
+            self.head.store(head.wrapping_add(P::EVICT));
+            self.stats.record_evict(P::EVICT);
+            unsafe { Self::evict(&self.buffer, head, sink) };
         }
 
         let idx = tail & (N - 1);
         unsafe { (*self.buffer[idx].data.get()).write(item) };
         self.tail.store(tail.wrapping_add(1));
+        self.stats
+            .record_push(1, tail.wrapping_add(1).wrapping_sub(self.head.load()));
     }
 
     /// Push an item with exclusive access (no `Cell` overhead).
@@ -156,15 +214,52 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         let head = self.head.load_mut();
 
         if tail.wrapping_sub(head) >= N {
-            let evict_idx = head & (N - 1);
-            let evicted = unsafe { (*self.buffer[evict_idx].data.get()).assume_init_read() };
-            self.head.store_mut(head.wrapping_add(1));
-            let _ = self.sink.get_mut().send(evicted);
+            if P::EVICT == 0 {
+                self.stats.record_push(1, N);
+                self.stats.record_evict(1);
+                Self::reject(self.sink.get_mut(), item);
+                return;
+            }
+            self.head.store_mut(head.wrapping_add(P::EVICT));
+            self.stats.record_evict(P::EVICT);
+            unsafe { Self::evict(&self.buffer, head, self.sink.get_mut()) };
         }
 
         let idx = tail & (N - 1);
         unsafe { (*self.buffer[idx].data.get()).write(item) };
         self.tail.store_mut(tail.wrapping_add(1));
+        self.stats
+            .record_push(1, tail.wrapping_add(1).wrapping_sub(self.head.load_mut()));
+    }
+
+    /// Handle an incoming item rejected by the policy.
+    #[inline(always)]
+    fn reject(sink: &mut S, item: T) {
+        if P::SPILL {
+            let _ = sink.send(item);
+        }
+    }
+
+    /// Move `P::EVICT` items starting at `head` out of the buffer, sending
+    /// them to the sink or dropping them per the policy.
+    ///
+    /// # Safety
+    /// The slots must be initialized, and the caller must already have
+    /// advanced head past them so a panicking sink cannot cause a double read.
+    #[inline(always)]
+    unsafe fn evict(buffer: &[Slot<T>; N], head: usize, sink: &mut S) {
+        let read = |i: usize| unsafe {
+            (*buffer[head.wrapping_add(i) & (N - 1)].data.get()).assume_init_read()
+        };
+        if !P::SPILL {
+            for i in 0..P::EVICT {
+                drop(read(i));
+            }
+        } else if P::EVICT == 1 {
+            let _ = sink.send(read(0));
+        } else {
+            let _ = sink.send_all((0..P::EVICT).map(read));
+        }
     }
 
     /// Pop the oldest item with exclusive access (no `Cell` overhead).
@@ -181,6 +276,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         let idx = head & (N - 1);
         let item = unsafe { (*self.buffer[idx].data.get()).assume_init_read() };
         self.head.store_mut(head.wrapping_add(1));
+        self.stats.record_pop(1);
         Some(item)
     }
 
@@ -190,6 +286,9 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
     /// Items that overflow the ring are evicted to the spout. If the slice
     /// is larger than the ring capacity, excess items go directly to the spout
     /// without touching the buffer.
+    ///
+    /// The bulk path applies to [`SpillOldest`]; other policies push item by
+    /// item so overflow follows the policy.
     #[inline]
     pub fn push_slice(&mut self, items: &[T])
     where
@@ -198,6 +297,12 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         if items.is_empty() {
             return;
         }
+        if P::EVICT != 1 || !P::SPILL {
+            for &item in items {
+                self.push_mut(item);
+            }
+            return;
+        }
 
         let mut tail = self.tail.load_mut();
         let mut head = self.head.load_mut();
@@ -215,6 +320,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
             for &item in &items[..excess] {
                 let _ = self.sink.get_mut().send(item);
             }
+            self.stats.record_evict(len + excess);
             head = head.wrapping_add(len);
             tail = head;
             self.head.store_mut(head);
@@ -237,6 +343,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
                     (*self.buffer[(h.wrapping_add(i)) & (N - 1)].data.get()).assume_init_read()
                 }));
             self.head.store_mut(head.wrapping_add(evict_count));
+            self.stats.record_evict(evict_count);
         }
 
         // Bulk memcpy (at most 2 segments)
@@ -259,6 +366,10 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         }
 
         self.tail.store_mut(tail.wrapping_add(count));
+        self.stats.record_push(
+            items.len(),
+            tail.wrapping_add(count).wrapping_sub(self.head.load_mut()),
+        );
     }
 
     /// Bulk-extend from a slice. Equivalent to `push_slice`.
@@ -309,6 +420,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         }
 
         self.head.store_mut(head.wrapping_add(count));
+        self.stats.record_pop(count);
         count
     }
 
@@ -325,6 +437,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
     /// in the spout will not cause double-reads during drop.
     #[inline]
     pub fn flush(&mut self) -> usize {
+        self.stats.record_flush();
         let head = self.head.load_mut();
         let tail = self.tail.load_mut();
         let count = tail.wrapping_sub(head);
@@ -360,6 +473,7 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
         let idx = head & (N - 1);
         let item = unsafe { (*self.buffer[idx].data.get()).assume_init_read() };
         self.head.store(head.wrapping_add(1));
+        self.stats.record_pop(1);
         Some(item)
     }
 
@@ -426,25 +540,62 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpillRin
 
     /// Iterate mutably, oldest to newest.
     #[inline]
-    pub fn iter_mut(&mut self) -> SpillRingIterMut<'_, T, N, S> {
+    pub fn iter_mut(&mut self) -> SpillRingIterMut<'_, T, N, S, P, M> {
         SpillRingIterMut::new(self)
     }
 
     /// Drain all items from the ring, returning an iterator.
     /// Items are removed oldest to newest.
     #[inline]
-    pub fn drain(&mut self) -> Drain<'_, T, N, S> {
+    pub fn drain(&mut self) -> Drain<'_, T, N, S, P, M> {
         Drain { ring: self }
     }
+
+    /// Zero the usage counters.
+    #[inline]
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
+}
+
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: EnabledStats,
+> SpillRing<T, N, S, P, M>
+{
+    /// Usage counters recorded by the stats layer.
+    ///
+    /// Only available for rings built with a stats layer such as
+    /// [`Counters`](crate::Counters).
+    #[inline]
+    #[must_use]
+    pub fn stats(&self) -> RingStats {
+        self.stats.snapshot()
+    }
 }
 
 /// Draining iterator over a SpillRing.
-pub struct Drain<'a, T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> {
-    ring: &'a mut SpillRing<T, N, S>,
+pub struct Drain<
+    'a,
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy = SpillOldest,
+    M: StatsLayer = NoStats,
+> {
+    ring: &'a mut SpillRing<T, N, S, P, M>,
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iterator
-    for Drain<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Iterator for Drain<'_, T, N, S, P, M>
 {
     type Item = T;
 
@@ -460,13 +611,23 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Iterator
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> ExactSizeIterator
-    for Drain<'_, T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> ExactSizeIterator for Drain<'_, T, N, S, P, M>
 {
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> core::iter::Extend<T>
-    for SpillRing<T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> core::iter::Extend<T> for SpillRing<T, N, S, P, M>
 {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for item in iter {
@@ -482,8 +643,13 @@ impl<T, const N: usize> Default for SpillRing<T, N, DropSpout> {
 }
 
 /// SpillRing can act as a Spout, enabling ring chaining (ring1 -> ring2).
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Spout<T>
-    for SpillRing<T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Spout<T> for SpillRing<T, N, S, P, M>
 {
     type Error = core::convert::Infallible;
 
@@ -500,8 +666,32 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Spout<T>
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
-    for SpillRing<T, N, S>
+/// SpillRing can act as a Source, draining oldest to newest.
+///
+/// The source is exhausted when the ring is empty.
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Source<T> for SpillRing<T, N, S, P, M>
+{
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(self.pop_mut())
+    }
+}
+
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> Drop for SpillRing<T, N, S, P, M>
 {
     fn drop(&mut self) {
         self.flush();
@@ -509,8 +699,13 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingInfo
-    for SpillRing<T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> RingInfo for SpillRing<T, N, S, P, M>
 {
     #[inline]
     fn len(&self) -> usize {
@@ -523,8 +718,27 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingInfo
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingProducer<T>
-    for SpillRing<T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: EnabledStats,
+> RingStatsInfo for SpillRing<T, N, S, P, M>
+{
+    #[inline]
+    fn stats(&self) -> RingStats {
+        SpillRing::stats(self)
+    }
+}
+
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> RingProducer<T> for SpillRing<T, N, S, P, M>
 {
     #[inline]
     fn try_push(&mut self, item: T) -> Result<(), crate::PushError<T>> {
@@ -540,13 +754,20 @@ impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingProd
             (*slot.data.get()).write(item);
         }
         self.tail.store_mut(tail.wrapping_add(1));
+        self.stats
+            .record_push(1, tail.wrapping_add(1).wrapping_sub(head));
 
         Ok(())
     }
 }
 
-impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingConsumer<T>
-    for SpillRing<T, N, S>
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> RingConsumer<T> for SpillRing<T, N, S, P, M>
 {
     #[inline]
     fn try_pop(&mut self) -> Option<T> {
@@ -0,0 +1,792 @@
+//! Cross-process SPSC ring in a shared memory mapping (Linux).
+//!
+//! [`ShmRing`] lays out the same stamped slots as [`SpscRing`](crate::SpscRing)
+//! in a file (or memfd) that both processes map with `MAP_SHARED`. The
+//! producer creates and initializes the mapping; a consumer in another
+//! process attaches to it by path or by an open file.
+//!
+//! The mapping starts with a header:
+//!
+//! | field | type |
+//! |-------|------|
+//! | magic `b"SPRGSHM\0"`, written last | `AtomicU64` |
+//! | format version | `u32` |
+//! | capacity | `u64` |
+//! | item size | `u64` |
+//! | item alignment | `u64` |
+//! | producer closed | `AtomicU32` |
+//! | head | `AtomicU64`, own cache line |
+//! | tail | `AtomicU64`, own cache line |
+//!
+//! followed by `N` slots, each a `u64` stamp and the item. Attaching
+//! checks the magic, version and layout against the consumer's `T` and
+//! `N`, so a consumer built against a different item type or capacity is
+//! refused rather than misreading the slots.
+//!
+//! Unlike the in-process ring, the peer can die at any point. A consumer
+//! killed after claiming an item but before freeing its slot would leave
+//! the producer waiting on that slot forever, so the producer only waits
+//! for a [reclaim timeout](ShmProducer::set_reclaim_timeout) and then takes
+//! the slot back, spilling the item. Freeing a slot is a compare-exchange
+//! on its stamp on both sides, so a consumer that was merely slow loses
+//! the race and discards its copy: the item is still popped or spilled
+//! exactly once.
+
+use core::marker::PhantomData;
+use core::mem::{MaybeUninit, align_of, size_of};
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::ffi::CString;
+use std::fs::{self, File};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bytecast::{IntoBytes, ZcFromBytes, ZeroCopyType};
+use spout::{DropSpout, Source, Spout};
+
+use crate::index::CachePadded;
+use crate::ring::MAX_CAPACITY;
+use crate::spsc::backoff;
+use crate::traits::{RingInfo, RingProducer};
+
+/// Stored last during initialization, so it also marks the ring ready.
+const MAGIC: u64 = u64::from_le_bytes(*b"SPRGSHM\0");
+const VERSION: u32 = 1;
+const DEFAULT_RECLAIM_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[repr(C)]
+struct Header {
+    magic: AtomicU64,
+    version: u32,
+    capacity: u64,
+    item_size: u64,
+    item_align: u64,
+    producer_closed: AtomicU32,
+    /// Position of the oldest item. Advanced by the consumer on pop and by
+    /// the producer on eviction, always with a compare-exchange.
+    head: CachePadded<AtomicU64>,
+    /// Position of the next push. Only written by the producer.
+    tail: CachePadded<AtomicU64>,
+}
+
+/// One slot; the stamp works as in [`SpscRing`](crate::SpscRing).
+#[repr(C)]
+struct Slot<T> {
+    stamp: AtomicU64,
+    value: core::cell::UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Stamp of a slot that is free to be written for position `pos`.
+#[inline]
+const fn empty(pos: u64) -> u64 {
+    pos.wrapping_mul(2)
+}
+
+/// Stamp of a slot holding the item for position `pos`.
+#[inline]
+const fn full(pos: u64) -> u64 {
+    pos.wrapping_mul(2).wrapping_add(1)
+}
+
+/// Offset of the first slot from the start of the mapping.
+const fn slots_offset<T>() -> usize {
+    size_of::<Header>().next_multiple_of(align_of::<Slot<T>>())
+}
+
+/// Bytes needed for the header and `N` slots.
+const fn mapping_len<T, const N: usize>() -> usize {
+    slots_offset::<T>() + N * size_of::<Slot<T>>()
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A `MAP_SHARED` mapping of a whole file, unmapped on drop.
+struct Mapping {
+    ptr: NonNull<u8>,
+    len: usize,
+    file: File,
+}
+
+impl Mapping {
+    fn new(file: File, len: usize) -> io::Result<Self> {
+        // SAFETY: a fresh shared mapping of an open file; the kernel picks
+        // the address.
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            ptr: NonNull::new(ptr.cast()).expect("mmap returned null"),
+            len,
+            file,
+        })
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: unmaps exactly the region mapped in `new`.
+        unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+    }
+}
+
+/// One process's view of the ring.
+struct Shared<T, const N: usize> {
+    map: Mapping,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: the mapping is only reached through atomics and the slot
+// protocol, the same as `SpscRing`'s heap buffer.
+unsafe impl<T: Send, const N: usize> Send for Shared<T, N> {}
+
+impl<T, const N: usize> Shared<T, N> {
+    #[inline]
+    fn header(&self) -> &Header {
+        // SAFETY: the mapping is page-aligned and at least header-sized.
+        unsafe { &*self.map.ptr.as_ptr().cast::<Header>() }
+    }
+
+    #[inline]
+    fn slot(&self, pos: u64) -> &Slot<T> {
+        let index = (pos & (N as u64 - 1)) as usize;
+        // SAFETY: the mapping holds `N` slots after the header, and
+        // `index < N`.
+        unsafe {
+            &*self
+                .map
+                .ptr
+                .as_ptr()
+                .add(slots_offset::<T>())
+                .cast::<Slot<T>>()
+                .add(index)
+        }
+    }
+
+    /// Write the header and slot stamps of a freshly sized (zeroed)
+    /// mapping, then publish the magic.
+    fn init(map: Mapping) -> Self {
+        let header = map.ptr.as_ptr().cast::<Header>();
+        // SAFETY: attaching processes read nothing but the magic until it
+        // is published, and the zeroed header is a valid `Header`.
+        unsafe {
+            (&raw mut (*header).version).write(VERSION);
+            (&raw mut (*header).capacity).write(N as u64);
+            (&raw mut (*header).item_size).write(size_of::<T>() as u64);
+            (&raw mut (*header).item_align).write(align_of::<T>() as u64);
+        }
+        let shared = Self {
+            map,
+            _marker: PhantomData,
+        };
+        for pos in 0..N as u64 {
+            shared.slot(pos).stamp.store(empty(pos), Ordering::Relaxed);
+        }
+        shared.header().magic.store(MAGIC, Ordering::Release);
+        shared
+    }
+
+    /// Check that an existing mapping is a ready ring of `T` with `N` slots.
+    fn validate(map: Mapping) -> io::Result<Self> {
+        if map.len < size_of::<Header>() {
+            return Err(invalid("file is too small for a shared ring"));
+        }
+        let shared = Self {
+            map,
+            _marker: PhantomData,
+        };
+        let header = shared.header();
+        // The other fields are only settled once the magic is published.
+        match header.magic.load(Ordering::Acquire) {
+            MAGIC => {}
+            0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "shared ring is still being initialized",
+                ));
+            }
+            _ => return Err(invalid("not a shared ring")),
+        }
+        if header.version != VERSION {
+            return Err(invalid("unsupported shared ring version"));
+        }
+        if header.capacity != N as u64
+            || header.item_size != size_of::<T>() as u64
+            || header.item_align != align_of::<T>() as u64
+        {
+            return Err(invalid("shared ring layout does not match"));
+        }
+        if shared.map.len < mapping_len::<T, N>() {
+            return Err(invalid("file is too small for a shared ring"));
+        }
+        Ok(shared)
+    }
+
+    /// Try to remove the item at `head`.
+    ///
+    /// Returns `Err(true)` if the ring is empty, `Err(false)` if the
+    /// producer raced ahead or reclaimed the slot and the caller should
+    /// retry.
+    #[inline]
+    fn take_head(&self, head: u64) -> Result<T, bool> {
+        let slot = self.slot(head);
+        let stamp = slot.stamp.load(Ordering::Acquire);
+        if stamp == full(head) {
+            if self
+                .header()
+                .head
+                .compare_exchange_weak(
+                    head,
+                    head.wrapping_add(1),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                // SAFETY: the stamp says the slot holds the item for `head`,
+                // and winning the exchange makes us its only reader. A
+                // producer that reclaims the slot may overwrite it while we
+                // copy; `T` is plain data, so a torn copy is still a valid
+                // `T`, and it is discarded below.
+                let item = unsafe { (*slot.value.get()).assume_init_read() };
+                if slot
+                    .stamp
+                    .compare_exchange(
+                        full(head),
+                        empty(head.wrapping_add(N as u64)),
+                        Ordering::AcqRel,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    return Ok(item);
+                }
+                // The producer gave up on us and spilled the item.
+                core::mem::forget(item);
+            }
+            Err(false)
+        } else {
+            Err(stamp == empty(head))
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        let head = self.header().head.load(Ordering::Acquire);
+        let tail = self.header().tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head).min(N as u64) as usize
+    }
+}
+
+/// Single-producer, single-consumer ring shared between processes.
+///
+/// [`ShmRing::create`] sizes a file, maps it and returns the
+/// [`ShmProducer`]; [`ShmRing::attach`] maps the same file in another
+/// process and returns the [`ShmConsumer`]. The two run concurrently with
+/// the same protocol as [`SpscRing`](crate::SpscRing): pushing into a full
+/// ring evicts the oldest item to the producer's sink, and an item is
+/// either popped or spilled, never both.
+///
+/// Items are copied in and out of the mapping as bytes, so `T` must be a
+/// plain [`ZeroCopyType`]. They are never dropped in place. `N` must be a
+/// power of two no larger than 2^20.
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::ShmRing;
+///
+/// let path = std::env::temp_dir().join(format!("spill-ring-shm-doc-{}", std::process::id()));
+/// let mut producer = ShmRing::<u64, 8>::create(&path).unwrap();
+/// producer.push(1);
+/// producer.push(2);
+///
+/// // Normally in another process:
+/// let mut consumer = ShmRing::<u64, 8>::attach(&path).unwrap();
+/// assert_eq!(consumer.pop(), Some(1));
+/// assert_eq!(consumer.pop(), Some(2));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct ShmRing<T, const N: usize> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: ZeroCopyType + IntoBytes + ZcFromBytes, const N: usize> ShmRing<T, N> {
+    /// Create a ring in a new file at `path` whose evicted items are
+    /// dropped.
+    ///
+    /// A file already at `path` is replaced; consumers attached to it keep
+    /// the old ring.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created, sized or mapped.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn create(path: impl AsRef<Path>) -> io::Result<ShmProducer<T, N>> {
+        Self::create_with_sink(path, DropSpout)
+    }
+
+    /// Create a ring in a new file at `path` whose evicted items are sent
+    /// to `sink`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`create`](Self::create).
+    pub fn create_with_sink<S: Spout<T, Error = core::convert::Infallible>>(
+        path: impl AsRef<Path>,
+        sink: S,
+    ) -> io::Result<ShmProducer<T, N, S>> {
+        let path = path.as_ref();
+        // Unlink rather than truncate: shrinking a file under a live
+        // mapping faults the process using it.
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        Self::create_in(file, sink)
+    }
+
+    /// Create a ring in an anonymous memfd named `name` whose evicted
+    /// items are sent to `sink`.
+    ///
+    /// Share it through [`ShmProducer::file`]: pass the descriptor to a
+    /// child process, or have another process open
+    /// `/proc/<pid>/fd/<fd>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` contains a NUL byte or the memfd cannot
+    /// be created, sized or mapped.
+    pub fn create_memfd<S: Spout<T, Error = core::convert::Infallible>>(
+        name: &str,
+        sink: S,
+    ) -> io::Result<ShmProducer<T, N, S>> {
+        let name = CString::new(name).map_err(|_| invalid("memfd name contains a NUL byte"))?;
+        // SAFETY: `name` is a valid C string.
+        let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a new descriptor that nothing else owns.
+        Self::create_in(unsafe { File::from_raw_fd(fd) }, sink)
+    }
+
+    fn create_in<S: Spout<T, Error = core::convert::Infallible>>(
+        file: File,
+        sink: S,
+    ) -> io::Result<ShmProducer<T, N, S>> {
+        const { assert!(N > 0, "capacity must be > 0") };
+        const { assert!(N.is_power_of_two(), "capacity must be power of two") };
+        const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+
+        let len = mapping_len::<T, N>();
+        file.set_len(len as u64)?;
+        Ok(ShmProducer {
+            shared: Shared::init(Mapping::new(file, len)?),
+            tail: 0,
+            sink,
+            reclaim_timeout: DEFAULT_RECLAIM_TIMEOUT,
+        })
+    }
+
+    /// Attach a consumer to the ring in the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped,
+    /// [`ErrorKind::WouldBlock`](io::ErrorKind::WouldBlock) if the producer
+    /// has not finished initializing it, and
+    /// [`ErrorKind::InvalidData`](io::ErrorKind::InvalidData) if it is not
+    /// a ring, was written by an unknown format version, or holds a
+    /// different item size, alignment or capacity.
+    pub fn attach(path: impl AsRef<Path>) -> io::Result<ShmConsumer<T, N>> {
+        Self::attach_file(File::options().read(true).write(true).open(path)?)
+    }
+
+    /// Attach a consumer to the ring in an open file, such as an inherited
+    /// memfd. The file must be open for reading and writing.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`attach`](Self::attach).
+    pub fn attach_file(file: File) -> io::Result<ShmConsumer<T, N>> {
+        const { assert!(N > 0, "capacity must be > 0") };
+        const { assert!(N.is_power_of_two(), "capacity must be power of two") };
+        const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| invalid("file is too large to map"))?;
+        if len < size_of::<Header>() {
+            return Err(invalid("file is too small for a shared ring"));
+        }
+        Ok(ShmConsumer {
+            shared: Shared::validate(Mapping::new(file, len)?)?,
+        })
+    }
+}
+
+/// Producer half of a [`ShmRing`]. Owns the mapping's initialization.
+///
+/// When dropped, buffered items stay in the mapping for the consumer, the
+/// ring is marked closed, and the sink is flushed.
+pub struct ShmProducer<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+> {
+    shared: Shared<T, N>,
+    /// Local copy of the shared tail; only this half writes it.
+    tail: u64,
+    sink: S,
+    reclaim_timeout: Duration,
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> ShmProducer<T, N, S> {
+    /// Push an item. If full, evicts the oldest item to the sink.
+    ///
+    /// Waits if the consumer is in the middle of popping the slot being
+    /// reused, for at most the [reclaim
+    /// timeout](Self::set_reclaim_timeout).
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        let tail = self.tail;
+        let slot = self.shared.slot(tail);
+        let mut evicted = None;
+        let mut step = 0;
+        let mut stalled: Option<Instant> = None;
+        loop {
+            if slot.stamp.load(Ordering::Acquire) == empty(tail) {
+                break;
+            }
+            // Full: claim the item from one lap ago unless the consumer
+            // got there first.
+            let oldest = tail.wrapping_sub(N as u64);
+            if self
+                .shared
+                .header()
+                .head
+                .compare_exchange(
+                    oldest,
+                    oldest.wrapping_add(1),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                // SAFETY: the slot holds the item for `oldest`, and winning
+                // the exchange makes us its only reader.
+                evicted = Some(unsafe { (*slot.value.get()).assume_init_read() });
+                break;
+            }
+            // The consumer claimed the item and has not freed the slot yet.
+            // Past the timeout, assume it died and take the slot back.
+            if stalled.get_or_insert_with(Instant::now).elapsed() >= self.reclaim_timeout
+                && slot
+                    .stamp
+                    .compare_exchange(
+                        full(oldest),
+                        empty(tail),
+                        Ordering::AcqRel,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                // SAFETY: the slot still held the item for `oldest`, and
+                // winning the exchange means the consumer will discard its
+                // copy. Consumers only read the value, so it is intact.
+                evicted = Some(unsafe { (*slot.value.get()).assume_init_read() });
+                break;
+            }
+            backoff(&mut step);
+        }
+
+        // SAFETY: the slot is empty and the consumer will not touch it
+        // until the stamp is published.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.stamp.store(full(tail), Ordering::Release);
+        self.tail = tail.wrapping_add(1);
+        self.shared
+            .header()
+            .tail
+            .store(self.tail, Ordering::Release);
+
+        if let Some(evicted) = evicted {
+            let _ = self.sink.send(evicted);
+        }
+    }
+
+    /// Send every buffered item to the sink. Returns count flushed.
+    ///
+    /// Items the consumer pops concurrently are not counted.
+    pub fn flush(&mut self) -> usize {
+        let mut count = 0;
+        let mut step = 0;
+        loop {
+            let head = self.shared.header().head.load(Ordering::Acquire);
+            if head == self.tail {
+                return count;
+            }
+            match self.shared.take_head(head) {
+                Ok(item) => {
+                    count += 1;
+                    let _ = self.sink.send(item);
+                }
+                Err(_) => backoff(&mut step),
+            }
+        }
+    }
+
+    /// Number of items in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// True if empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// True if full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() >= N
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// How long [`push`](Self::push) waits for the consumer to free a slot
+    /// it is popping before taking it back (default 100 ms).
+    ///
+    /// A consumer that dies mid-pop never frees its slot, so without a
+    /// bound the producer would wait forever. A live consumer stalled for
+    /// longer than this (descheduled, stopped in a debugger) loses the
+    /// item it was popping to the sink instead.
+    #[inline]
+    pub fn set_reclaim_timeout(&mut self, timeout: Duration) {
+        self.reclaim_timeout = timeout;
+    }
+
+    /// The current reclaim timeout.
+    #[inline]
+    pub fn reclaim_timeout(&self) -> Duration {
+        self.reclaim_timeout
+    }
+
+    /// The mapped file, e.g. to hand a memfd to another process.
+    #[inline]
+    pub fn file(&self) -> &File {
+        &self.shared.map.file
+    }
+
+    /// Reference to the sink.
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable reference to the sink.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
+    for ShmProducer<T, N, S>
+{
+    fn drop(&mut self) {
+        self.shared
+            .header()
+            .producer_closed
+            .store(1, Ordering::Release);
+        let _ = self.sink.flush();
+    }
+}
+
+/// ShmProducer can act as a Spout, so it can sit at the end of a chain.
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Spout<T>
+    for ShmProducer<T, N, S>
+{
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.push(item);
+        Ok(())
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingInfo
+    for ShmProducer<T, N, S>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        ShmProducer::len(self)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingProducer<T>
+    for ShmProducer<T, N, S>
+{
+    #[inline]
+    fn try_push(&mut self, item: T) -> Result<(), crate::PushError<T>> {
+        let tail = self.tail;
+        let slot = self.shared.slot(tail);
+        if slot.stamp.load(Ordering::Acquire) != empty(tail) {
+            return Err(crate::PushError::Full(item));
+        }
+        // SAFETY: as in `push`.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.stamp.store(full(tail), Ordering::Release);
+        self.tail = tail.wrapping_add(1);
+        self.shared
+            .header()
+            .tail
+            .store(self.tail, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Consumer half of a [`ShmRing`], usually in another process.
+pub struct ShmConsumer<T, const N: usize> {
+    shared: Shared<T, N>,
+}
+
+impl<T, const N: usize> ShmConsumer<T, N> {
+    /// Pop the oldest item, or `None` if the ring is empty right now.
+    #[inline]
+    #[must_use]
+    pub fn pop(&mut self) -> Option<T> {
+        let mut step = 0;
+        loop {
+            let head = self.shared.header().head.load(Ordering::Acquire);
+            match self.shared.take_head(head) {
+                Ok(item) => return Some(item),
+                Err(true) => return None,
+                Err(false) => backoff(&mut step),
+            }
+        }
+    }
+
+    /// Claim the oldest item and stop, as a consumer killed mid-pop would.
+    #[cfg(test)]
+    pub(crate) fn abandon_pop(&mut self) {
+        let head = self.shared.header().head.load(Ordering::Acquire);
+        self.shared
+            .header()
+            .head
+            .store(head.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Pop items until the ring is empty.
+    #[inline]
+    pub fn drain(&mut self) -> ShmDrain<'_, T, N> {
+        ShmDrain { consumer: self }
+    }
+
+    /// True once the producer has been dropped. Items may still be
+    /// buffered.
+    #[inline]
+    pub fn is_producer_closed(&self) -> bool {
+        self.shared.header().producer_closed.load(Ordering::Acquire) != 0
+    }
+
+    /// Number of items in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// True if empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> RingInfo for ShmConsumer<T, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        ShmConsumer::len(self)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+/// ShmConsumer can act as a Source.
+///
+/// `recv` waits until an item arrives and is exhausted once the producer
+/// is dropped and the ring is empty; `try_recv` never waits.
+impl<T, const N: usize> Source<T> for ShmConsumer<T, N> {
+    type Error = core::convert::Infallible;
+
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        loop {
+            if let Some(item) = self.pop() {
+                return Ok(Some(item));
+            }
+            if self.is_producer_closed() {
+                // The producer may have pushed just before closing.
+                return Ok(self.pop());
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(self.pop())
+    }
+}
+
+/// Draining iterator over a [`ShmConsumer`].
+pub struct ShmDrain<'a, T, const N: usize> {
+    consumer: &'a mut ShmConsumer<T, N>,
+}
+
+impl<T, const N: usize> Iterator for ShmDrain<'_, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.consumer.pop()
+    }
+}
@@ -0,0 +1,205 @@
+//! Snapshot and restore of ring contents through bytecast.
+//!
+//! A snapshot is a small header followed by the items, oldest first, each
+//! serialized with [`ToBytes`]:
+//!
+//! | bytes | field |
+//! |-------|-------|
+//! | 4 | magic `b"SPRG"` |
+//! | 1 | format version |
+//! | 4 | capacity of the source, little-endian `u32` |
+//! | 4 | item count, little-endian `u32` |
+//! | … | items |
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use bytecast::{BytesError, FromBytes, ToBytes, ToBytesExt};
+use spout::Spout;
+
+use crate::mpsc::Consumer;
+use crate::policy::OverflowPolicy;
+use crate::ring::SpillRing;
+use crate::stats::StatsLayer;
+
+const MAGIC: [u8; 4] = *b"SPRG";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 13;
+
+/// Header of a ring snapshot.
+///
+/// Read it with [`SnapshotInfo::read`] to inspect a snapshot without
+/// deserializing its items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// Capacity of the ring (or total capacity of the rings) the snapshot
+    /// was taken from.
+    pub capacity: usize,
+    /// Number of items in the snapshot.
+    pub len: usize,
+}
+
+impl SnapshotInfo {
+    /// Parse the header at the start of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BytesError::UnexpectedEof`] if `bytes` is shorter than the
+    /// header, and [`BytesError::InvalidData`] if it is not a snapshot or
+    /// was written by an unknown format version.
+    pub fn read(bytes: &[u8]) -> Result<Self, BytesError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(BytesError::UnexpectedEof {
+                needed: HEADER_LEN,
+                available: bytes.len(),
+            });
+        }
+        if bytes[..4] != MAGIC {
+            return Err(BytesError::InvalidData {
+                message: "not a ring snapshot",
+            });
+        }
+        if bytes[4] != VERSION {
+            return Err(BytesError::InvalidData {
+                message: "unsupported snapshot version",
+            });
+        }
+        let field = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]) as usize
+        };
+        Ok(Self {
+            capacity: field(5),
+            len: field(9),
+        })
+    }
+}
+
+/// Serialize `items` (oldest first) behind a header.
+fn write_snapshot<'a, T: ToBytes + 'a>(
+    capacity: usize,
+    len: usize,
+    items: impl Iterator<Item = &'a T>,
+) -> Result<Vec<u8>, BytesError> {
+    let count = |n: usize| {
+        u32::try_from(n).map_err(|_| BytesError::Custom {
+            message: "snapshot too large",
+        })
+    };
+    let mut out = Vec::with_capacity(HEADER_LEN + len * T::MAX_SIZE.unwrap_or(0));
+    out.extend_from_slice(&MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&count(capacity)?.to_le_bytes());
+    out.extend_from_slice(&count(len)?.to_le_bytes());
+    for item in items {
+        out.extend_from_slice(&item.to_vec()?);
+    }
+    Ok(out)
+}
+
+/// Deserialize every item of a snapshot, oldest first.
+fn read_snapshot<T: FromBytes>(bytes: &[u8]) -> Result<Vec<T>, BytesError> {
+    let info = SnapshotInfo::read(bytes)?;
+    let mut rest = &bytes[HEADER_LEN..];
+    // Each item takes at least one byte; don't trust the count further.
+    let mut items = Vec::with_capacity(info.len.min(rest.len()));
+    for _ in 0..info.len {
+        let (item, n) = T::from_bytes(rest)?;
+        items.push(item);
+        rest = &rest[n..];
+    }
+    if !rest.is_empty() {
+        return Err(BytesError::Custom {
+            message: "trailing bytes",
+        });
+    }
+    Ok(items)
+}
+
+impl<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+    M: StatsLayer,
+> SpillRing<T, N, S, P, M>
+{
+    /// Serialize the buffered items, oldest first, along with the ring's
+    /// capacity. The ring is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from serializing an item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spill_ring::{SnapshotInfo, SpillRing};
+    ///
+    /// let ring = SpillRing::<u32, 8>::new();
+    /// ring.push(1);
+    /// ring.push(2);
+    /// let bytes = ring.snapshot_to_bytes().unwrap();
+    /// assert_eq!(SnapshotInfo::read(&bytes).unwrap().capacity, 8);
+    ///
+    /// let restored = SpillRing::<u32, 8>::new();
+    /// restored.restore_from_bytes(&bytes).unwrap();
+    /// assert_eq!(restored.pop(), Some(1));
+    /// assert_eq!(restored.pop(), Some(2));
+    /// ```
+    pub fn snapshot_to_bytes(&self) -> Result<Vec<u8>, BytesError>
+    where
+        T: ToBytes,
+    {
+        write_snapshot(N, self.len(), self.iter())
+    }
+
+    /// Push every item of a snapshot, oldest first, and return how many
+    /// there were.
+    ///
+    /// Items go through [`push`](Self::push), so restoring more items than
+    /// fit (into a smaller ring, or one that is not empty) spills the
+    /// excess to the sink according to the overflow policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid snapshot or an item fails
+    /// to deserialize. The whole snapshot is decoded before anything is
+    /// pushed, so the ring is unchanged on error.
+    pub fn restore_from_bytes(&self, bytes: &[u8]) -> Result<usize, BytesError>
+    where
+        T: FromBytes,
+    {
+        let items = read_snapshot(bytes)?;
+        let count = items.len();
+        for item in items {
+            self.push(item);
+        }
+        Ok(count)
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>, M: StatsLayer>
+    Consumer<T, N, S, M>
+{
+    /// Serialize the items of every ring, in the order
+    /// [`drain`](Self::drain) would produce them: by producer, then FIFO.
+    ///
+    /// The recorded capacity is the total across rings. The snapshot has
+    /// the same format as [`SpillRing::snapshot_to_bytes`], so it can be
+    /// restored into a single ring with [`SpillRing::restore_from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from serializing an item.
+    pub fn snapshot_to_bytes(&self) -> Result<Vec<u8>, BytesError>
+    where
+        T: ToBytes,
+    {
+        write_snapshot(
+            N * self.num_producers(),
+            self.len(),
+            self.rings().iter().flat_map(|ring| ring.iter()),
+        )
+    }
+}
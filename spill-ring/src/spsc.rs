@@ -0,0 +1,513 @@
+//! Lock-free SPSC (Single-Producer, Single-Consumer) ring buffer.
+//!
+//! Unlike [`MpscRing`](crate::MpscRing), where the consumer only sees items
+//! once producers hand their rings back, the two halves of an [`SpscRing`]
+//! share one buffer: the consumer pops while the producer is still pushing.
+//! The producer keeps spill semantics — pushing into a full ring evicts the
+//! oldest item to its sink.
+//!
+//! # Example
+//!
+//! ```
+//! use spill_ring::SpscRing;
+//! use std::thread;
+//!
+//! let (mut producer, mut consumer) = SpscRing::<u64, 1024>::new();
+//!
+//! let handle = thread::spawn(move || {
+//!     for i in 0..10_000 {
+//!         producer.push(i);
+//!     }
+//! });
+//!
+//! let mut received = 0;
+//! while !(consumer.is_producer_closed() && consumer.is_empty()) {
+//!     if consumer.pop().is_some() {
+//!         received += 1;
+//!     }
+//! }
+//! handle.join().unwrap();
+//! assert!(received > 0);
+//! ```
+
+extern crate alloc;
+
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{
+    index::CachePadded,
+    ring::MAX_CAPACITY,
+    traits::{RingInfo, RingProducer},
+};
+use spout::{DropSpout, Source, Spout};
+
+/// One slot with a sequence stamp.
+///
+/// The stamp says what the slot holds relative to a ring position `p`
+/// that maps to it: `2p` means it is empty and may be written for `p`;
+/// `2p + 1` means it holds the item for `p`. Whoever removes the item sets
+/// it to `2(p + N)`, freeing it for the next lap. Doubling keeps the two
+/// states distinct even when `N` is 1.
+struct Slot<T> {
+    stamp: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Wait a little before retrying a contended operation.
+///
+/// Spins with exponential growth, then yields to the scheduler (under
+/// `std`) so a preempted peer can finish on a busy or single-core machine.
+#[inline]
+pub(crate) fn backoff(step: &mut u32) {
+    if *step < 6 {
+        for _ in 0..1u32 << *step {
+            core::hint::spin_loop();
+        }
+        *step += 1;
+    } else {
+        #[cfg(feature = "std")]
+        std::thread::yield_now();
+        #[cfg(not(feature = "std"))]
+        core::hint::spin_loop();
+    }
+}
+
+/// Stamp of a slot that is free to be written for position `pos`.
+#[inline]
+const fn empty(pos: usize) -> usize {
+    pos.wrapping_mul(2)
+}
+
+/// Stamp of a slot holding the item for position `pos`.
+#[inline]
+const fn full(pos: usize) -> usize {
+    pos.wrapping_mul(2).wrapping_add(1)
+}
+
+/// Buffer and indices shared by the two halves.
+struct Shared<T, const N: usize> {
+    /// Position of the oldest item. Advanced by the consumer on pop and by
+    /// the producer on eviction, always with a compare-exchange.
+    head: CachePadded<AtomicUsize>,
+    /// Position of the next push. Only written by the producer.
+    tail: CachePadded<AtomicUsize>,
+    producer_closed: AtomicBool,
+    slots: Box<[Slot<T>]>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for Shared<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for Shared<T, N> {}
+
+impl<T, const N: usize> Shared<T, N> {
+    fn new() -> Self {
+        Self {
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+            producer_closed: AtomicBool::new(false),
+            slots: (0..N)
+                .map(|i| Slot {
+                    stamp: AtomicUsize::new(empty(i)),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+        }
+    }
+
+    #[inline]
+    fn slot(&self, pos: usize) -> &Slot<T> {
+        &self.slots[pos & (N - 1)]
+    }
+
+    /// Try to remove the item at `head`.
+    ///
+    /// Returns `Err(true)` if the ring is empty, `Err(false)` if the
+    /// producer raced ahead and the caller should retry.
+    #[inline]
+    fn take_head(&self, head: usize) -> Result<T, bool> {
+        let slot = self.slot(head);
+        let stamp = slot.stamp.load(Ordering::Acquire);
+        if stamp == full(head) {
+            if self
+                .head
+                .compare_exchange_weak(
+                    head,
+                    head.wrapping_add(1),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                // SAFETY: the stamp says the slot holds the item for `head`,
+                // and winning the exchange makes us its only reader.
+                let item = unsafe { (*slot.value.get()).assume_init_read() };
+                slot.stamp
+                    .store(empty(head.wrapping_add(N)), Ordering::Release);
+                return Ok(item);
+            }
+            Err(false)
+        } else {
+            Err(stamp == empty(head))
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head).min(N)
+    }
+}
+
+impl<T, const N: usize> Drop for Shared<T, N> {
+    fn drop(&mut self) {
+        let head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        let mut pos = head;
+        while pos != tail {
+            let slot = &mut self.slots[pos & (N - 1)];
+            // SAFETY: both halves are gone, and `head..tail` are initialized.
+            unsafe { slot.value.get_mut().assume_init_drop() };
+            pos = pos.wrapping_add(1);
+        }
+    }
+}
+
+/// Lock-free single-producer, single-consumer ring buffer.
+///
+/// [`SpscRing::new`] and [`SpscRing::with_sink`] return a [`SpscProducer`]
+/// and a [`SpscConsumer`] that share an `N`-slot buffer and can live on
+/// different threads. Head and tail sit on separate cache lines, and each
+/// slot carries a sequence stamp so the producer can evict the oldest item
+/// while the consumer may be popping it: exactly one of them wins and the
+/// item is either popped or spilled, never both.
+///
+/// `N` must be a power of two no larger than 2^20, as for
+/// [`SpillRing`](crate::SpillRing).
+pub struct SpscRing<T, const N: usize> {
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T, const N: usize> SpscRing<T, N> {
+    /// Create a ring whose evicted items are dropped.
+    #[allow(clippy::new_ret_no_self)]
+    #[must_use]
+    pub fn new() -> (SpscProducer<T, N>, SpscConsumer<T, N>) {
+        Self::with_sink(DropSpout)
+    }
+
+    /// Create a ring whose evicted items are sent to `sink`.
+    ///
+    /// The sink belongs to the producer.
+    #[must_use]
+    pub fn with_sink<S: Spout<T, Error = core::convert::Infallible>>(
+        sink: S,
+    ) -> (SpscProducer<T, N, S>, SpscConsumer<T, N>) {
+        const { assert!(N > 0, "capacity must be > 0") };
+        const { assert!(N.is_power_of_two(), "capacity must be power of two") };
+        const { assert!(N <= MAX_CAPACITY, "capacity exceeds maximum (2^20)") };
+
+        let shared = Arc::new(Shared::new());
+        (
+            SpscProducer {
+                shared: Arc::clone(&shared),
+                tail: 0,
+                sink,
+            },
+            SpscConsumer { shared },
+        )
+    }
+}
+
+/// Producer half of an [`SpscRing`].
+///
+/// When dropped, buffered items stay in the ring for the consumer, and the
+/// sink is flushed.
+pub struct SpscProducer<
+    T,
+    const N: usize,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+> {
+    shared: Arc<Shared<T, N>>,
+    /// Local copy of the shared tail; only this half writes it.
+    tail: usize,
+    sink: S,
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> SpscProducer<T, N, S> {
+    /// Push an item. If full, evicts the oldest item to the sink.
+    ///
+    /// Waits briefly if the consumer is in the middle of popping the slot
+    /// being reused.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        let tail = self.tail;
+        let slot = self.shared.slot(tail);
+        let mut evicted = None;
+        let mut step = 0;
+        loop {
+            if slot.stamp.load(Ordering::Acquire) == empty(tail) {
+                break;
+            }
+            // The slot still holds the item from one lap ago: the ring is
+            // full. Claim that item unless the consumer got there first.
+            let oldest = tail.wrapping_sub(N);
+            if self
+                .shared
+                .head
+                .compare_exchange(
+                    oldest,
+                    oldest.wrapping_add(1),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                // SAFETY: the slot holds the item for `oldest`, and winning
+                // the exchange makes us its only reader.
+                evicted = Some(unsafe { (*slot.value.get()).assume_init_read() });
+                break;
+            }
+            // The consumer is reading the slot; it frees it when done.
+            backoff(&mut step);
+        }
+
+        // SAFETY: the slot is empty and the consumer will not touch it
+        // until the stamp is published.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.stamp.store(full(tail), Ordering::Release);
+        self.tail = tail.wrapping_add(1);
+        self.shared.tail.store(self.tail, Ordering::Release);
+
+        if let Some(evicted) = evicted {
+            let _ = self.sink.send(evicted);
+        }
+    }
+
+    /// Send every buffered item to the sink. Returns count flushed.
+    ///
+    /// Items the consumer pops concurrently are not counted.
+    pub fn flush(&mut self) -> usize {
+        let mut count = 0;
+        let mut step = 0;
+        loop {
+            let head = self.shared.head.load(Ordering::Acquire);
+            if head == self.tail {
+                return count;
+            }
+            match self.shared.take_head(head) {
+                Ok(item) => {
+                    count += 1;
+                    let _ = self.sink.send(item);
+                }
+                Err(_) => backoff(&mut step),
+            }
+        }
+    }
+
+    /// Number of items in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// True if empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// True if full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() >= N
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Reference to the sink.
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable reference to the sink.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Drop
+    for SpscProducer<T, N, S>
+{
+    fn drop(&mut self) {
+        self.shared.producer_closed.store(true, Ordering::Release);
+        let _ = self.sink.flush();
+    }
+}
+
+/// SpscProducer can act as a Spout, so it can sit at the end of a chain.
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> Spout<T>
+    for SpscProducer<T, N, S>
+{
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.push(item);
+        Ok(())
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingInfo
+    for SpscProducer<T, N, S>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        SpscProducer::len(self)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize, S: Spout<T, Error = core::convert::Infallible>> RingProducer<T>
+    for SpscProducer<T, N, S>
+{
+    #[inline]
+    fn try_push(&mut self, item: T) -> Result<(), crate::PushError<T>> {
+        let tail = self.tail;
+        let slot = self.shared.slot(tail);
+        if slot.stamp.load(Ordering::Acquire) != empty(tail) {
+            return Err(crate::PushError::Full(item));
+        }
+        // SAFETY: as in `push`.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.stamp.store(full(tail), Ordering::Release);
+        self.tail = tail.wrapping_add(1);
+        self.shared.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Consumer half of an [`SpscRing`].
+///
+/// Pops run concurrently with the producer. Items left when both halves
+/// are dropped are dropped.
+pub struct SpscConsumer<T, const N: usize> {
+    shared: Arc<Shared<T, N>>,
+}
+
+impl<T, const N: usize> SpscConsumer<T, N> {
+    /// Pop the oldest item, or `None` if the ring is empty right now.
+    #[inline]
+    #[must_use]
+    pub fn pop(&mut self) -> Option<T> {
+        let mut step = 0;
+        loop {
+            let head = self.shared.head.load(Ordering::Acquire);
+            match self.shared.take_head(head) {
+                Ok(item) => return Some(item),
+                Err(true) => return None,
+                Err(false) => backoff(&mut step),
+            }
+        }
+    }
+
+    /// Pop items until the ring is empty.
+    #[inline]
+    pub fn drain(&mut self) -> SpscDrain<'_, T, N> {
+        SpscDrain { consumer: self }
+    }
+
+    /// True once the producer has been dropped. Items may still be
+    /// buffered.
+    #[inline]
+    pub fn is_producer_closed(&self) -> bool {
+        self.shared.producer_closed.load(Ordering::Acquire)
+    }
+
+    /// Number of items in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// True if empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> RingInfo for SpscConsumer<T, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        SpscConsumer::len(self)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+/// SpscConsumer can act as a Source.
+///
+/// `recv` spins until an item arrives and is exhausted once the producer is
+/// dropped and the ring is empty; `try_recv` never waits.
+impl<T, const N: usize> Source<T> for SpscConsumer<T, N> {
+    type Error = core::convert::Infallible;
+
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        loop {
+            if let Some(item) = self.pop() {
+                return Ok(Some(item));
+            }
+            if self.is_producer_closed() {
+                // The producer may have pushed just before closing.
+                return Ok(self.pop());
+            }
+            #[cfg(feature = "std")]
+            std::thread::yield_now();
+            #[cfg(not(feature = "std"))]
+            core::hint::spin_loop();
+        }
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(self.pop())
+    }
+}
+
+/// Draining iterator over an [`SpscConsumer`].
+pub struct SpscDrain<'a, T, const N: usize> {
+    consumer: &'a mut SpscConsumer<T, N>,
+}
+
+impl<T, const N: usize> Iterator for SpscDrain<'_, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.consumer.pop()
+    }
+}
@@ -0,0 +1,180 @@
+//! Opt-in usage counters.
+//!
+//! A ring's statistics layer is a type parameter, chosen with
+//! [`SpillRingBuilder::stats`](crate::SpillRingBuilder::stats) (or
+//! [`PoolBuilder::stats`](crate::PoolBuilder::stats) for worker pools). The
+//! default, [`NoStats`], is zero-sized and its hooks are empty, so a ring
+//! without stats compiles to the same code as before. [`Counters`] records
+//! pushes, pops, evictions, flushes and peak occupancy.
+//!
+//! Reading counters requires a layer that implements [`EnabledStats`], so
+//! asking a ring without stats for them is a type error.
+
+use core::cell::Cell;
+
+/// Counters read from a ring, or summed across rings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RingStats {
+    /// Items pushed, including ones the overflow policy rejected.
+    pub pushes: u64,
+    /// Items popped or drained.
+    pub pops: u64,
+    /// Items that left through the overflow policy: evicted to the sink,
+    /// dropped by [`Overwrite`](crate::Overwrite), or rejected by
+    /// [`SpillNewest`](crate::SpillNewest).
+    pub evictions: u64,
+    /// Calls to `flush`, including the one made on drop.
+    pub flushes: u64,
+    /// Highest number of items held at once. When merged across rings,
+    /// the highest of any one ring.
+    pub peak_len: usize,
+}
+
+impl RingStats {
+    /// Combine two sets of counters: counts add, peaks take the maximum.
+    #[inline]
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            pushes: self.pushes + other.pushes,
+            pops: self.pops + other.pops,
+            evictions: self.evictions + other.evictions,
+            flushes: self.flushes + other.flushes,
+            peak_len: self.peak_len.max(other.peak_len),
+        }
+    }
+
+    /// Fraction of pushes that caused an eviction, or 0 with no pushes.
+    #[must_use]
+    pub fn eviction_rate(&self) -> f64 {
+        if self.pushes == 0 {
+            0.0
+        } else {
+            self.evictions as f64 / self.pushes as f64
+        }
+    }
+}
+
+impl core::iter::Sum for RingStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Self::merge)
+    }
+}
+
+/// Hooks a ring calls as items move through it.
+///
+/// Selected per ring as a type parameter. Hooks take `&self` because rings
+/// push through `&self`; implementations use `Cell`s, like the ring's own
+/// indices.
+pub trait StatsLayer: Default {
+    /// Whether this layer records anything. Lets generic code skip taking
+    /// snapshots of a layer that has nothing to report.
+    const ENABLED: bool;
+
+    /// `n` items were pushed; the ring now holds `len`.
+    fn record_push(&self, n: usize, len: usize);
+    /// `n` items were popped.
+    fn record_pop(&self, n: usize);
+    /// `n` items left through the overflow policy.
+    fn record_evict(&self, n: usize);
+    /// `flush` was called.
+    fn record_flush(&self);
+    /// Current counter values.
+    fn snapshot(&self) -> RingStats;
+    /// Zero every counter.
+    fn reset(&self);
+}
+
+/// A [`StatsLayer`] whose counters can be read.
+///
+/// Bounds `stats()` on rings, MPSC handles and worker pools. Implement it
+/// for custom layers that set [`StatsLayer::ENABLED`].
+///
+/// ```compile_fail
+/// use spill_ring::SpillRing;
+///
+/// let ring = SpillRing::<u32, 4>::new();
+/// // `NoStats` does not implement `EnabledStats`.
+/// let _ = ring.stats();
+/// ```
+pub trait EnabledStats: StatsLayer {}
+
+/// The default layer: records nothing and takes no space.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoStats;
+
+impl StatsLayer for NoStats {
+    const ENABLED: bool = false;
+
+    #[inline(always)]
+    fn record_push(&self, _n: usize, _len: usize) {}
+    #[inline(always)]
+    fn record_pop(&self, _n: usize) {}
+    #[inline(always)]
+    fn record_evict(&self, _n: usize) {}
+    #[inline(always)]
+    fn record_flush(&self) {}
+    #[inline(always)]
+    fn snapshot(&self) -> RingStats {
+        RingStats::default()
+    }
+    #[inline(always)]
+    fn reset(&self) {}
+}
+
+/// Layer that counts every operation.
+#[derive(Debug, Default)]
+pub struct Counters {
+    pushes: Cell<u64>,
+    pops: Cell<u64>,
+    evictions: Cell<u64>,
+    flushes: Cell<u64>,
+    peak_len: Cell<usize>,
+}
+
+impl EnabledStats for Counters {}
+
+impl StatsLayer for Counters {
+    const ENABLED: bool = true;
+
+    #[inline]
+    fn record_push(&self, n: usize, len: usize) {
+        self.pushes.set(self.pushes.get() + n as u64);
+        if len > self.peak_len.get() {
+            self.peak_len.set(len);
+        }
+    }
+
+    #[inline]
+    fn record_pop(&self, n: usize) {
+        self.pops.set(self.pops.get() + n as u64);
+    }
+
+    #[inline]
+    fn record_evict(&self, n: usize) {
+        self.evictions.set(self.evictions.get() + n as u64);
+    }
+
+    #[inline]
+    fn record_flush(&self) {
+        self.flushes.set(self.flushes.get() + 1);
+    }
+
+    fn snapshot(&self) -> RingStats {
+        RingStats {
+            pushes: self.pushes.get(),
+            pops: self.pops.get(),
+            evictions: self.evictions.get(),
+            flushes: self.flushes.get(),
+            peak_len: self.peak_len.get(),
+        }
+    }
+
+    fn reset(&self) {
+        self.pushes.set(0);
+        self.pops.set(0);
+        self.evictions.set(0);
+        self.flushes.set(0);
+        self.peak_len.set(0);
+    }
+}
@@ -0,0 +1,314 @@
+extern crate std;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{thread, vec, vec::Vec};
+
+use crate::{AtomicBroadcast, BroadcastRing, Lagged};
+use spout::{CollectSpout, FnSpout};
+
+#[test]
+fn every_reader_sees_every_item() {
+    let mut ring = BroadcastRing::<u32, 8>::new();
+    let a = ring.subscribe();
+    let b = ring.subscribe();
+    for i in 0..5 {
+        ring.push(i);
+    }
+    assert_eq!(ring.unread(a), 5);
+
+    let read_a: Vec<u32> = core::iter::from_fn(|| ring.recv(a).unwrap()).collect();
+    assert_eq!(read_a, [0, 1, 2, 3, 4]);
+    assert_eq!(ring.peek(b), Some(&0));
+    let read_b: Vec<u32> = core::iter::from_fn(|| ring.recv(b).unwrap()).collect();
+    assert_eq!(read_b, [0, 1, 2, 3, 4]);
+    assert_eq!(ring.recv(a), Ok(None));
+}
+
+#[test]
+fn late_subscriber_starts_at_tail() {
+    let mut ring = BroadcastRing::<u32, 8>::new();
+    ring.push(1);
+    let reader = ring.subscribe();
+    assert_eq!(ring.recv(reader), Ok(None));
+    ring.push(2);
+    assert_eq!(ring.recv(reader), Ok(Some(2)));
+}
+
+#[test]
+fn consumed_slots_are_reused_without_spilling() {
+    let mut ring = BroadcastRing::<u32, 4, _>::with_sink(CollectSpout::new());
+    let reader = ring.subscribe();
+    for i in 0..100 {
+        ring.push(i);
+        assert_eq!(ring.recv(reader), Ok(Some(i)));
+    }
+    assert!(ring.sink().items().is_empty());
+}
+
+#[test]
+fn slowest_reader_lags_and_counts_missed() {
+    let mut ring = BroadcastRing::<u32, 4, _>::with_sink(CollectSpout::new());
+    let fast = ring.subscribe();
+    let slow = ring.subscribe();
+    for i in 0..10 {
+        ring.push(i);
+        assert_eq!(ring.recv(fast), Ok(Some(i)));
+    }
+
+    // Only the items the slow reader had not read were spilled.
+    assert_eq!(ring.sink().items(), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(ring.recv(slow), Err(Lagged { missed: 6 }));
+    assert_eq!(ring.recv(slow), Ok(Some(6)));
+    assert_eq!(ring.unread(slow), 3);
+}
+
+#[test]
+fn try_push_reports_slowest_reader() {
+    let mut ring = BroadcastRing::<u32, 4>::new();
+    let fast = ring.subscribe();
+    let slow = ring.subscribe();
+    for i in 0..4 {
+        ring.try_push(i).unwrap();
+    }
+    ring.recv(fast).unwrap();
+    ring.recv(slow).unwrap();
+    ring.recv(fast).unwrap();
+
+    // Slot 0 is freed; slot 1 is still held by `slow`.
+    ring.try_push(4).unwrap();
+    let err = ring.try_push(5).unwrap_err();
+    assert_eq!(err.reader, slow);
+    assert_eq!(err.unread, 4);
+    assert_eq!(err.into_inner(), 5);
+    assert_eq!(ring.len(), 4);
+}
+
+#[test]
+fn no_readers_drops_without_lag() {
+    let mut ring = BroadcastRing::<u32, 4, _>::with_sink(CollectSpout::new());
+    for i in 0..10 {
+        ring.try_push(i).unwrap();
+    }
+    // Nobody holds the old items, so they are dropped when room is needed.
+    assert!(ring.sink().items().is_empty());
+    assert_eq!(ring.len(), 2);
+}
+
+#[test]
+fn unsubscribe_releases_held_items() {
+    let mut ring = BroadcastRing::<u32, 4, _>::with_sink(CollectSpout::new());
+    let a = ring.subscribe();
+    let b = ring.subscribe();
+    for i in 0..4 {
+        ring.push(i);
+    }
+    ring.unsubscribe(b);
+    while ring.recv(a).unwrap().is_some() {}
+    assert_eq!(ring.num_readers(), 1);
+    ring.try_push(4).unwrap();
+    assert!(ring.sink().items().is_empty());
+
+    // The freed id is handed out again.
+    assert_eq!(ring.subscribe(), b);
+}
+
+#[test]
+#[should_panic(expected = "reader is not subscribed")]
+fn recv_after_unsubscribe_panics() {
+    let mut ring = BroadcastRing::<u32, 4>::new();
+    let reader = ring.subscribe();
+    ring.unsubscribe(reader);
+    let _ = ring.recv(reader);
+}
+
+#[test]
+fn drop_spills_unread_items() {
+    let mut spilled = Vec::new();
+    {
+        let mut ring = BroadcastRing::<u32, 8, _>::with_sink(FnSpout(|x| spilled.push(x)));
+        let a = ring.subscribe();
+        let b = ring.subscribe();
+        for i in 0..5 {
+            ring.push(i);
+        }
+        ring.recv(a).unwrap();
+        ring.recv(a).unwrap();
+        ring.recv(b).unwrap();
+    }
+    assert_eq!(spilled, [1, 2, 3, 4]);
+}
+
+#[test]
+fn atomic_readers_see_every_item() {
+    let mut producer = AtomicBroadcast::<u32, 8>::new();
+    let mut a = producer.subscribe();
+    let mut b = producer.subscribe();
+    assert_ne!(a.id(), b.id());
+    for i in 0..5 {
+        producer.push(i);
+    }
+    assert_eq!(a.unread(), 5);
+    for i in 0..5 {
+        assert_eq!(a.recv(), Ok(Some(i)));
+    }
+    assert_eq!(a.recv(), Ok(None));
+    assert_eq!(b.recv(), Ok(Some(0)));
+    assert!(!b.is_producer_closed());
+    drop(producer);
+    assert!(b.is_producer_closed());
+    assert_eq!(b.recv(), Ok(Some(1)));
+}
+
+#[test]
+fn atomic_lag_and_try_push() {
+    let mut producer = AtomicBroadcast::<u32, 4>::with_sink(CollectSpout::new());
+    let mut fast = producer.subscribe();
+    let mut slow = producer.subscribe();
+    for i in 0..10 {
+        producer.push(i);
+        assert_eq!(fast.recv(), Ok(Some(i)));
+    }
+    assert_eq!(producer.sink().items(), [0, 1, 2, 3, 4, 5]);
+
+    let err = producer.try_push(10).unwrap_err();
+    assert_eq!(err.reader, slow.id());
+    assert_eq!(err.unread, 10);
+
+    assert_eq!(slow.recv(), Err(Lagged { missed: 6 }));
+    assert_eq!(slow.recv(), Ok(Some(6)));
+    producer.try_push(10).unwrap();
+}
+
+#[test]
+fn atomic_dropped_reader_stops_holding_items() {
+    let mut producer = AtomicBroadcast::<u32, 4>::with_sink(CollectSpout::new());
+    let reader = producer.subscribe();
+    assert_eq!(producer.num_readers(), 1);
+    drop(reader);
+    assert_eq!(producer.num_readers(), 0);
+    for i in 0..10 {
+        producer.try_push(i).unwrap();
+    }
+    assert!(producer.sink().items().is_empty());
+}
+
+/// A reader whose `clone` panics must not leave its slot pinned, or the
+/// producer would wait forever to spill it.
+#[test]
+fn atomic_panicking_clone_does_not_wedge_producer() {
+    #[derive(Debug, PartialEq)]
+    struct Fragile(u32);
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            assert!(self.0 != 0, "clone failed");
+            Fragile(self.0)
+        }
+    }
+
+    let mut producer = AtomicBroadcast::<Fragile, 4>::with_sink(CollectSpout::new());
+    let mut reader = producer.subscribe();
+    // Keeps item 0 unread, so it has to be spilled rather than released.
+    let mut holder = producer.subscribe();
+    producer.push(Fragile(0));
+    let result = thread::spawn(move || {
+        let _ = reader.recv();
+    })
+    .join();
+    assert!(result.is_err());
+
+    for i in 1..=8 {
+        producer.push(Fragile(i));
+    }
+    assert_eq!(producer.sink().items().len(), 5);
+    assert_eq!(producer.sink().items()[0], Fragile(0));
+    assert_eq!(holder.recv().map(|_| ()), Err(Lagged { missed: 5 }));
+}
+
+/// Readers on their own threads must see an increasing sequence, and what
+/// each one received plus what it missed must account for every item.
+#[test]
+fn atomic_stress_received_plus_missed_is_total() {
+    const ITEMS: u64 = 200_000;
+    let mut producer = AtomicBroadcast::<u64, 64>::new();
+    let readers: Vec<_> = (0..3).map(|_| producer.subscribe()).collect();
+
+    let handles: Vec<_> = readers
+        .into_iter()
+        .map(|mut reader| {
+            thread::spawn(move || {
+                let (mut received, mut missed) = (0u64, 0u64);
+                let mut last = None;
+                loop {
+                    match reader.recv() {
+                        Ok(Some(v)) => {
+                            assert!(last.is_none_or(|l| v > l), "out of order");
+                            last = Some(v);
+                            received += 1;
+                        }
+                        Err(Lagged { missed: m }) => missed += m as u64,
+                        Ok(None) if reader.is_producer_closed() => {
+                            // Items pushed just before the close.
+                            if reader.unread() == 0 {
+                                break;
+                            }
+                        }
+                        Ok(None) => thread::yield_now(),
+                    }
+                }
+                (received, missed)
+            })
+        })
+        .collect();
+
+    for i in 0..ITEMS {
+        producer.push(i);
+    }
+    drop(producer);
+
+    for handle in handles {
+        let (received, missed) = handle.join().unwrap();
+        assert_eq!(received + missed, ITEMS);
+    }
+}
+
+#[test]
+fn atomic_stress_no_double_drop() {
+    let live = Arc::new(AtomicUsize::new(0));
+
+    struct Tracked(Arc<AtomicUsize>);
+    impl Tracked {
+        fn new(live: &Arc<AtomicUsize>) -> Self {
+            live.fetch_add(1, Ordering::SeqCst);
+            Self(Arc::clone(live))
+        }
+    }
+    impl Clone for Tracked {
+        fn clone(&self) -> Self {
+            Self::new(&self.0)
+        }
+    }
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            let before = self.0.fetch_sub(1, Ordering::SeqCst);
+            assert!(before > 0, "dropped more items than created");
+        }
+    }
+
+    let mut producer = AtomicBroadcast::<Tracked, 8>::new();
+    let mut readers = vec![producer.subscribe(), producer.subscribe()];
+    let producer_live = Arc::clone(&live);
+    let handle = thread::spawn(move || {
+        for _ in 0..50_000 {
+            producer.push(Tracked::new(&producer_live));
+        }
+    });
+    while !handle.is_finished() {
+        for reader in &mut readers {
+            drop(reader.recv());
+        }
+    }
+    handle.join().unwrap();
+    drop(readers);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
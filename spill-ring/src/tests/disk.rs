@@ -0,0 +1,213 @@
+extern crate std;
+
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::{DiskSpill, SpillRing};
+use spout::Spout;
+
+/// A fresh directory per test, removed on drop.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(std::format!(
+            "spill-ring-disk-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn replay_yields_spilled_then_ring_items_in_order() {
+    let dir = TempDir::new("order");
+    let mut ring = SpillRing::<u64, 8, _>::with_sink(DiskSpill::open(&dir.0).unwrap());
+    for i in 0..100 {
+        ring.push(i);
+    }
+    assert_eq!(ring.sink().written(), 92);
+
+    let log: Vec<u64> = ring.replay().map(Result::unwrap).collect();
+    assert_eq!(log, (0..100).collect::<Vec<_>>());
+    // Replay consumes nothing.
+    assert_eq!(ring.len(), 8);
+    assert_eq!(ring.replay().count(), 100);
+}
+
+#[test]
+fn segments_roll_over_at_size() {
+    let dir = TempDir::new("rollover");
+    // Each u32 record is 4 + 4 bytes: two per 16-byte segment.
+    let mut sink = DiskSpill::<u32>::open(&dir.0)
+        .unwrap()
+        .with_segment_size(16);
+    for i in 0..5 {
+        sink.send(i).unwrap();
+    }
+    sink.flush().unwrap();
+    let segments = sink.segments();
+    assert_eq!(segments.len(), 3);
+    assert_eq!(fs::metadata(&segments[0]).unwrap().len(), 16);
+    assert_eq!(fs::metadata(&segments[2]).unwrap().len(), 8);
+
+    let items: Vec<u32> = sink.replay().map(Result::unwrap).collect();
+    assert_eq!(items, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn variable_length_records_round_trip() {
+    let dir = TempDir::new("strings");
+    let mut sink = DiskSpill::<String>::open(&dir.0)
+        .unwrap()
+        .with_segment_size(1);
+    for word in ["", "alpha", "a much longer record than the segment"] {
+        sink.send(word.to_string()).unwrap();
+    }
+    let items: Vec<String> = sink.replay().map(Result::unwrap).collect();
+    assert_eq!(
+        items,
+        ["", "alpha", "a much longer record than the segment"]
+    );
+    assert_eq!(sink.segments().len(), 3);
+}
+
+#[test]
+fn reopen_keeps_history_before_new_items() {
+    let dir = TempDir::new("reopen");
+    {
+        let ring = SpillRing::<u32, 4, _>::with_sink(DiskSpill::open(&dir.0).unwrap());
+        for i in 0..6 {
+            ring.push(i);
+        }
+        // Dropping the ring flushes the rest to disk.
+    }
+
+    let mut ring = SpillRing::<u32, 4, _>::with_sink(DiskSpill::open(&dir.0).unwrap());
+    ring.push(6);
+    let log: Vec<u32> = ring.replay().map(Result::unwrap).collect();
+    assert_eq!(log, [0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(ring.sink().segments().len(), 1);
+}
+
+#[test]
+fn truncated_record_ends_replay_with_error() {
+    let dir = TempDir::new("truncated");
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    for i in 0..3 {
+        sink.send(i).unwrap();
+    }
+    sink.flush().unwrap();
+    let path = sink.segments().pop().unwrap();
+    drop(sink);
+
+    // Simulate a crash in the middle of writing a fourth record.
+    let mut file = fs::File::options().append(true).open(&path).unwrap();
+    file.write_all(&8u32.to_le_bytes()).unwrap();
+    file.write_all(&[1, 2, 3]).unwrap();
+    drop(file);
+
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    let mut replay = sink.replay();
+    for i in 0..3 {
+        assert_eq!(replay.next().unwrap().unwrap(), i);
+    }
+    assert!(replay.next().unwrap().is_err());
+    assert!(replay.next().is_none());
+}
+
+#[test]
+fn truncated_length_prefix_is_an_error() {
+    let dir = TempDir::new("prefix");
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    sink.send(1).unwrap();
+    sink.flush().unwrap();
+    let first = sink.segments().pop().unwrap();
+    drop(sink);
+
+    // A crash after two bytes of the next length prefix, followed by a
+    // later, intact segment.
+    let mut file = fs::File::options().append(true).open(&first).unwrap();
+    file.write_all(&[8, 0]).unwrap();
+    drop(file);
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    sink.send(2).unwrap();
+
+    let mut replay = sink.replay();
+    assert_eq!(replay.next().unwrap().unwrap(), 1);
+    let err = replay.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert!(replay.next().is_none());
+}
+
+#[test]
+fn oversized_record_length_is_rejected_without_allocating() {
+    let dir = TempDir::new("oversized");
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    sink.send(7).unwrap();
+    sink.flush().unwrap();
+    let path = sink.segments().pop().unwrap();
+    drop(sink);
+
+    // A corrupt length prefix claiming a 4 GiB record.
+    let mut file = fs::File::options().append(true).open(&path).unwrap();
+    file.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    file.write_all(&[0; 8]).unwrap();
+    drop(file);
+
+    let mut sink = DiskSpill::<u64>::open(&dir.0).unwrap();
+    let mut replay = sink.replay();
+    assert_eq!(replay.next().unwrap().unwrap(), 7);
+    let err = replay.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(replay.next().is_none());
+}
+
+#[test]
+fn failed_write_leaves_no_torn_record() {
+    let dir = TempDir::new("failed-write");
+    let mut sink = DiskSpill::<String>::open(&dir.0).unwrap();
+    sink.send("before".to_string()).unwrap();
+    sink.flush().unwrap();
+    let first = sink.segments().pop().unwrap();
+    let good_len = fs::metadata(&first).unwrap().len();
+
+    // The write stops six bytes into the next record.
+    sink.fail_next_write_after(6);
+    sink.send("lost".to_string()).unwrap();
+    sink.flush().unwrap();
+    assert_eq!(sink.written(), 1);
+    assert_eq!(sink.failed(), 1);
+    assert!(sink.take_error().is_some());
+    assert_eq!(fs::metadata(&first).unwrap().len(), good_len);
+
+    sink.send("after".to_string()).unwrap();
+    assert_eq!(sink.written(), 2);
+    assert!(sink.take_error().is_none());
+    assert_eq!(sink.segments().len(), 2);
+    let items: Vec<String> = sink.replay().map(Result::unwrap).collect();
+    assert_eq!(items, ["before", "after"]);
+}
+
+#[test]
+fn io_errors_are_counted_not_propagated() {
+    let dir = TempDir::new("errors");
+    let mut sink = DiskSpill::<u32>::open(&dir.0).unwrap();
+    fs::remove_dir_all(&dir.0).unwrap();
+
+    assert_eq!(sink.send(1), Ok(()));
+    assert_eq!(sink.failed(), 1);
+    assert_eq!(sink.written(), 0);
+    assert!(sink.take_error().is_some());
+    assert!(sink.take_error().is_none());
+}
@@ -0,0 +1,242 @@
+extern crate std;
+
+use core::mem::MaybeUninit;
+use std::{vec, vec::Vec};
+
+use crate::DynSpillRing;
+use crate::traits::{RingConsumer, RingInfo, RingProducer};
+use spout::{CollectSpout, Spout};
+
+fn collecting(capacity: usize) -> DynSpillRing<i32, CollectSpout<i32>> {
+    DynSpillRing::builder(capacity)
+        .any_capacity()
+        .sink(CollectSpout::new())
+        .build()
+}
+
+fn contents<S: Spout<i32, Error = core::convert::Infallible>>(
+    ring: &DynSpillRing<i32, S>,
+) -> Vec<i32> {
+    ring.iter().copied().collect()
+}
+
+#[test]
+fn new_ring_is_empty() {
+    let ring: DynSpillRing<i32> = DynSpillRing::new(8);
+    assert!(ring.is_empty());
+    assert!(!ring.is_full());
+    assert_eq!(ring.capacity(), 8);
+    assert_eq!(ring.peek(), None);
+}
+
+#[test]
+fn push_pop_and_evict() {
+    let ring = DynSpillRing::with_sink(4, CollectSpout::new());
+    for i in 1..=6 {
+        ring.push(i);
+    }
+    assert!(ring.is_full());
+    assert_eq!(ring.sink_ref().items(), vec![1, 2]);
+    assert_eq!(ring.peek(), Some(&3));
+    assert_eq!(ring.peek_back(), Some(&6));
+    assert_eq!(ring.pop(), Some(3));
+    assert_eq!(ring.len(), 3);
+}
+
+#[test]
+fn non_power_of_two_wraparound() {
+    let mut ring = collecting(3);
+    ring.push_mut(0);
+    ring.push_mut(1);
+    for i in 2..10 {
+        ring.push_mut(i);
+        assert_eq!(ring.pop_mut(), Some(i - 2));
+        assert_eq!(ring.len(), 2);
+    }
+    assert_eq!(contents(&ring), vec![8, 9]);
+    assert!(ring.sink().items().is_empty());
+}
+
+#[test]
+fn non_power_of_two_evicts_oldest() {
+    let mut ring = collecting(5);
+    ring.extend(0..12);
+    assert_eq!(contents(&ring), vec![7, 8, 9, 10, 11]);
+    assert_eq!(ring.get(0), Some(&7));
+    assert_eq!(ring.get(4), Some(&11));
+    assert_eq!(ring.get(5), None);
+    assert_eq!(ring.sink().items(), (0..7).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "capacity must be power of two")]
+fn rejects_non_power_of_two_by_default() {
+    let _: DynSpillRing<i32> = DynSpillRing::new(3);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be > 0")]
+fn rejects_zero_capacity() {
+    let _: DynSpillRing<i32> = DynSpillRing::builder(0).any_capacity().build();
+}
+
+#[test]
+fn capacity_above_const_limit() {
+    let mut ring: DynSpillRing<u8> = DynSpillRing::builder((1 << 20) + 1)
+        .any_capacity()
+        .cold()
+        .build();
+    ring.push_mut(1);
+    assert_eq!(ring.capacity(), (1 << 20) + 1);
+}
+
+#[test]
+fn flush_and_drop_send_to_sink() {
+    let mut ring = collecting(3);
+    ring.extend([1, 2]);
+    assert_eq!(ring.flush(), 2);
+    assert!(ring.is_empty());
+    ring.push_and_flush(3);
+    assert_eq!(ring.sink().items(), vec![1, 2, 3]);
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static SENT: AtomicUsize = AtomicUsize::new(0);
+    {
+        let ring = DynSpillRing::with_sink(
+            4,
+            spout::FnSpout(|_: i32| {
+                SENT.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+        ring.push(1);
+        ring.push(2);
+        assert_eq!(SENT.load(Ordering::SeqCst), 0);
+    }
+    assert_eq!(SENT.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn resize_grow_keeps_order() {
+    let mut ring = collecting(3);
+    ring.extend(0..5);
+    ring.resize(6);
+    assert_eq!(ring.capacity(), 6);
+    assert_eq!(contents(&ring), vec![2, 3, 4]);
+    ring.extend(5..8);
+    assert_eq!(contents(&ring), vec![2, 3, 4, 5, 6, 7]);
+    assert_eq!(ring.sink().items(), vec![0, 1]);
+}
+
+#[test]
+fn resize_shrink_spills_oldest() {
+    let mut ring = collecting(5);
+    ring.extend(0..7);
+    ring.resize(2);
+    assert_eq!(contents(&ring), vec![5, 6]);
+    assert_eq!(ring.sink().items(), vec![0, 1, 2, 3, 4]);
+    ring.push_mut(7);
+    assert_eq!(contents(&ring), vec![6, 7]);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be power of two")]
+fn resize_enforces_power_of_two() {
+    let mut ring: DynSpillRing<i32> = DynSpillRing::new(4);
+    ring.resize(6);
+}
+
+#[test]
+fn push_slice_and_pop_slice() {
+    let mut ring = collecting(3);
+    ring.push_slice(&[1, 2]);
+    ring.push_slice(&[3, 4]);
+    assert_eq!(contents(&ring), vec![2, 3, 4]);
+
+    ring.push_slice(&[5, 6, 7, 8, 9]);
+    assert_eq!(contents(&ring), vec![7, 8, 9]);
+    assert_eq!(ring.sink().items(), vec![1, 2, 3, 4, 5, 6]);
+
+    let mut buf = [MaybeUninit::uninit(); 2];
+    assert_eq!(ring.pop_slice(&mut buf), 2);
+    assert_eq!(
+        unsafe { [buf[0].assume_init(), buf[1].assume_init()] },
+        [7, 8]
+    );
+    assert_eq!(contents(&ring), vec![9]);
+}
+
+#[test]
+fn iter_mut_and_slices() {
+    let mut ring = collecting(3);
+    ring.extend(1..=4);
+    for x in ring.iter_mut() {
+        *x *= 10;
+    }
+    let (a, b) = ring.as_slices();
+    assert_eq!([a, b].concat(), vec![20, 30, 40]);
+    assert_eq!(ring.iter().len(), 3);
+    assert_eq!(
+        ring.iter().rev().copied().collect::<Vec<_>>(),
+        vec![40, 30, 20]
+    );
+}
+
+#[test]
+fn drain_empties_ring() {
+    let mut ring = collecting(4);
+    ring.extend(1..=3);
+    let drain = ring.drain();
+    assert_eq!(drain.len(), 3);
+    assert_eq!(drain.collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn ring_traits() {
+    let mut ring: DynSpillRing<i32> = DynSpillRing::builder(3).any_capacity().build();
+    for i in 0..3 {
+        assert!(RingProducer::try_push(&mut ring, i).is_ok());
+    }
+    assert_eq!(
+        RingProducer::try_push(&mut ring, 3)
+            .unwrap_err()
+            .into_inner(),
+        3
+    );
+    assert!(RingInfo::is_full(&ring));
+    assert_eq!(RingInfo::capacity(&ring), 3);
+    assert_eq!(RingConsumer::peek(&mut ring), Some(&0));
+    assert_eq!(RingConsumer::try_pop(&mut ring), Some(0));
+}
+
+#[test]
+fn chains_into_another_ring() {
+    let mut ring = DynSpillRing::builder(2)
+        .sink(
+            DynSpillRing::builder(3)
+                .any_capacity()
+                .sink(CollectSpout::new())
+                .build(),
+        )
+        .build();
+    ring.extend(0..8);
+    assert_eq!(contents(&ring), vec![6, 7]);
+    assert_eq!(contents(ring.sink()), vec![3, 4, 5]);
+    assert_eq!(ring.sink().sink_ref().items(), vec![0, 1, 2]);
+}
+
+#[test]
+fn drop_drops_buffered_items_once() {
+    use std::rc::Rc;
+
+    let marker = Rc::new(());
+    {
+        let mut ring: DynSpillRing<Rc<()>> = DynSpillRing::builder(3).any_capacity().build();
+        for _ in 0..5 {
+            ring.push_mut(Rc::clone(&marker));
+        }
+        ring.resize(2);
+        assert_eq!(Rc::strong_count(&marker), 3);
+    }
+    assert_eq!(Rc::strong_count(&marker), 1);
+}
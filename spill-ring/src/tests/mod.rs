@@ -1,6 +1,22 @@
 #[cfg(feature = "alloc")]
+mod broadcast;
+#[cfg(all(feature = "std", feature = "bytecast"))]
+mod disk;
+#[cfg(feature = "alloc")]
+mod dyn_ring;
+#[cfg(feature = "alloc")]
 mod mpsc;
+mod policy;
 mod ring;
 mod ring_chaining;
+#[cfg(all(feature = "shm", target_os = "linux"))]
+mod shm;
+#[cfg(feature = "bytecast")]
+mod snapshot;
 mod spout;
+#[cfg(feature = "alloc")]
+mod spsc;
+#[cfg(feature = "std")]
+mod stats;
 mod traits;
+mod ttl;
@@ -71,8 +71,12 @@ fn single_producer() {
 
 #[cfg(feature = "std")]
 mod worker_pool_tests {
-    use crate::MpscRing;
-    use spout::CollectSpout;
+    use crate::{MpscRing, PanicPolicy, PoolError};
+    use spout::{CollectSpout, Spout};
+    use std::panic::AssertUnwindSafe;
+    use std::string::{String, ToString};
+    use std::sync::{Arc, Mutex};
+    use std::vec::Vec;
 
     #[test]
     fn basic_worker_pool() {
@@ -82,7 +86,7 @@ mod worker_pool_tests {
             }
         });
 
-        pool.run(&50);
+        pool.run(&50).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut sink = CollectSpout::new();
@@ -101,7 +105,7 @@ mod worker_pool_tests {
             }
         });
 
-        pool.run(&100);
+        pool.run(&100).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut sink = CollectSpout::new();
@@ -128,8 +132,8 @@ mod worker_pool_tests {
             }
         });
 
-        pool.run(&10);
-        pool.run(&10);
+        pool.run(&10).unwrap();
+        pool.run(&10).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut sink = CollectSpout::new();
@@ -146,7 +150,7 @@ mod worker_pool_tests {
             ring.push(id as u64);
         });
 
-        pool.run(&());
+        pool.run(&()).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut sink = CollectSpout::new();
@@ -163,8 +167,8 @@ mod worker_pool_tests {
             ring.push(*val);
         });
 
-        pool.run(&42);
-        pool.run(&99);
+        pool.run(&42).unwrap();
+        pool.run(&99).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut sink = CollectSpout::new();
@@ -188,7 +192,7 @@ mod worker_pool_tests {
             });
 
         // Push 10 items per worker into ring of size 4 — forces overflow to sink
-        pool.run(&10);
+        pool.run(&10).unwrap();
 
         let mut consumer = pool.into_consumer();
         let mut drain_sink = CollectSpout::new();
@@ -211,9 +215,315 @@ mod worker_pool_tests {
             }
         });
 
-        pool.run(&100);
+        pool.run(&100).unwrap();
         drop(pool); // Should not panic or hang
     }
+
+    #[test]
+    fn worker_pool_panic_on_first_run_poisons() {
+        let mut pool = MpscRing::<u64, 64>::pool(3).spawn(|ring, id, _args: &()| {
+            ring.push(id as u64);
+            if id == 1 {
+                panic!("boom");
+            }
+        });
+
+        let err = pool.run(&()).unwrap_err();
+        assert!(matches!(
+            err,
+            PoolError::WorkerPanicked { worker_id: 1, .. }
+        ));
+        assert_eq!(err.message(), Some("boom"));
+        assert_eq!(err.to_string(), "worker 1 panicked: boom");
+        assert!(pool.is_poisoned());
+
+        let err = pool.run(&()).unwrap_err();
+        assert!(matches!(err, PoolError::Poisoned { worker_id: 1 }));
+
+        // Rings survive, including the one that panicked.
+        let mut consumer = pool.into_consumer();
+        let mut sink = CollectSpout::new();
+        consumer.drain(&mut sink);
+        assert_eq!(sink.into_items(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn worker_pool_panic_on_later_run_respawns() {
+        let mut pool = MpscRing::<u64, 64>::pool(2)
+            .on_panic(PanicPolicy::Respawn)
+            .spawn(|ring, id, run: &u64| {
+                ring.push(*run);
+                if id == 0 && *run == 2 {
+                    panic!("run {run} failed");
+                }
+            });
+
+        pool.run(&1).unwrap();
+        let err = pool.run(&2).unwrap_err();
+        assert_eq!(err.worker_id(), 0);
+        assert_eq!(err.message(), Some("run 2 failed"));
+        assert!(!pool.is_poisoned());
+        pool.run(&3).unwrap();
+
+        // Worker 0 got a fresh ring after the panic.
+        let mut consumer = pool.into_consumer();
+        let mut sink = CollectSpout::new();
+        consumer.drain(&mut sink);
+        assert_eq!(sink.into_items(), vec![3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn worker_pool_reports_lowest_panicking_worker() {
+        let mut pool = MpscRing::<u64, 8>::pool(4)
+            .on_panic(PanicPolicy::Respawn)
+            .spawn(|_ring, id, _args: &()| {
+                if id >= 2 {
+                    panic!("worker {id}");
+                }
+            });
+
+        let err = pool.run(&()).unwrap_err();
+        assert_eq!(err.worker_id(), 2);
+        assert_eq!(err.message(), Some("worker 2"));
+        pool.run(&()).unwrap_err();
+    }
+
+    #[test]
+    fn worker_pool_panic_during_shutdown() {
+        // The caller panics with the pool alive, so the pool shuts down
+        // while unwinding. Shutdown must not hang or double-panic.
+        let result = std::panic::catch_unwind(|| {
+            let mut pool = MpscRing::<u64, 64>::pool(2).spawn(|ring, _id, _args: &()| {
+                ring.push(1);
+                panic!("boom");
+            });
+            pool.run(&()).unwrap();
+        });
+
+        let payload = result.unwrap_err();
+        let msg = payload.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("WorkerPanicked"), "{msg}");
+    }
+
+    /// Sink that panics on every item.
+    #[derive(Clone)]
+    struct PanickingSink;
+
+    impl Spout<u64> for PanickingSink {
+        type Error = core::convert::Infallible;
+
+        fn send(&mut self, _item: u64) -> Result<(), Self::Error> {
+            panic!("sink failed");
+        }
+    }
+
+    fn panicking_sink_pool() -> impl Drop {
+        let mut pool = MpscRing::<u64, 8, _>::pool_with_sink(3, PanickingSink).spawn(
+            |ring, id, _args: &()| {
+                ring.push(id as u64);
+            },
+        );
+        pool.run(&()).unwrap();
+        pool
+    }
+
+    #[test]
+    fn worker_pool_sink_panic_during_shutdown() {
+        // Every worker's ring panics when shutdown flushes it. The first
+        // panic is reported; the rest must not abort the process.
+        let pool = panicking_sink_pool();
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(|| drop(pool))).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"sink failed"));
+
+        // Shutting down while the caller is already unwinding.
+        let payload = std::panic::catch_unwind(|| {
+            let _pool = panicking_sink_pool();
+            panic!("caller failed");
+        })
+        .unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"caller failed"));
+
+        // `into_consumer` hands the rings over without flushing them.
+        let mut pool = MpscRing::<u64, 8, _>::pool_with_sink(2, PanickingSink)
+            .spawn(|ring, id, _args: &()| ring.push(id as u64));
+        pool.run(&()).unwrap();
+        let mut consumer = pool.into_consumer();
+        let mut sink = CollectSpout::new();
+        consumer.drain(&mut sink);
+        assert_eq!(sink.into_items(), vec![0, 1]);
+
+        // Retiring rings on resize reports the panic after restarting.
+        let mut pool = MpscRing::<u64, 8, _>::pool_with_sink(3, PanickingSink)
+            .spawn(|ring, id, _args: &()| ring.push(id as u64));
+        pool.run(&()).unwrap();
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| pool.resize(1)));
+        assert!(result.is_err());
+        assert_eq!(pool.num_rings(), 1);
+        let mut consumer = pool.into_consumer();
+        let mut sink = CollectSpout::new();
+        consumer.drain(&mut sink);
+        assert_eq!(sink.into_items(), vec![0]);
+    }
+
+    #[test]
+    fn worker_pool_into_consumer_after_panic() {
+        let mut pool = MpscRing::<u64, 64>::pool(2).spawn(|ring, id, _args: &()| {
+            ring.push(id as u64);
+            if id == 0 {
+                panic!("boom");
+            }
+        });
+        assert!(pool.run(&()).is_err());
+
+        let consumer = pool.into_consumer();
+        assert_eq!(consumer.num_producers(), 2);
+    }
+
+    /// Sink shared by every worker, so flushed items can be observed.
+    #[derive(Clone, Default)]
+    struct SharedSink(Arc<Mutex<Vec<u64>>>);
+
+    impl Spout<u64> for SharedSink {
+        type Error = core::convert::Infallible;
+
+        fn send(&mut self, item: u64) -> Result<(), Self::Error> {
+            self.0.lock().unwrap().push(item);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn worker_pool_run_with_results() {
+        let mut pool = MpscRing::<u64, 64>::pool(3).spawn(|ring, id, base: &u64| {
+            ring.push(*base);
+            *base + id as u64
+        });
+
+        assert_eq!(pool.run_with_results(&10).unwrap(), vec![10, 11, 12]);
+        assert_eq!(pool.run_with_results(&20).unwrap(), vec![20, 21, 22]);
+        pool.run(&30).unwrap();
+
+        let consumer = pool.into_consumer();
+        assert_eq!(consumer.len(), 9);
+    }
+
+    #[test]
+    fn worker_pool_submit_overlaps_caller() {
+        let mut pool = MpscRing::<u64, 64>::pool(2)
+            .spawn(|_ring, _id, items: &Vec<u64>| items.iter().sum::<u64>());
+
+        pool.submit((1..=100).collect()).unwrap();
+        assert!(pool.is_submitted());
+        let local: u64 = (1..=10).sum();
+        let results = pool.wait().unwrap();
+        assert!(!pool.is_submitted());
+        assert_eq!(results, vec![5050, 5050]);
+        assert_eq!(local, 55);
+
+        // The pool is reusable after wait.
+        pool.submit(vec![1, 2]).unwrap();
+        assert_eq!(pool.wait().unwrap(), vec![3, 3]);
+    }
+
+    #[test]
+    fn worker_pool_submit_panic_reported_by_wait() {
+        let mut pool = MpscRing::<u64, 8>::pool(2).spawn(|_ring, id, _args: &()| {
+            if id == 1 {
+                panic!("boom");
+            }
+        });
+
+        pool.submit(()).unwrap();
+        assert_eq!(pool.wait().unwrap_err().worker_id(), 1);
+        assert!(matches!(pool.submit(()), Err(PoolError::Poisoned { .. })));
+        assert!(!pool.is_submitted());
+    }
+
+    #[test]
+    #[should_panic(expected = "a submitted run must be waited for first")]
+    fn worker_pool_run_while_submitted_panics() {
+        let mut pool = MpscRing::<u64, 8>::pool(1).spawn(|_ring, _id, _args: &()| {});
+        pool.submit(()).unwrap();
+        let _ = pool.run(&());
+    }
+
+    #[test]
+    fn worker_pool_drop_with_submitted_run() {
+        let sink = SharedSink::default();
+        let mut pool = MpscRing::<u64, 64, _>::pool_with_sink(2, sink.clone()).spawn(
+            |ring, _id, count: &u64| {
+                for i in 0..*count {
+                    ring.push(i);
+                }
+            },
+        );
+        pool.submit(10).unwrap();
+        drop(pool);
+        // The run completed before shutdown; rings flushed on drop.
+        assert_eq!(sink.0.lock().unwrap().len(), 20);
+    }
+
+    #[test]
+    fn worker_pool_resize_grow_and_shrink() {
+        let sink = SharedSink::default();
+        let mut pool =
+            MpscRing::<u64, 64, _>::pool_with_sink(2, sink.clone()).spawn(|ring, id, run: &u64| {
+                ring.push(run * 100 + id as u64);
+                id
+            });
+
+        pool.run(&1).unwrap();
+        pool.resize(4);
+        assert_eq!(pool.num_rings(), 4);
+        assert_eq!(pool.run_with_results(&2).unwrap(), vec![0, 1, 2, 3]);
+        assert!(sink.0.lock().unwrap().is_empty());
+
+        // Retiring workers 1..4 flushes their rings to the sink.
+        pool.resize(1);
+        assert_eq!(pool.num_rings(), 1);
+        let mut retired = sink.0.lock().unwrap().clone();
+        retired.sort_unstable();
+        assert_eq!(retired, vec![101, 201, 202, 203]);
+
+        // Worker 0 kept its ring across both resizes.
+        pool.run(&3).unwrap();
+        let mut consumer = pool.into_consumer();
+        let mut out = CollectSpout::new();
+        consumer.drain(&mut out);
+        assert_eq!(out.into_items(), vec![100, 200, 300]);
+    }
+
+    #[test]
+    fn worker_pool_resize_after_submit_discards_results() {
+        let mut pool = MpscRing::<u64, 8>::pool(2).spawn(|_ring, id, _args: &()| id);
+        pool.submit(()).unwrap();
+        pool.resize(3);
+        assert!(!pool.is_submitted());
+        assert_eq!(pool.run_with_results(&()).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn worker_pool_adaptive_parks_when_idle() {
+        let mut pool = MpscRing::<u64, 64>::pool(2)
+            .adaptive(16)
+            .spawn(|ring, _id, count: &u64| {
+                for i in 0..*count {
+                    ring.push(i);
+                }
+                ring.len()
+            });
+
+        for round in 1..=3u64 {
+            // Long enough for the workers to exhaust the budget and park.
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            let lens = pool.run_with_results(&5).unwrap();
+            assert_eq!(lens, vec![5 * round as usize; 2]);
+        }
+        for _ in 0..1000 {
+            pool.run(&0).unwrap();
+        }
+        assert_eq!(pool.into_consumer().len(), 30);
+    }
 }
 
 // --- Missing coverage tests ---
@@ -290,3 +600,91 @@ fn multi_threaded_producers() {
         assert_eq!(thread_items, expected);
     }
 }
+
+#[test]
+fn drain_merged_interleaves_producers() {
+    let (producers, mut consumer) = MpscRing::<(u64, usize), 16>::with_consumer(3);
+    for seq in 0..30u64 {
+        let id = (seq * 7 % 3) as usize;
+        producers[id].push((seq, id));
+    }
+    collect(producers, &mut consumer);
+
+    let mut sink = CollectSpout::new();
+    consumer.drain_merged_by(|&(seq, _)| seq, &mut sink);
+    let seqs: std::vec::Vec<u64> = sink.items().iter().map(|&(seq, _)| seq).collect();
+    assert_eq!(seqs, (0..30).collect::<std::vec::Vec<_>>());
+    assert!(consumer.is_empty());
+}
+
+#[test]
+fn drain_merged_equal_keys_keep_producer_order() {
+    let (producers, mut consumer) = MpscRing::<(u64, char), 8>::with_consumer(2);
+    producers[0].push((1, 'a'));
+    producers[0].push((2, 'a'));
+    producers[1].push((1, 'b'));
+    producers[1].push((2, 'b'));
+    collect(producers, &mut consumer);
+
+    let mut sink = CollectSpout::new();
+    consumer.drain_merged_by(|&(ts, _)| ts, &mut sink);
+    assert_eq!(sink.items(), [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+}
+
+#[test]
+fn drain_merged_with_spilled_streams() {
+    let (producers, mut consumer) = MpscRing::<u64, 4>::with_consumer(2);
+    let mut spilled = [std::vec::Vec::new(), std::vec::Vec::new()];
+    for seq in 0..20u64 {
+        let id = (seq % 2) as usize;
+        if producers[id].is_full() {
+            // Stand-in for a sink that captured the evicted items.
+            spilled[id].push(seq - 8);
+        }
+        producers[id].push(seq);
+    }
+    collect(producers, &mut consumer);
+
+    let mut sink = CollectSpout::new();
+    consumer.drain_merged_with_by(spilled, |&seq| seq, &mut sink);
+    assert_eq!(sink.items(), (0..20).collect::<std::vec::Vec<_>>());
+
+    // Equal keys: spilled streams come before ring items.
+    let (producers, mut consumer) = MpscRing::<(u64, &str), 4>::with_consumer(1);
+    producers[0].push((5, "ring"));
+    collect(producers, &mut consumer);
+    let mut sink = CollectSpout::new();
+    consumer.drain_merged_with_by([[(5, "spilled")]], |&(k, _)| k, &mut sink);
+    assert_eq!(sink.items(), [(5, "spilled"), (5, "ring")]);
+}
+
+#[test]
+fn drain_merged_multi_threaded_sequence() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::thread;
+
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let (producers, mut consumer) = MpscRing::<u64, 1024>::with_consumer(4);
+
+    let finished: std::vec::Vec<_> = thread::scope(|s| {
+        producers
+            .into_iter()
+            .map(|producer| {
+                s.spawn(move || {
+                    for _ in 0..200 {
+                        producer.push(SEQ.fetch_add(1, Ordering::SeqCst));
+                    }
+                    producer
+                })
+            })
+            .collect::<std::vec::Vec<_>>()
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect()
+    });
+    collect(finished, &mut consumer);
+
+    let mut sink = CollectSpout::new();
+    consumer.drain_merged_by(|&seq| seq, &mut sink);
+    assert_eq!(sink.items(), (0..800).collect::<std::vec::Vec<_>>());
+}
@@ -0,0 +1,150 @@
+extern crate std;
+
+use core::convert::Infallible;
+use std::{vec, vec::Vec};
+
+use crate::traits::RingProducer;
+use crate::{Overwrite, SpillBatch, SpillNewest, SpillOldest, SpillRing};
+use spout::{CollectSpout, Spout};
+
+/// Records each sink call as one batch.
+#[derive(Default)]
+struct Batches(Vec<Vec<i32>>);
+
+impl Spout<i32> for Batches {
+    type Error = Infallible;
+
+    fn send(&mut self, item: i32) -> Result<(), Infallible> {
+        self.0.push(vec![item]);
+        Ok(())
+    }
+
+    fn send_all(&mut self, items: impl Iterator<Item = i32>) -> Result<(), Infallible> {
+        self.0.push(items.collect());
+        Ok(())
+    }
+}
+
+fn contents<const N: usize, S, P>(ring: &SpillRing<i32, N, S, P>) -> Vec<i32>
+where
+    S: Spout<i32, Error = Infallible>,
+    P: crate::OverflowPolicy,
+{
+    ring.iter().copied().collect()
+}
+
+#[test]
+fn spill_oldest_is_default() {
+    let mut ring = SpillRing::<i32, 4>::builder()
+        .policy(SpillOldest)
+        .sink(CollectSpout::new())
+        .build();
+    ring.extend(0..6);
+    assert_eq!(contents(&ring), vec![2, 3, 4, 5]);
+    assert_eq!(ring.sink().items(), vec![0, 1]);
+}
+
+#[test]
+fn spill_newest_keeps_buffered_items() {
+    let mut ring = SpillRing::<i32, 4>::builder()
+        .sink(CollectSpout::new())
+        .policy(SpillNewest)
+        .build();
+    ring.extend(0..6);
+    ring.push(6);
+    assert_eq!(contents(&ring), vec![0, 1, 2, 3]);
+    assert_eq!(ring.sink().items(), vec![4, 5, 6]);
+
+    assert_eq!(ring.pop_mut(), Some(0));
+    ring.push_mut(7);
+    assert_eq!(contents(&ring), vec![1, 2, 3, 7]);
+}
+
+#[test]
+fn spill_batch_evicts_in_one_call() {
+    let mut ring = SpillRing::<i32, 8>::builder()
+        .sink(Batches::default())
+        .policy(SpillBatch::<3>)
+        .build();
+    ring.extend(0..9);
+    assert_eq!(contents(&ring), vec![3, 4, 5, 6, 7, 8]);
+    assert_eq!(ring.sink().0, vec![vec![0, 1, 2]]);
+
+    // Room was freed, so the next two pushes do not evict.
+    ring.push(9);
+    ring.push(10);
+    assert_eq!(ring.sink().0.len(), 1);
+    ring.push(11);
+    assert_eq!(ring.sink().0, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+}
+
+#[test]
+fn spill_batch_of_full_capacity() {
+    let mut ring = SpillRing::<i32, 4>::builder()
+        .sink(Batches::default())
+        .policy(SpillBatch::<4>)
+        .build();
+    ring.extend(0..5);
+    assert_eq!(contents(&ring), vec![4]);
+    assert_eq!(ring.sink().0, vec![vec![0, 1, 2, 3]]);
+}
+
+#[test]
+fn overwrite_never_touches_sink_on_overflow() {
+    let mut ring = SpillRing::<i32, 4>::builder()
+        .sink(CollectSpout::new())
+        .policy(Overwrite)
+        .build();
+    ring.extend(0..10);
+    assert_eq!(contents(&ring), vec![6, 7, 8, 9]);
+    assert!(ring.sink().items().is_empty());
+
+    // Flush still reaches the sink.
+    ring.flush();
+    assert_eq!(ring.sink().items(), vec![6, 7, 8, 9]);
+}
+
+#[test]
+fn overwrite_drops_evicted_items() {
+    use std::rc::Rc;
+
+    let marker = Rc::new(());
+    let ring = SpillRing::<Rc<()>, 2>::builder().policy(Overwrite).build();
+    for _ in 0..5 {
+        ring.push(Rc::clone(&marker));
+    }
+    assert_eq!(Rc::strong_count(&marker), 3);
+    drop(ring);
+    assert_eq!(Rc::strong_count(&marker), 1);
+}
+
+#[test]
+fn push_slice_follows_policy() {
+    let mut newest = SpillRing::<i32, 4>::builder()
+        .sink(CollectSpout::new())
+        .policy(SpillNewest)
+        .build();
+    newest.push_slice(&[0, 1, 2, 3, 4, 5]);
+    assert_eq!(contents(&newest), vec![0, 1, 2, 3]);
+    assert_eq!(newest.sink().items(), vec![4, 5]);
+
+    let mut batch = SpillRing::<i32, 4>::builder()
+        .sink(Batches::default())
+        .policy(SpillBatch::<2>)
+        .build();
+    batch.push_slice(&[0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(contents(&batch), vec![4, 5, 6]);
+    assert_eq!(batch.sink().0, vec![vec![0, 1], vec![2, 3]]);
+}
+
+#[test]
+fn try_push_ignores_policy() {
+    let mut ring = SpillRing::<i32, 2>::builder()
+        .sink(CollectSpout::new())
+        .policy(SpillNewest)
+        .build();
+    assert!(ring.try_push(1).is_ok());
+    assert!(ring.try_push(2).is_ok());
+    assert_eq!(ring.try_push(3).unwrap_err().into_inner(), 3);
+    assert!(ring.sink().items().is_empty());
+}
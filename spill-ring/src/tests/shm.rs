@@ -0,0 +1,253 @@
+extern crate std;
+
+use std::fs;
+use std::io::ErrorKind;
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
+use std::process::Command;
+use std::string::String;
+use std::time::Duration;
+use std::vec::Vec;
+
+use crate::{RingProducer, ShmRing};
+use spout::{CollectSpout, DropSpout};
+
+/// Set in the child process to the path of the ring to drain.
+const CHILD_ENV: &str = "SPILL_RING_SHM_CHILD";
+const CHILD_TEST: &str = "tests::shm::child_consumer";
+/// Set in the child process to the path of the ring to die on.
+const DYING_CHILD_ENV: &str = "SPILL_RING_SHM_DYING_CHILD";
+const DYING_CHILD_TEST: &str = "tests::shm::child_killed_mid_pop";
+
+/// A ring file per test, removed on drop.
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> Self {
+        Self(
+            std::env::temp_dir().join(std::format!("spill-ring-shm-{}-{name}", std::process::id())),
+        )
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Child process entry point: attaches to the ring named by [`CHILD_ENV`],
+/// pops until the producer is closed and the ring is empty, and prints
+/// what it received. A no-op when run as an ordinary test.
+#[test]
+fn child_consumer() {
+    let Ok(path) = std::env::var(CHILD_ENV) else {
+        return;
+    };
+    let mut consumer = ShmRing::<u64, 64>::attach(&path).unwrap();
+    let mut received = Vec::new();
+    loop {
+        match consumer.pop() {
+            Some(item) => received.push(item),
+            None if consumer.is_producer_closed() => {
+                received.extend(consumer.drain());
+                break;
+            }
+            None => std::thread::yield_now(),
+        }
+    }
+    let items: Vec<String> = received.iter().map(|i| std::format!("{i}")).collect();
+    std::println!("received:{}", items.join(","));
+}
+
+/// Child process entry point: attaches to the ring named by
+/// [`DYING_CHILD_ENV`], pops two items, then claims a third and kills
+/// itself before freeing its slot. A no-op when run as an ordinary test.
+#[test]
+fn child_killed_mid_pop() {
+    let Ok(path) = std::env::var(DYING_CHILD_ENV) else {
+        return;
+    };
+    let mut consumer = ShmRing::<u64, 8>::attach(&path).unwrap();
+    assert_eq!(consumer.pop(), Some(0));
+    assert_eq!(consumer.pop(), Some(1));
+    consumer.abandon_pop();
+    // SAFETY: plain libc calls on this process.
+    unsafe { libc::kill(libc::getpid(), libc::SIGKILL) };
+}
+
+/// Run the child test `test` in a new process against the ring at `path`.
+fn spawn_child(test: &str, env: &str, path: &str) -> std::process::Child {
+    Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--nocapture", "--test-threads=1"])
+        .env(env, path)
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+/// Run [`child_consumer`] in a new process against the ring at `path`.
+fn spawn_consumer(path: &str) -> std::process::Child {
+    spawn_child(CHILD_TEST, CHILD_ENV, path)
+}
+
+/// Wait for the child and parse the items it received.
+fn received(child: std::process::Child) -> Vec<u64> {
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "child consumer failed");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // The harness prints the test name on the same line.
+    let line = stdout
+        .lines()
+        .find_map(|line| line.split_once("received:").map(|(_, items)| items))
+        .expect("child printed no items");
+    line.split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn consumer_in_child_process_sees_every_unspilled_item() {
+    let path = TempPath::new("child");
+    let mut producer = ShmRing::<u64, 64>::create_with_sink(&path.0, CollectSpout::new()).unwrap();
+    let mut child = spawn_consumer(path.0.to_str().unwrap());
+
+    // Wait until the child has attached and popped the first item.
+    producer.push(0);
+    while !producer.is_empty() {
+        assert!(child.try_wait().unwrap().is_none(), "child consumer exited");
+        std::thread::yield_now();
+    }
+    for i in 1..20_000 {
+        producer.push(i);
+        if i % 256 == 0 {
+            std::thread::yield_now();
+        }
+    }
+    let spilled = producer.sink().items().to_vec();
+    drop(producer);
+
+    let received = received(child);
+    assert_eq!(received[0], 0);
+    assert!(received.windows(2).all(|w| w[0] < w[1]));
+    assert!(spilled.windows(2).all(|w| w[0] < w[1]));
+    // Every item was either popped or spilled, never both.
+    let mut all: Vec<u64> = received.iter().chain(&spilled).copied().collect();
+    all.sort_unstable();
+    assert_eq!(all, (0..20_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn memfd_ring_is_reachable_from_another_process() {
+    let mut producer = ShmRing::<u64, 64>::create_memfd("spill-ring-test", DropSpout).unwrap();
+    // Keep the memfd open after the producer closes.
+    let file = producer.file().try_clone().unwrap();
+    for i in 0..10 {
+        producer.push(i);
+    }
+    drop(producer);
+
+    let path = std::format!("/proc/{}/fd/{}", std::process::id(), file.as_raw_fd());
+    assert_eq!(received(spawn_consumer(&path)), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn attach_in_same_process_shares_items() {
+    let path = TempPath::new("local");
+    let mut producer = ShmRing::<u32, 4>::create_with_sink(&path.0, CollectSpout::new()).unwrap();
+    let mut consumer = ShmRing::<u32, 4>::attach(&path.0).unwrap();
+
+    for i in 0..6 {
+        producer.push(i);
+    }
+    assert_eq!(producer.sink().items(), [0, 1]);
+    assert_eq!(consumer.len(), 4);
+    assert_eq!(consumer.pop(), Some(2));
+    assert!(producer.try_push(6).is_ok());
+    assert!(producer.try_push(7).is_err());
+
+    assert!(!consumer.is_producer_closed());
+    drop(producer);
+    assert!(consumer.is_producer_closed());
+    assert_eq!(consumer.drain().collect::<Vec<_>>(), [3, 4, 5, 6]);
+}
+
+#[test]
+fn attach_rejects_mismatched_layout() {
+    let path = TempPath::new("layout");
+    let _producer = ShmRing::<u64, 8>::create(&path.0).unwrap();
+
+    let err = ShmRing::<u64, 16>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = ShmRing::<u32, 8>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(ShmRing::<u64, 8>::attach(&path.0).is_ok());
+}
+
+#[test]
+fn attach_rejects_unknown_version_and_foreign_files() {
+    let path = TempPath::new("version");
+    let producer = ShmRing::<u64, 8>::create(&path.0).unwrap();
+    drop(producer);
+    let mut bytes = fs::read(&path.0).unwrap();
+    bytes[8] = bytes[8].wrapping_add(1);
+    fs::write(&path.0, &bytes).unwrap();
+    let err = ShmRing::<u64, 8>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    fs::write(&path.0, std::vec![0xAB; 4096]).unwrap();
+    let err = ShmRing::<u64, 8>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    // Zeroed: the producer has not published the header yet.
+    fs::write(&path.0, std::vec![0; 4096]).unwrap();
+    let err = ShmRing::<u64, 8>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+
+    fs::write(&path.0, b"short").unwrap();
+    let err = ShmRing::<u64, 8>::attach(&path.0).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn create_replaces_existing_ring() {
+    let path = TempPath::new("replace");
+    let mut old = ShmRing::<u64, 8>::create(&path.0).unwrap();
+    old.push(1);
+    let mut old_consumer = ShmRing::<u64, 8>::attach(&path.0).unwrap();
+
+    let _new = ShmRing::<u64, 8>::create(&path.0).unwrap();
+    let mut new_consumer = ShmRing::<u64, 8>::attach(&path.0).unwrap();
+    assert_eq!(new_consumer.pop(), None);
+    // The old mapping is untouched.
+    assert_eq!(old_consumer.pop(), Some(1));
+}
+
+#[test]
+fn producer_reclaims_slot_from_consumer_killed_mid_pop() {
+    let path = TempPath::new("killed");
+    let mut producer = ShmRing::<u64, 8>::create_with_sink(&path.0, CollectSpout::new()).unwrap();
+    producer.set_reclaim_timeout(Duration::from_millis(10));
+    for i in 0..8 {
+        producer.push(i);
+    }
+
+    let mut child = spawn_child(DYING_CHILD_TEST, DYING_CHILD_ENV, path.0.to_str().unwrap());
+    let status = child.wait().unwrap();
+    assert!(!status.success(), "child was meant to die mid-pop");
+
+    // Items 8 and 9 take the slots the child freed; item 10 needs the slot
+    // the dead child claimed but never freed.
+    for i in 8..24 {
+        producer.push(i);
+    }
+    // The claimed item was never delivered, so it is spilled with the rest.
+    assert_eq!(producer.sink().items(), (2..16).collect::<Vec<_>>());
+
+    let mut consumer = ShmRing::<u64, 8>::attach(&path.0).unwrap();
+    assert_eq!(
+        consumer.drain().collect::<Vec<_>>(),
+        (16..24).collect::<Vec<_>>()
+    );
+}
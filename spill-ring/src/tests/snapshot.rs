@@ -0,0 +1,142 @@
+extern crate std;
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::{MpscRing, SnapshotInfo, SpillNewest, SpillRing, collect};
+use bytecast::BytesError;
+use spout::CollectSpout;
+
+#[test]
+fn round_trip_keeps_fifo_order_and_capacity() {
+    let ring = SpillRing::<u64, 8>::new();
+    for i in 0..12 {
+        ring.push(i);
+    }
+    let bytes = ring.snapshot_to_bytes().unwrap();
+    assert_eq!(ring.len(), 8, "snapshot leaves the ring unchanged");
+    assert_eq!(
+        SnapshotInfo::read(&bytes).unwrap(),
+        SnapshotInfo {
+            capacity: 8,
+            len: 8
+        }
+    );
+
+    let mut restored = SpillRing::<u64, 8>::new();
+    assert_eq!(restored.restore_from_bytes(&bytes), Ok(8));
+    let items: Vec<u64> = restored.drain().collect();
+    assert_eq!(items, [4, 5, 6, 7, 8, 9, 10, 11]);
+}
+
+#[test]
+fn round_trip_variable_length_items() {
+    let ring = SpillRing::<String, 4>::new();
+    for word in ["alpha", "", "gamma"] {
+        ring.push(word.to_string());
+    }
+    let bytes = ring.snapshot_to_bytes().unwrap();
+
+    let mut restored = SpillRing::<String, 4>::new();
+    restored.restore_from_bytes(&bytes).unwrap();
+    let items: Vec<String> = restored.drain().collect();
+    assert_eq!(items, ["alpha", "", "gamma"]);
+}
+
+#[test]
+fn empty_ring_round_trips() {
+    let ring = SpillRing::<u32, 4>::new();
+    let bytes = ring.snapshot_to_bytes().unwrap();
+    let restored = SpillRing::<u32, 4>::new();
+    assert_eq!(restored.restore_from_bytes(&bytes), Ok(0));
+    assert!(restored.is_empty());
+}
+
+#[test]
+fn restore_into_smaller_ring_spills_excess() {
+    let ring = SpillRing::<u32, 8>::new();
+    for i in 0..8 {
+        ring.push(i);
+    }
+    let bytes = ring.snapshot_to_bytes().unwrap();
+
+    let mut small = SpillRing::<u32, 4, _>::with_sink(CollectSpout::new());
+    assert_eq!(small.restore_from_bytes(&bytes), Ok(8));
+    assert_eq!(small.sink().items(), [0, 1, 2, 3]);
+    let kept: Vec<u32> = small.drain().collect();
+    assert_eq!(kept, [4, 5, 6, 7]);
+}
+
+#[test]
+fn restore_follows_overflow_policy() {
+    let ring = SpillRing::<u32, 8>::new();
+    for i in 0..6 {
+        ring.push(i);
+    }
+    let bytes = ring.snapshot_to_bytes().unwrap();
+
+    let mut small = SpillRing::<u32, 4>::builder()
+        .sink(CollectSpout::new())
+        .policy(SpillNewest)
+        .build();
+    small.restore_from_bytes(&bytes).unwrap();
+    assert_eq!(small.sink().items(), [4, 5]);
+}
+
+#[test]
+fn invalid_snapshot_leaves_ring_unchanged() {
+    let ring = SpillRing::<u32, 4>::new();
+    ring.push(1);
+    ring.push(2);
+    let bytes = ring.snapshot_to_bytes().unwrap();
+
+    let target = SpillRing::<u32, 4>::new();
+    target.push(9);
+
+    // Cut off in the middle of the last item.
+    assert!(matches!(
+        target.restore_from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BytesError::UnexpectedEof { .. })
+    ));
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert!(matches!(
+        target.restore_from_bytes(&bad_magic),
+        Err(BytesError::InvalidData { .. })
+    ));
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(target.restore_from_bytes(&trailing).is_err());
+    assert!(matches!(
+        SnapshotInfo::read(&bytes[..3]),
+        Err(BytesError::UnexpectedEof { .. })
+    ));
+
+    assert_eq!(target.len(), 1);
+    assert_eq!(target.pop(), Some(9));
+}
+
+#[test]
+fn consumer_snapshot_covers_all_rings() {
+    let (producers, mut consumer) = MpscRing::<u32, 4>::with_consumer(2);
+    for (id, producer) in producers.iter().enumerate() {
+        for i in 0..3 {
+            producer.push(id as u32 * 10 + i);
+        }
+    }
+    collect(producers, &mut consumer);
+
+    let bytes = consumer.snapshot_to_bytes().unwrap();
+    assert_eq!(
+        SnapshotInfo::read(&bytes).unwrap(),
+        SnapshotInfo {
+            capacity: 8,
+            len: 6
+        }
+    );
+
+    let mut restored = SpillRing::<u32, 8>::new();
+    restored.restore_from_bytes(&bytes).unwrap();
+    let items: Vec<u32> = restored.drain().collect();
+    assert_eq!(items, [0, 1, 2, 10, 11, 12]);
+}
@@ -51,6 +51,24 @@ fn batch_sink_with_ring_chain() {
     assert_eq!(ring.pop(), Some(999));
 }
 
+#[test]
+fn ring_as_source_pumps_fifo() {
+    use spout::{Source, pump};
+
+    let mut ring = SpillRing::<i32, 8>::new();
+    for i in 0..6 {
+        ring.push(i);
+    }
+
+    let mut sink = CollectSpout::new();
+    let moved = pump(&mut ring, &mut sink, 4).unwrap();
+
+    assert_eq!(moved, 6);
+    assert_eq!(sink.into_items(), vec![0, 1, 2, 3, 4, 5]);
+    assert!(ring.is_empty());
+    assert_eq!(ring.recv(), Ok(None));
+}
+
 #[cfg(feature = "std")]
 mod channel_sink_tests {
     use spout::{ChannelSpout, Spout};
@@ -71,6 +89,84 @@ mod channel_sink_tests {
         assert_eq!(rx.recv(), Ok(99));
     }
 
+    #[test]
+    fn fallible_sink_behind_dead_letter_spout() {
+        use crate::SpillRing;
+        use spout::{CollectSpout, DeadLetterSpout};
+
+        let (tx, rx) = mpsc::channel::<i32>();
+        let sink = DeadLetterSpout::new(ChannelSpout::new(tx), CollectSpout::new());
+        let ring = SpillRing::<i32, 2, _>::with_sink(sink);
+
+        ring.push(1);
+        ring.push(2);
+        ring.push(3); // Evicts 1 to the channel
+        assert_eq!(rx.recv(), Ok(1));
+
+        drop(rx);
+        ring.push(4); // Evicts 2, channel is closed
+
+        let dead = ring.sink_ref().dead_letters().items();
+        assert_eq!(ring.sink_ref().failures(), 1);
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].item, Some(2));
+        assert_eq!(dead[0].error.0, 2);
+    }
+
+    #[test]
+    fn bounded_sink_for_spill_ring() {
+        use crate::SpillRing;
+        use spout::{DropSpout, ErrorCaptureSpout, OverflowPolicy, bounded};
+
+        let (tx, rx) = bounded(2, OverflowPolicy::DropOldest);
+        let mut ring = SpillRing::<i32, 2, _>::with_sink(ErrorCaptureSpout::new(tx, DropSpout));
+
+        for i in 0..6 {
+            ring.push(i);
+        }
+        // 0..4 evicted into a queue of 2, oldest dropped
+        assert_eq!(rx.try_recv(), Ok(2));
+        assert_eq!(rx.try_recv(), Ok(3));
+        assert_eq!(rx.dropped_oldest(), 2);
+
+        ring.flush();
+        assert_eq!(rx.try_recv(), Ok(4));
+        assert_eq!(rx.try_recv(), Ok(5));
+    }
+
+    #[test]
+    fn bounded_sink_for_mpsc_ring() {
+        use crate::MpscRing;
+        use spout::{DropSpout, ErrorCaptureSpout, OverflowPolicy, bounded};
+        use std::thread;
+
+        let (tx, rx) = bounded(16, OverflowPolicy::Block);
+        let sink = ErrorCaptureSpout::new(tx, DropSpout);
+        let producers = MpscRing::<u64, 4, _>::with_sink(2, sink);
+
+        let consumer = thread::spawn(move || {
+            let mut items = std::vec::Vec::new();
+            while let Ok(item) = rx.recv() {
+                items.push(item);
+            }
+            items
+        });
+
+        thread::scope(|s| {
+            for (id, producer) in producers.into_iter().enumerate() {
+                s.spawn(move || {
+                    for i in 0..100 {
+                        producer.push(id as u64 * 1000 + i);
+                    }
+                    // Remaining items flush to the queue when the producer drops
+                });
+            }
+        });
+
+        let items = consumer.join().unwrap();
+        assert_eq!(items.len(), 200);
+    }
+
     #[test]
     fn channel_sink_ignores_disconnected_receiver() {
         let (tx, rx) = mpsc::channel::<i32>();
@@ -0,0 +1,217 @@
+extern crate std;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{thread, vec, vec::Vec};
+
+use crate::SpscRing;
+use crate::traits::{RingInfo, RingProducer};
+use spout::{CollectSpout, Source};
+
+#[test]
+fn push_pop_single_thread() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 4>::new();
+    assert!(consumer.is_empty());
+    assert_eq!(consumer.pop(), None);
+
+    producer.push(1);
+    producer.push(2);
+    assert_eq!(producer.len(), 2);
+    assert_eq!(consumer.len(), 2);
+    assert_eq!(consumer.pop(), Some(1));
+    assert_eq!(consumer.pop(), Some(2));
+    assert_eq!(consumer.pop(), None);
+}
+
+#[test]
+fn overflow_spills_oldest_to_sink() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 4>::with_sink(CollectSpout::new());
+    for i in 0..10 {
+        producer.push(i);
+    }
+    assert!(producer.is_full());
+    assert_eq!(producer.sink().items(), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(consumer.drain().collect::<Vec<_>>(), vec![6, 7, 8, 9]);
+}
+
+#[test]
+fn flush_sends_buffered_items_to_sink() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 8>::with_sink(CollectSpout::new());
+    producer.push(1);
+    producer.push(2);
+    assert_eq!(producer.flush(), 2);
+    assert_eq!(producer.sink().items(), vec![1, 2]);
+    assert_eq!(consumer.pop(), None);
+}
+
+#[test]
+fn try_push_fails_when_full() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 2>::new();
+    assert!(producer.try_push(1).is_ok());
+    assert!(producer.try_push(2).is_ok());
+    assert_eq!(producer.try_push(3).unwrap_err().into_inner(), 3);
+    assert!(RingInfo::is_full(&producer));
+
+    assert_eq!(consumer.pop(), Some(1));
+    assert!(producer.try_push(3).is_ok());
+    assert_eq!(consumer.drain().collect::<Vec<_>>(), vec![2, 3]);
+}
+
+#[test]
+fn capacity_one() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 1>::with_sink(CollectSpout::new());
+    producer.push(1);
+    producer.push(2);
+    assert_eq!(consumer.pop(), Some(2));
+    producer.push(3);
+    assert_eq!(consumer.pop(), Some(3));
+    assert_eq!(producer.sink().items(), vec![1]);
+}
+
+#[test]
+fn recv_finishes_after_producer_drops() {
+    let (mut producer, mut consumer) = SpscRing::<u32, 64>::new();
+    producer.push(1);
+    producer.push(2);
+    drop(producer);
+    assert!(consumer.is_producer_closed());
+    assert_eq!(consumer.recv(), Ok(Some(1)));
+    assert_eq!(consumer.recv(), Ok(Some(2)));
+    assert_eq!(consumer.recv(), Ok(None));
+}
+
+#[test]
+fn leftover_items_dropped_once() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Counted;
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    DROPS.store(0, Ordering::SeqCst);
+    let (mut producer, mut consumer) = SpscRing::<Counted, 4>::new();
+    for _ in 0..6 {
+        producer.push(Counted);
+    }
+    // Two evicted to the DropSpout.
+    assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+    drop(consumer.pop());
+    assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+    drop(producer);
+    drop(consumer);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 6);
+}
+
+/// Run a producer and a live consumer concurrently and check that every
+/// item ends up exactly once in the consumer's output, the sink, or the
+/// ring, with each stream in push order.
+fn stress<const N: usize>(items: u64) {
+    let (mut producer, mut consumer) = SpscRing::<u64, N>::with_sink(CollectSpout::new());
+
+    let handle = thread::spawn(move || {
+        for i in 0..items {
+            producer.push(i);
+        }
+        producer
+    });
+
+    let mut popped = Vec::new();
+    while !handle.is_finished() {
+        if let Some(item) = consumer.pop() {
+            popped.push(item);
+        }
+    }
+    let producer = handle.join().unwrap();
+    let spilled = producer.sink().items().to_vec();
+    let remaining: Vec<u64> = consumer.drain().collect();
+
+    for stream in [&popped, &spilled, &remaining] {
+        assert!(stream.windows(2).all(|w| w[0] < w[1]), "order violated");
+    }
+    let mut all: Vec<u64> = popped
+        .iter()
+        .chain(&spilled)
+        .chain(&remaining)
+        .copied()
+        .collect();
+    all.sort_unstable();
+    assert_eq!(all.len() as u64, items, "item lost or duplicated");
+    assert!(all.iter().copied().eq(0..items), "item lost or duplicated");
+}
+
+#[test]
+fn stress_tiny_ring_heavy_eviction() {
+    for _ in 0..20 {
+        stress::<2>(20_000);
+    }
+}
+
+#[test]
+fn stress_small_ring() {
+    for _ in 0..5 {
+        stress::<16>(100_000);
+    }
+}
+
+#[test]
+fn stress_large_ring() {
+    stress::<4096>(500_000);
+}
+
+#[test]
+fn stress_no_double_drop() {
+    let live = Arc::new(AtomicUsize::new(0));
+
+    struct Tracked(Arc<AtomicUsize>);
+    impl Tracked {
+        fn new(live: &Arc<AtomicUsize>) -> Self {
+            live.fetch_add(1, Ordering::SeqCst);
+            Self(Arc::clone(live))
+        }
+    }
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            let before = self.0.fetch_sub(1, Ordering::SeqCst);
+            assert!(before > 0, "dropped more items than created");
+        }
+    }
+
+    let (mut producer, mut consumer) = SpscRing::<Tracked, 8>::new();
+    let producer_live = Arc::clone(&live);
+    let handle = thread::spawn(move || {
+        for _ in 0..100_000 {
+            producer.push(Tracked::new(&producer_live));
+        }
+    });
+    while !handle.is_finished() {
+        drop(consumer.pop());
+    }
+    handle.join().unwrap();
+    drop(consumer);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn stress_source_recv_sees_every_item_without_eviction() {
+    let (mut producer, mut consumer) = SpscRing::<u64, 64>::new();
+    let handle = thread::spawn(move || {
+        for i in 0..50_000u64 {
+            // Back off instead of evicting so the consumer sees everything.
+            let mut item = i;
+            while let Err(e) = producer.try_push(item) {
+                item = e.into_inner();
+                thread::yield_now();
+            }
+        }
+    });
+
+    let mut expected = 0u64;
+    while let Ok(Some(item)) = consumer.recv() {
+        assert_eq!(item, expected);
+        expected += 1;
+    }
+    handle.join().unwrap();
+    assert_eq!(expected, 50_000);
+}
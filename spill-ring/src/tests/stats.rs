@@ -0,0 +1,174 @@
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{
+    Counters, MpscRing, NoStats, Overwrite, RingStats, RingStatsInfo, SpillNewest, SpillOldest,
+    SpillRing, collect,
+};
+use spout::{CollectSpout, DropSpout};
+
+#[test]
+fn counters_track_pushes_pops_and_peak() {
+    let ring = SpillRing::<u32, 8>::builder().stats::<Counters>().build();
+    for i in 0..5 {
+        ring.push(i);
+    }
+    assert_eq!(ring.pop(), Some(0));
+    assert_eq!(ring.pop(), Some(1));
+    ring.push(5);
+
+    let stats = ring.stats();
+    assert_eq!(stats.pushes, 6);
+    assert_eq!(stats.pops, 2);
+    assert_eq!(stats.evictions, 0);
+    assert_eq!(stats.peak_len, 5);
+}
+
+#[test]
+fn counters_track_evictions_to_sink() {
+    let mut ring = SpillRing::<u32, 4>::builder()
+        .sink(CollectSpout::new())
+        .stats::<Counters>()
+        .build();
+    for i in 0..10 {
+        ring.push_mut(i);
+    }
+    let stats = ring.stats();
+    assert_eq!(stats.pushes, 10);
+    assert_eq!(stats.evictions, 6);
+    assert_eq!(stats.peak_len, 4);
+    assert_eq!(ring.sink().items().len(), 6);
+    assert!((stats.eviction_rate() - 0.6).abs() < 1e-9);
+}
+
+#[test]
+fn rejected_and_overwritten_items_count_as_evictions() {
+    let newest = SpillRing::<u32, 2>::builder()
+        .policy(SpillNewest)
+        .stats::<Counters>()
+        .build();
+    for i in 0..5 {
+        newest.push(i);
+    }
+    assert_eq!(newest.stats().evictions, 3);
+
+    let overwrite = SpillRing::<u32, 2>::builder()
+        .policy(Overwrite)
+        .stats::<Counters>()
+        .build();
+    for i in 0..5 {
+        overwrite.push(i);
+    }
+    assert_eq!(overwrite.stats().evictions, 3);
+}
+
+#[test]
+fn slices_drain_and_flush_are_counted() {
+    let mut ring = SpillRing::<u32, 8>::builder().stats::<Counters>().build();
+    ring.push_slice(&[1, 2, 3, 4, 5, 6]);
+    let drained: Vec<u32> = ring.drain().take(2).collect();
+    assert_eq!(drained, [1, 2]);
+    ring.flush();
+
+    let stats = ring.stats();
+    assert_eq!(stats.pushes, 6);
+    assert_eq!(stats.pops, 2);
+    assert_eq!(stats.flushes, 1);
+    assert_eq!(stats.peak_len, 6);
+}
+
+#[test]
+fn reset_zeroes_counters() {
+    let ring = SpillRing::<u32, 4>::builder().stats::<Counters>().build();
+    ring.push(1);
+    ring.reset_stats();
+    assert_eq!(ring.stats(), RingStats::default());
+}
+
+#[test]
+fn no_stats_adds_no_size() {
+    type Plain = SpillRing<u64, 8>;
+    type Explicit = SpillRing<u64, 8, DropSpout, SpillOldest, NoStats>;
+    type Counted = SpillRing<u64, 8, DropSpout, SpillOldest, Counters>;
+    assert_eq!(size_of::<Plain>(), size_of::<Explicit>());
+    assert!(size_of::<Counted>() > size_of::<Plain>());
+}
+
+#[test]
+fn merge_adds_counts_and_keeps_highest_peak() {
+    let a = RingStats {
+        pushes: 3,
+        pops: 1,
+        evictions: 0,
+        flushes: 1,
+        peak_len: 3,
+    };
+    let b = RingStats {
+        pushes: 5,
+        pops: 0,
+        evictions: 2,
+        flushes: 0,
+        peak_len: 4,
+    };
+    let sum: RingStats = [a, b].into_iter().sum();
+    assert_eq!(
+        sum,
+        RingStats {
+            pushes: 8,
+            pops: 1,
+            evictions: 2,
+            flushes: 1,
+            peak_len: 4,
+        }
+    );
+}
+
+#[test]
+fn consumer_sums_producer_stats() {
+    let (producers, mut consumer) = MpscRing::<u32, 4, _, Counters>::with_consumer(3);
+    for (id, producer) in producers.iter().enumerate() {
+        for i in 0..=id as u32 * 3 {
+            producer.push(i);
+        }
+    }
+    assert_eq!(producers[2].stats().pushes, 7);
+    collect(producers, &mut consumer);
+
+    let stats = RingStatsInfo::stats(&consumer);
+    assert_eq!(stats.pushes, 1 + 4 + 7);
+    assert_eq!(stats.evictions, 3);
+    assert_eq!(stats.peak_len, 4);
+
+    let mut sink = CollectSpout::new();
+    consumer.drain(&mut sink);
+    assert_eq!(consumer.stats().pops, 9);
+}
+
+#[test]
+fn pool_sums_worker_stats_after_each_run() {
+    let mut pool =
+        MpscRing::<u64, 8>::pool(3)
+            .stats::<Counters>()
+            .spawn(|ring, worker_id, count: &u64| {
+                for i in 0..*count + worker_id as u64 {
+                    ring.push(i);
+                }
+            });
+    assert_eq!(pool.stats(), RingStats::default());
+
+    pool.run(&4).unwrap();
+    let stats = pool.stats();
+    assert_eq!(stats.pushes, 4 + 5 + 6);
+    assert_eq!(stats.peak_len, 6);
+
+    pool.run(&4).unwrap();
+    assert_eq!(pool.stats().pushes, 30);
+    assert_eq!(pool.stats().evictions, 2 + 4);
+
+    pool.resize(2);
+    assert_eq!(pool.stats().pushes, 4 + 5 + 4 + 5);
+
+    let consumer = pool.into_consumer();
+    assert_eq!(consumer.stats().pushes, 18);
+}
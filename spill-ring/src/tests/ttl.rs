@@ -0,0 +1,113 @@
+extern crate std;
+
+use std::cell::Cell;
+use std::vec::Vec;
+
+use crate::{SpillNewest, SpillRing, TimedSpillRing};
+use spout::CollectSpout;
+
+#[test]
+fn expire_spills_items_older_than_ttl() {
+    let now = Cell::new(0u64);
+    let mut ring = TimedSpillRing::<u32, 8, _, _>::with_sink(|| now.get(), 10, CollectSpout::new());
+    for i in 0..5 {
+        now.set(i as u64 * 5);
+        ring.push(i);
+    }
+    // Ticks 0, 5, 10, 15, 20. At 22 the cutoff is 12.
+    assert_eq!(ring.expire(22), 3);
+    assert_eq!(ring.sink().items(), [0, 1, 2]);
+    assert_eq!(ring.oldest_tick(), Some(15));
+    assert_eq!(ring.len(), 2);
+
+    // Nothing is older than the TTL yet.
+    assert_eq!(ring.expire(5), 0);
+}
+
+#[test]
+fn expire_now_uses_clock() {
+    let now = Cell::new(100u64);
+    let mut ring = TimedSpillRing::<u32, 4, _>::new(|| now.get(), 50);
+    ring.push(1);
+    now.set(149);
+    assert_eq!(ring.expire_now(), 0);
+    now.set(151);
+    assert_eq!(ring.expire_now(), 1);
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn drain_older_than_yields_oldest_first_and_stops() {
+    let mut ring = TimedSpillRing::<u32, 8, _>::new(|| 0, 0);
+    for i in 0..6 {
+        ring.push_at(i, u64::from(i) * 10);
+    }
+    let old: Vec<u32> = ring.drain_older_than(30).collect();
+    assert_eq!(old, [0, 1, 2]);
+    assert_eq!(ring.pop_with_tick(), Some((30, 3)));
+
+    // Stopping early keeps the rest.
+    assert_eq!(ring.drain_older_than(100).next(), Some(4));
+    assert_eq!(ring.peek(), Some(&5));
+}
+
+#[test]
+fn ticks_follow_count_eviction() {
+    let mut ring = TimedSpillRing::<u32, 4, _, _>::with_sink(|| 0, 0, CollectSpout::new());
+    for i in 0..7 {
+        ring.push_at(i, u64::from(i) + 100);
+    }
+    assert_eq!(ring.sink().items(), [0, 1, 2]);
+    let ticks: Vec<(u64, u32)> = ring.iter().map(|(t, v)| (t, *v)).collect();
+    assert_eq!(ticks, [(103, 3), (104, 4), (105, 5), (106, 6)]);
+}
+
+#[test]
+fn rejected_push_keeps_existing_ticks() {
+    let mut ring = SpillRing::<u32, 2>::builder()
+        .sink(CollectSpout::new())
+        .policy(SpillNewest)
+        .build_timed(|| 0, 0);
+    ring.push_at(1, 10);
+    ring.push_at(2, 20);
+    ring.push_at(3, 30);
+    assert_eq!(ring.sink().items(), [3]);
+    assert_eq!(ring.oldest_tick(), Some(10));
+    assert_eq!(ring.pop_with_tick(), Some((10, 1)));
+    assert_eq!(ring.pop_with_tick(), Some((20, 2)));
+    assert_eq!(ring.pop_with_tick(), None);
+}
+
+#[test]
+fn set_ttl_applies_to_next_expiry() {
+    let mut ring = TimedSpillRing::<u32, 4, _>::new(|| 0, 100);
+    ring.push_at(1, 0);
+    assert_eq!(ring.expire(50), 0);
+    ring.set_ttl(10);
+    assert_eq!(ring.ttl(), 10);
+    assert_eq!(ring.expire(50), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn monotonic_clock_expires_by_nanoseconds() {
+    use spout::{Clock, MonotonicClock};
+    use std::time::Duration;
+
+    let hour = Duration::from_secs(3600).as_nanos() as u64;
+    let mut ring = TimedSpillRing::<u32, 4, _>::new(MonotonicClock::new(), hour);
+    ring.push(1);
+    assert_eq!(ring.expire_now(), 0);
+    ring.set_ttl(0);
+    let later = ring.clock().now() + 1;
+    assert_eq!(ring.expire(later), 1);
+}
+
+#[test]
+fn flush_sends_remaining_items() {
+    let mut ring = TimedSpillRing::<u32, 4, _, _>::with_sink(|| 0, 0, CollectSpout::new());
+    ring.push(1);
+    ring.push(2);
+    assert_eq!(ring.flush(), 2);
+    assert_eq!(ring.sink().items(), [1, 2]);
+}
@@ -18,6 +18,16 @@ pub trait RingInfo {
     }
 }
 
+/// Usage counters of a ring, or summed across a set of rings.
+///
+/// Implemented by rings built with a stats layer that implements
+/// [`EnabledStats`](crate::EnabledStats); a ring without one does not
+/// implement it.
+pub trait RingStatsInfo {
+    /// Counters recorded so far.
+    fn stats(&self) -> crate::RingStats;
+}
+
 /// Producer side of a ring buffer.
 ///
 /// Methods for pushing items into the ring. Implementations
@@ -0,0 +1,287 @@
+//! Time-to-live mode: rings that hold "the last 30 seconds" instead of
+//! "the last N items".
+//!
+//! A [`TimedSpillRing`] wraps a [`SpillRing`] and records an insertion tick
+//! from a [`Clock`] next to every slot. Any `Fn() -> u64` is a clock, and
+//! [`MonotonicClock`](spout::MonotonicClock) counts nanoseconds under `std`.
+//! Plain `SpillRing`s carry no ticks and pay nothing for this mode.
+
+use core::cell::Cell;
+
+use crate::policy::{OverflowPolicy, SpillOldest};
+use crate::ring::SpillRing;
+use spout::{Clock, DropSpout, Spout};
+
+/// A [`SpillRing`] whose items expire after a time-to-live.
+///
+/// Each push records the clock's current tick, and the TTL is measured in
+/// the same unit. Items still leave by count when the ring is full, as set
+/// by the overflow policy `P`; in addition, [`expire`](Self::expire) spills
+/// every item older than the TTL to the sink, and
+/// [`drain_older_than`](Self::drain_older_than) removes items by age.
+/// Expiry is explicit: nothing happens on a timer.
+///
+/// Build one with [`SpillRingBuilder::build_timed`](crate::SpillRingBuilder::build_timed),
+/// or [`new`](Self::new) / [`with_sink`](Self::with_sink).
+///
+/// # Example
+///
+/// ```
+/// use spill_ring::SpillRing;
+/// use spout::CollectSpout;
+/// use std::cell::Cell;
+///
+/// let now = Cell::new(0u64);
+/// let mut ring = SpillRing::<&str, 8>::builder()
+///     .sink(CollectSpout::new())
+///     .build_timed(|| now.get(), 30);
+///
+/// ring.push("boot");
+/// now.set(20);
+/// ring.push("ready");
+/// now.set(45);
+/// assert_eq!(ring.expire_now(), 1);
+/// assert_eq!(ring.sink().items(), ["boot"]);
+/// assert_eq!(ring.pop(), Some("ready"));
+/// ```
+pub struct TimedSpillRing<
+    T,
+    const N: usize,
+    C: Clock,
+    S: Spout<T, Error = core::convert::Infallible> = DropSpout,
+    P: OverflowPolicy = SpillOldest,
+> {
+    ring: SpillRing<T, N, S, P>,
+    /// Insertion tick of the item in the matching buffer slot.
+    ticks: [Cell<u64>; N],
+    clock: C,
+    ttl: u64,
+}
+
+impl<T, const N: usize, C: Clock> TimedSpillRing<T, N, C, DropSpout> {
+    /// Create a timed ring whose spilled and expired items are dropped.
+    #[must_use]
+    pub fn new(clock: C, ttl: u64) -> Self {
+        Self::from_ring(SpillRing::new(), clock, ttl)
+    }
+}
+
+impl<T, const N: usize, C: Clock, S: Spout<T, Error = core::convert::Infallible>>
+    TimedSpillRing<T, N, C, S>
+{
+    /// Create a timed ring whose spilled and expired items go to `sink`.
+    #[must_use]
+    pub fn with_sink(clock: C, ttl: u64, sink: S) -> Self {
+        Self::from_ring(SpillRing::with_sink(sink), clock, ttl)
+    }
+}
+
+impl<T, const N: usize, C: Clock, S: Spout<T, Error = core::convert::Infallible>, P: OverflowPolicy>
+    TimedSpillRing<T, N, C, S, P>
+{
+    /// Wrap an empty ring. Used by the builder.
+    pub(crate) fn from_ring(ring: SpillRing<T, N, S, P>, clock: C, ttl: u64) -> Self {
+        debug_assert!(ring.is_empty());
+        Self {
+            ring,
+            ticks: [const { Cell::new(0) }; N],
+            clock,
+            ttl,
+        }
+    }
+
+    /// Push an item stamped with the clock's current tick.
+    #[inline]
+    pub fn push(&self, item: T) {
+        self.push_at(item, self.clock.now());
+    }
+
+    /// Push an item stamped with `tick`.
+    ///
+    /// Ticks must not decrease from one push to the next, or age-based
+    /// removal will stop early at the out-of-order item.
+    #[inline]
+    pub fn push_at(&self, item: T, tick: u64) {
+        let tail = self.ring.tail.load();
+        self.ring.push(item);
+        // Every policy that accepts the item writes it at the old tail.
+        if self.ring.tail.load() != tail {
+            self.ticks[tail & (N - 1)].set(tick);
+        }
+    }
+
+    /// Pop the oldest item.
+    #[inline]
+    #[must_use]
+    pub fn pop(&self) -> Option<T> {
+        self.ring.pop()
+    }
+
+    /// Pop the oldest item along with its insertion tick.
+    #[inline]
+    #[must_use]
+    pub fn pop_with_tick(&self) -> Option<(u64, T)> {
+        let tick = self.oldest_tick()?;
+        self.ring.pop().map(|item| (tick, item))
+    }
+
+    /// Peek at the oldest item.
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.ring.peek()
+    }
+
+    /// Insertion tick of the oldest item.
+    #[inline]
+    #[must_use]
+    pub fn oldest_tick(&self) -> Option<u64> {
+        if self.ring.is_empty() {
+            return None;
+        }
+        Some(self.ticks[self.ring.head.load() & (N - 1)].get())
+    }
+
+    /// Spill every item pushed before `now - ttl` to the sink. Returns the
+    /// number of items spilled.
+    pub fn expire(&mut self, now: u64) -> usize {
+        let Some(cutoff) = now.checked_sub(self.ttl) else {
+            return 0;
+        };
+        let mut count = 0;
+        while let Some(item) = self.pop_older_than(cutoff) {
+            let _ = self.ring.sink_mut().send(item);
+            count += 1;
+        }
+        if count > 0 {
+            let _ = self.ring.sink_mut().flush();
+        }
+        count
+    }
+
+    /// [`expire`](Self::expire) at the clock's current tick.
+    pub fn expire_now(&mut self) -> usize {
+        let now = self.clock.now();
+        self.expire(now)
+    }
+
+    /// Remove and yield items pushed before `tick`, oldest first.
+    ///
+    /// Unlike [`expire`](Self::expire), the items go to the caller rather
+    /// than the sink. Items not consumed from the iterator stay in the
+    /// ring.
+    pub fn drain_older_than(&mut self, tick: u64) -> DrainOlderThan<'_, T, N, C, S, P> {
+        DrainOlderThan { ring: self, tick }
+    }
+
+    #[inline]
+    fn pop_older_than(&mut self, tick: u64) -> Option<T> {
+        if self.oldest_tick()? < tick {
+            self.ring.pop_mut()
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over items with their insertion ticks, oldest first.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (u64, &T)> + '_ {
+        let head = self.ring.head.load();
+        self.ring
+            .iter()
+            .enumerate()
+            .map(move |(i, item)| (self.ticks[head.wrapping_add(i) & (N - 1)].get(), item))
+    }
+
+    /// The time-to-live.
+    #[inline]
+    #[must_use]
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    /// Change the time-to-live. Takes effect at the next expiry.
+    #[inline]
+    pub fn set_ttl(&mut self, ttl: u64) {
+        self.ttl = ttl;
+    }
+
+    /// Reference to the clock.
+    #[inline]
+    #[must_use]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Number of items in buffer.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// True if empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Buffer capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Flush all items to the sink. Returns the number flushed.
+    pub fn flush(&mut self) -> usize {
+        self.ring.flush()
+    }
+
+    /// Reference to the spout.
+    #[inline]
+    #[must_use]
+    pub fn sink(&mut self) -> &S {
+        self.ring.sink()
+    }
+
+    /// Mutable reference to the spout.
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        self.ring.sink_mut()
+    }
+
+    /// Unwrap into the inner ring, discarding the ticks.
+    #[must_use]
+    pub fn into_ring(self) -> SpillRing<T, N, S, P> {
+        self.ring
+    }
+}
+
+/// Iterator returned by [`TimedSpillRing::drain_older_than`].
+pub struct DrainOlderThan<
+    'a,
+    T,
+    const N: usize,
+    C: Clock,
+    S: Spout<T, Error = core::convert::Infallible>,
+    P: OverflowPolicy,
+> {
+    ring: &'a mut TimedSpillRing<T, N, C, S, P>,
+    tick: u64,
+}
+
+impl<T, const N: usize, C: Clock, S: Spout<T, Error = core::convert::Infallible>, P: OverflowPolicy>
+    Iterator for DrainOlderThan<'_, T, N, C, S, P>
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.ring.pop_older_than(self.tick)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.ring.len()))
+    }
+}
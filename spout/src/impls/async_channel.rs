@@ -0,0 +1,297 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::TryRecvError;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::vec::Vec;
+
+use crate::{AsyncSpout, Source, block_on};
+
+/// Error returned by [`AsyncChannelSpout`] once the receiver is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelClosed;
+
+impl core::fmt::Display for ChannelClosed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("async channel receiver disconnected")
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+impl<T> State<T> {
+    fn wake_senders(&mut self) {
+        for waker in self.send_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.recv_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn park_sender(&mut self, waker: &Waker) {
+        if !self.send_wakers.iter().any(|w| w.will_wake(waker)) {
+            self.send_wakers.push(waker.clone());
+        }
+    }
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // Queue state stays consistent across a panic, so poisoning is ignored.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Create a bounded async channel.
+///
+/// The async counterpart to [`SyncChannelSpout`](crate::SyncChannelSpout):
+/// when `capacity` items are queued, [`AsyncSpout::poll_ready`] returns
+/// `Pending` until the receiver makes room, so backpressure suspends the
+/// producing task instead of blocking its thread. `poll_flush` completes
+/// once the receiver has taken every queued item.
+///
+/// For an unbounded async channel, wrap a
+/// [`ChannelSpout`](crate::ChannelSpout) in an
+/// [`AsyncAdapter`](crate::AsyncAdapter).
+///
+/// # Panics
+/// Panics if `capacity` is 0.
+///
+/// # Example
+///
+/// ```
+/// use spout::{AsyncSpout, async_channel, block_on};
+///
+/// let (mut tx, mut rx) = async_channel(4);
+/// block_on(async {
+///     tx.send_async(1).await.unwrap();
+///     tx.send_async(2).await.unwrap();
+///     assert_eq!(rx.recv().await, Some(1));
+///     assert_eq!(rx.recv().await, Some(2));
+/// });
+/// ```
+pub fn async_channel<T>(capacity: usize) -> (AsyncChannelSpout<T>, AsyncReceiver<T>) {
+    assert!(capacity > 0, "async channel capacity must be at least 1");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            senders: 1,
+            receiver: true,
+            recv_waker: None,
+            send_wakers: Vec::new(),
+        }),
+        capacity,
+    });
+    (
+        AsyncChannelSpout {
+            shared: Arc::clone(&shared),
+        },
+        AsyncReceiver { shared },
+    )
+}
+
+/// Sending half of an [`async_channel`].
+pub struct AsyncChannelSpout<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> AsyncChannelSpout<T> {
+    /// Maximum number of queued items.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// True if no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for AsyncChannelSpout<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for AsyncChannelSpout<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            state.wake_receiver();
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for AsyncChannelSpout<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AsyncChannelSpout")
+            .field("capacity", &self.shared.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> AsyncSpout<T> for AsyncChannelSpout<T> {
+    type Error = ChannelClosed;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mut state = self.shared.lock();
+        if !state.receiver {
+            return Poll::Ready(Err(ChannelClosed));
+        }
+        if state.queue.len() < self.shared.capacity {
+            return Poll::Ready(Ok(()));
+        }
+        state.park_sender(cx.waker());
+        Poll::Pending
+    }
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let mut state = self.shared.lock();
+        if !state.receiver {
+            return Err(ChannelClosed);
+        }
+        state.queue.push_back(item);
+        state.wake_receiver();
+        Ok(())
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mut state = self.shared.lock();
+        if state.queue.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        if !state.receiver {
+            return Poll::Ready(Err(ChannelClosed));
+        }
+        state.park_sender(cx.waker());
+        Poll::Pending
+    }
+}
+
+/// Receiving half of an [`async_channel`].
+pub struct AsyncReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> AsyncReceiver<T> {
+    /// Poll for the next item.
+    ///
+    /// Returns `Ready(None)` once the queue is empty and every sender has
+    /// been dropped.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if let Some(item) = state.queue.pop_front() {
+            state.wake_senders();
+            return Poll::Ready(Some(item));
+        }
+        if state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        state.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Wait for the next item.
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Take the next item without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.queue.pop_front() {
+            Some(item) => {
+                state.wake_senders();
+                Ok(item)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Maximum number of queued items.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// True if no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Blocks the current thread in `recv`. Exhausted once every sender is dropped.
+impl<T> Source<T> for AsyncReceiver<T> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(block_on(AsyncReceiver::recv(self)))
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(AsyncReceiver::try_recv(self).ok())
+    }
+}
+
+impl<T> Drop for AsyncReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver = false;
+        state.wake_senders();
+    }
+}
+
+impl<T> core::fmt::Debug for AsyncReceiver<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AsyncReceiver")
+            .field("capacity", &self.shared.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`AsyncReceiver::recv`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct RecvFuture<'a, T> {
+    receiver: &'a mut AsyncReceiver<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
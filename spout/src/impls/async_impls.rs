@@ -0,0 +1,241 @@
+//! Adapters between [`Spout`] and [`AsyncSpout`], and async batching.
+
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+use crate::{AsyncSpout, Spout};
+
+/// Drive a future to completion on the current thread.
+///
+/// A minimal executor for bridging async spouts into synchronous code and
+/// for tests. With `std`, the thread parks until woken; without it, this
+/// busy-polls.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+
+    #[cfg(feature = "std")]
+    let waker = Waker::from(alloc::sync::Arc::new(ThreadWaker(std::thread::current())));
+    #[cfg(not(feature = "std"))]
+    let waker = Waker::noop().clone();
+
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        #[cfg(feature = "std")]
+        std::thread::park();
+        #[cfg(not(feature = "std"))]
+        core::hint::spin_loop();
+    }
+}
+
+/// Unparks the blocked thread on wake.
+#[cfg(feature = "std")]
+struct ThreadWaker(std::thread::Thread);
+
+#[cfg(feature = "std")]
+impl alloc::task::Wake for ThreadWaker {
+    fn wake(self: alloc::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &alloc::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Uses a synchronous [`Spout`] as an [`AsyncSpout`].
+///
+/// Always ready; `send` and `flush` run the inner spout inline. Suitable for
+/// spouts that never block, such as [`CollectSpout`](crate::CollectSpout)
+/// or [`ChannelSpout`](crate::ChannelSpout).
+#[derive(Debug, Clone, Default)]
+pub struct AsyncAdapter<S> {
+    inner: S,
+}
+
+impl<S> AsyncAdapter<S> {
+    /// Wrap a synchronous spout.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S: Spout<T>> AsyncSpout<T> for AsyncAdapter<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.inner.send(item)
+    }
+
+    #[inline]
+    fn poll_flush(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(self.inner.flush())
+    }
+}
+
+/// Uses an [`AsyncSpout`] as a synchronous [`Spout`].
+///
+/// Each `send` and `flush` blocks the current thread with [`block_on`]
+/// until the async spout is done.
+#[derive(Debug, Clone, Default)]
+pub struct BlockingSpout<A> {
+    inner: A,
+}
+
+impl<A> BlockingSpout<A> {
+    /// Wrap an async spout.
+    pub fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut A {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<T, A: AsyncSpout<T>> Spout<T> for BlockingSpout<A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        block_on(self.inner.send_async(item))
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        block_on(self.inner.flush_async())
+    }
+}
+
+/// Async counterpart to [`BatchSpout`](crate::BatchSpout).
+///
+/// Buffers items and forwards them as a `Vec<T>` once `threshold` have
+/// accumulated. The full batch is handed to the inner spout on the next
+/// `poll_ready` or `poll_flush`, so a slow inner spout holds back the
+/// producer instead of growing the buffer.
+#[derive(Debug, Clone)]
+pub struct AsyncBatchSpout<T, A> {
+    buffer: Vec<T>,
+    threshold: usize,
+    sink: A,
+}
+
+impl<T, A> AsyncBatchSpout<T, A> {
+    /// Create a new async batch spout.
+    ///
+    /// # Panics
+    /// Panics if `threshold` is 0.
+    pub fn new(threshold: usize, sink: A) -> Self {
+        assert!(
+            threshold > 0,
+            "AsyncBatchSpout threshold must be at least 1"
+        );
+        Self {
+            buffer: Vec::with_capacity(threshold),
+            threshold,
+            sink,
+        }
+    }
+
+    /// Get the batch threshold.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Number of items currently buffered.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &A {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut A {
+        &mut self.sink
+    }
+
+    /// Consume and return the inner spout.
+    ///
+    /// Buffered items are dropped. Flush first to forward them.
+    pub fn into_inner(self) -> A {
+        self.sink
+    }
+}
+
+impl<T, A: AsyncSpout<Vec<T>>> AsyncBatchSpout<T, A> {
+    /// Hand the buffered batch to the inner spout once it is ready.
+    fn poll_send_batch(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), A::Error>> {
+        match self.sink.poll_ready(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        let batch = core::mem::replace(&mut self.buffer, Vec::with_capacity(self.threshold));
+        Poll::Ready(self.sink.send(batch))
+    }
+}
+
+impl<T, A: AsyncSpout<Vec<T>>> AsyncSpout<T> for AsyncBatchSpout<T, A> {
+    type Error = A::Error;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.buffer.len() >= self.threshold {
+            return self.poll_send_batch(cx);
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.buffer.push(item);
+        Ok(())
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if !self.buffer.is_empty() {
+            match self.poll_send_batch(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+        }
+        self.sink.poll_flush(cx)
+    }
+}
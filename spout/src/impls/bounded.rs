@@ -0,0 +1,406 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{Source, Spout};
+
+/// What a [`BoundedSpout`] does when its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Block until the receiver makes room.
+    Block,
+    /// Block for at most the given duration, then fail with
+    /// [`BoundedSendError::Timeout`].
+    BlockTimeout(Duration),
+    /// Drop the incoming item.
+    DropNewest,
+    /// Drop the oldest queued item to make room for the incoming one.
+    DropOldest,
+    /// Fail immediately with [`BoundedSendError::Full`].
+    Error,
+}
+
+/// Error returned by [`BoundedSpout`].
+///
+/// The rejected item is returned so the caller can retry or handle it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedSendError<T> {
+    /// The queue is full and the policy is [`OverflowPolicy::Error`].
+    Full(T),
+    /// The queue stayed full for the whole [`OverflowPolicy::BlockTimeout`].
+    Timeout(T),
+    /// The receiver has been dropped.
+    Disconnected(T),
+}
+
+impl<T> BoundedSendError<T> {
+    /// Extract the item that failed to send.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(item) | Self::Timeout(item) | Self::Disconnected(item) => item,
+        }
+    }
+}
+
+impl<T> core::fmt::Display for BoundedSendError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("bounded channel is full"),
+            Self::Timeout(_) => f.write_str("timed out waiting for bounded channel capacity"),
+            Self::Disconnected(_) => f.write_str("bounded channel receiver disconnected"),
+        }
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+    dropped_newest: AtomicU64,
+    dropped_oldest: AtomicU64,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // Queue state stays consistent across a panic, so poisoning is ignored.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn dropped(&self) -> u64 {
+        self.dropped_newest.load(Ordering::Relaxed) + self.dropped_oldest.load(Ordering::Relaxed)
+    }
+
+    /// Enqueue one item under the lock, applying the overflow policy.
+    fn push<'a>(
+        &'a self,
+        mut state: MutexGuard<'a, State<T>>,
+        item: T,
+    ) -> (MutexGuard<'a, State<T>>, Result<(), BoundedSendError<T>>) {
+        let mut deadline = None;
+        loop {
+            if !state.receiver {
+                return (state, Err(BoundedSendError::Disconnected(item)));
+            }
+            if state.queue.len() < self.capacity {
+                state.queue.push_back(item);
+                self.not_empty.notify_one();
+                return (state, Ok(()));
+            }
+            match self.policy {
+                OverflowPolicy::Block => {
+                    state = self
+                        .not_full
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                OverflowPolicy::BlockTimeout(timeout) => {
+                    let deadline = *deadline.get_or_insert_with(|| Instant::now() + timeout);
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return (state, Err(BoundedSendError::Timeout(item)));
+                    }
+                    state = self
+                        .not_full
+                        .wait_timeout(state, remaining)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+                OverflowPolicy::DropNewest => {
+                    self.dropped_newest.fetch_add(1, Ordering::Relaxed);
+                    return (state, Ok(()));
+                }
+                OverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    state.queue.push_back(item);
+                    self.dropped_oldest.fetch_add(1, Ordering::Relaxed);
+                    self.not_empty.notify_one();
+                    return (state, Ok(()));
+                }
+                OverflowPolicy::Error => {
+                    return (state, Err(BoundedSendError::Full(item)));
+                }
+            }
+        }
+    }
+}
+
+/// Create a bounded in-process queue with an explicit overflow policy.
+///
+/// Returns the sending half as a [`Spout`] and the receiving half. The
+/// sender can be cloned for multiple producers, so it works as the sink of
+/// an `MpscRing`. Rings that require an infallible sink can wrap it in
+/// [`ErrorCaptureSpout`](crate::ErrorCaptureSpout).
+///
+/// # Panics
+/// Panics if `capacity` is 0.
+///
+/// # Example
+///
+/// ```
+/// use spout::{OverflowPolicy, Spout, bounded};
+///
+/// let (mut tx, rx) = bounded(2, OverflowPolicy::DropOldest);
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+/// tx.send(3).unwrap(); // Drops 1
+///
+/// assert_eq!(rx.try_recv(), Ok(2));
+/// assert_eq!(rx.try_recv(), Ok(3));
+/// assert_eq!(rx.dropped_oldest(), 1);
+/// ```
+pub fn bounded<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+) -> (BoundedSpout<T>, BoundedReceiver<T>) {
+    assert!(capacity > 0, "bounded channel capacity must be at least 1");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            senders: 1,
+            receiver: true,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity,
+        policy,
+        dropped_newest: AtomicU64::new(0),
+        dropped_oldest: AtomicU64::new(0),
+    });
+    (
+        BoundedSpout {
+            shared: Arc::clone(&shared),
+        },
+        BoundedReceiver { shared },
+    )
+}
+
+/// Sending half of a [`bounded`] queue.
+pub struct BoundedSpout<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> BoundedSpout<T> {
+    /// The overflow policy.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.shared.policy
+    }
+
+    /// Maximum number of queued items.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// True if no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Incoming items dropped by [`OverflowPolicy::DropNewest`].
+    pub fn dropped_newest(&self) -> u64 {
+        self.shared.dropped_newest.load(Ordering::Relaxed)
+    }
+
+    /// Queued items dropped by [`OverflowPolicy::DropOldest`].
+    pub fn dropped_oldest(&self) -> u64 {
+        self.shared.dropped_oldest.load(Ordering::Relaxed)
+    }
+
+    /// Total items dropped by either drop policy.
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped()
+    }
+}
+
+impl<T> Clone for BoundedSpout<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for BoundedSpout<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for BoundedSpout<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoundedSpout")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Spout<T> for BoundedSpout<T> {
+    type Error = BoundedSendError<T>;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let state = self.shared.lock();
+        self.shared.push(state, item).1
+    }
+
+    /// Enqueue items under a single lock acquisition (released while blocking).
+    fn send_all(&mut self, items: impl Iterator<Item = T>) -> Result<(), Self::Error> {
+        let mut state = self.shared.lock();
+        for item in items {
+            let (next, result) = self.shared.push(state, item);
+            result?;
+            state = next;
+        }
+        Ok(())
+    }
+}
+
+/// Receiving half of a [`bounded`] queue.
+pub struct BoundedReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> BoundedReceiver<T> {
+    /// Block until an item is available.
+    ///
+    /// Returns `Err(RecvError)` once the queue is empty and every sender
+    /// has been dropped.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(item) = state.queue.pop_front() {
+                self.shared.not_full.notify_one();
+                return Ok(item);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self
+                .shared
+                .not_empty
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Take an item if one is queued, without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.queue.pop_front() {
+            Some(item) => {
+                self.shared.not_full.notify_one();
+                Ok(item)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Block for at most `timeout` waiting for an item.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(item) = state.queue.pop_front() {
+                self.shared.not_full.notify_one();
+                return Ok(item);
+            }
+            if state.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = self
+                .shared
+                .not_empty
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Maximum number of queued items.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// True if no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Incoming items dropped by [`OverflowPolicy::DropNewest`].
+    pub fn dropped_newest(&self) -> u64 {
+        self.shared.dropped_newest.load(Ordering::Relaxed)
+    }
+
+    /// Queued items dropped by [`OverflowPolicy::DropOldest`].
+    pub fn dropped_oldest(&self) -> u64 {
+        self.shared.dropped_oldest.load(Ordering::Relaxed)
+    }
+
+    /// Total items dropped by either drop policy.
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped()
+    }
+}
+
+/// Exhausted once the queue is empty and every sender is dropped.
+impl<T> Source<T> for BoundedReceiver<T> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(BoundedReceiver::recv(self).ok())
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(BoundedReceiver::try_recv(self).ok())
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receiver = false;
+        self.shared.not_full.notify_all();
+    }
+}
+
+impl<T> core::fmt::Debug for BoundedReceiver<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoundedReceiver")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,8 +1,9 @@
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
 use bytecast::{ByteCursor, ByteReader, BytesError, FromBytes, ToBytes};
 
-use crate::Spout;
+use crate::{Source, Spout};
 
 /// Prepends framing headers (producer_id, byte length, payload) before forwarding.
 ///
@@ -60,8 +61,34 @@ impl<S> FramedSpout<S> {
     }
 }
 
-impl<T: ToBytes, S: Spout<Vec<u8>, Error = core::convert::Infallible>> Spout<T> for FramedSpout<S> {
-    type Error = BytesError;
+/// Error from a framing spout.
+///
+/// Wraps either a serialization error or the inner spout's error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramedSpoutError<E> {
+    /// The item could not be serialized into a frame.
+    Bytes(BytesError),
+    /// The inner spout rejected the frame.
+    Spout(E),
+}
+
+impl<E> From<BytesError> for FramedSpoutError<E> {
+    fn from(e: BytesError) -> Self {
+        Self::Bytes(e)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for FramedSpoutError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bytes(e) => write!(f, "{e}"),
+            Self::Spout(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<T: ToBytes, S: Spout<Vec<u8>>> Spout<T> for FramedSpout<S> {
+    type Error = FramedSpoutError<S::Error>;
 
     #[inline]
     fn send(&mut self, item: T) -> Result<(), Self::Error> {
@@ -90,15 +117,14 @@ impl<T: ToBytes, S: Spout<Vec<u8>, Error = core::convert::Infallible>> Spout<T>
         // Truncate to actual frame size and reuse buffer
         let total = FRAME_HEADER_SIZE + payload_written;
         self.buf.truncate(total);
-        // Inner spout is infallible
-        let _ = self.inner.send(self.buf.split_off(0));
-        Ok(())
+        self.inner
+            .send(self.buf.split_off(0))
+            .map_err(FramedSpoutError::Spout)
     }
 
     #[inline]
     fn flush(&mut self) -> Result<(), Self::Error> {
-        let _ = self.inner.flush();
-        Ok(())
+        self.inner.flush().map_err(FramedSpoutError::Spout)
     }
 }
 
@@ -121,6 +147,616 @@ pub fn decode_frame<T: FromBytes>(frame: &[u8]) -> Result<(usize, T), BytesError
     Ok((producer_id, item))
 }
 
+// --- Versioned (v2) frames ---
+
+/// Leading byte of every v2 frame.
+pub const FRAME_MAGIC: u8 = 0xF5;
+
+/// Wire format version written by [`FramedSpoutV2`].
+pub const FRAME_VERSION: u8 = 2;
+
+/// Flag bit: an 8-byte timestamp follows the sequence number.
+const FLAG_TIMESTAMP: u8 = 0b0000_0001;
+
+/// Fixed v2 header: magic + version + flags + producer_id (u64) +
+/// sequence (u64) + payload length (u32).
+const FRAME_V2_HEADER_SIZE: usize = 3 + 8 + 8 + 4;
+
+/// Optional timestamp field.
+const FRAME_V2_TIMESTAMP_SIZE: usize = 8;
+
+/// CRC32 trailer.
+const FRAME_V2_TRAILER_SIZE: usize = 4;
+
+/// Versioned framing with sequence numbers and a CRC32 trailer.
+///
+/// Opt-in successor to [`FramedSpout`]. Each item is serialized via
+/// `ToBytes` and wrapped in a frame:
+///
+/// `[magic: u8] [version: u8] [flags: u8] [producer_id: u64] [sequence: u64]
+/// [timestamp: u64, if flagged] [payload_len: u32] [payload bytes] [crc32: u32]`
+///
+/// The sequence number starts at 0 and increases by one per frame, so a
+/// [`FrameDecoder`] can detect gaps and duplicates per producer. The CRC32
+/// covers every byte before the trailer.
+///
+/// Timestamps are written only when a [`Clock`](crate::Clock) is attached
+/// via [`with_clock`](Self::with_clock).
+pub struct FramedSpoutV2<S, C = fn() -> u64> {
+    inner: S,
+    producer_id: usize,
+    next_sequence: u64,
+    clock: Option<C>,
+    /// Reusable buffer to avoid per-send allocation.
+    buf: Vec<u8>,
+}
+
+impl<S> FramedSpoutV2<S> {
+    /// Create a new v2 framed spout without timestamps.
+    pub fn new(producer_id: usize, inner: S) -> Self {
+        Self {
+            inner,
+            producer_id,
+            next_sequence: 0,
+            clock: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<S, C> FramedSpoutV2<S, C> {
+    /// Stamp every frame with a timestamp read from `clock`.
+    pub fn with_clock<C2: crate::Clock>(self, clock: C2) -> FramedSpoutV2<S, C2> {
+        FramedSpoutV2 {
+            inner: self.inner,
+            producer_id: self.producer_id,
+            next_sequence: self.next_sequence,
+            clock: Some(clock),
+            buf: self.buf,
+        }
+    }
+
+    /// Get the producer ID.
+    pub fn producer_id(&self) -> usize {
+        self.producer_id
+    }
+
+    /// Get the sequence number the next frame will carry.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S, C> Spout<T> for FramedSpoutV2<S, C>
+where
+    T: ToBytes,
+    S: Spout<Vec<u8>>,
+    C: crate::Clock,
+{
+    type Error = FramedSpoutError<S::Error>;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let timestamp = self.clock.as_ref().map(crate::Clock::now);
+        let header_size = FRAME_V2_HEADER_SIZE
+            + if timestamp.is_some() {
+                FRAME_V2_TIMESTAMP_SIZE
+            } else {
+                0
+            };
+        let payload_size = item.byte_len().or(T::MAX_SIZE).unwrap_or(256);
+
+        self.buf.clear();
+        self.buf.resize(header_size + payload_size, 0);
+
+        // Write payload first to learn actual size
+        let payload_written = match item.to_bytes(&mut self.buf[header_size..]) {
+            Ok(n) => n,
+            Err(BytesError::BufferTooSmall { needed, .. }) => {
+                self.buf.resize(header_size + needed, 0);
+                item.to_bytes(&mut self.buf[header_size..])?
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let payload_len =
+            u32::try_from(payload_written).map_err(|_| BytesError::BufferTooSmall {
+                needed: payload_written,
+                available: u32::MAX as usize,
+            })?;
+
+        let flags = if timestamp.is_some() {
+            FLAG_TIMESTAMP
+        } else {
+            0
+        };
+        let mut cursor = ByteCursor::new(&mut self.buf[..header_size]);
+        cursor.write(&FRAME_MAGIC)?;
+        cursor.write(&FRAME_VERSION)?;
+        cursor.write(&flags)?;
+        cursor.write(&self.producer_id)?;
+        cursor.write(&self.next_sequence)?;
+        if let Some(ts) = timestamp {
+            cursor.write(&ts)?;
+        }
+        cursor.write(&payload_len)?;
+
+        let body = header_size + payload_written;
+        self.buf.truncate(body);
+        let crc = super::crc32::crc32(&self.buf);
+        self.buf.extend_from_slice(&[0; FRAME_V2_TRAILER_SIZE]);
+        crc.to_bytes(&mut self.buf[body..])?;
+
+        // The sequence number is consumed even if the inner spout fails,
+        // so the decoder sees the lost frame as a gap.
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.inner
+            .send(self.buf.split_off(0))
+            .map_err(FramedSpoutError::Spout)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().map_err(FramedSpoutError::Spout)
+    }
+}
+
+/// Error decoding a v2 frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The frame is truncated or its payload failed to deserialize.
+    Bytes(BytesError),
+    /// The first byte is not [`FRAME_MAGIC`].
+    BadMagic(u8),
+    /// The version byte is not one this decoder understands.
+    UnsupportedVersion(u8),
+    /// The flags byte has bits set that this decoder does not know.
+    UnknownFlags(u8),
+    /// The CRC32 trailer does not match the frame contents.
+    ChecksumMismatch {
+        /// Checksum stored in the trailer.
+        expected: u32,
+        /// Checksum computed over the received bytes.
+        actual: u32,
+    },
+    /// The header declares a frame longer than the decoder accepts.
+    TooLarge {
+        /// Frame length declared by the header.
+        len: usize,
+        /// Longest frame the decoder accepts.
+        max: usize,
+    },
+}
+
+impl From<BytesError> for FrameError {
+    fn from(e: BytesError) -> Self {
+        Self::Bytes(e)
+    }
+}
+
+impl core::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bytes(e) => write!(f, "{e}"),
+            Self::BadMagic(b) => write!(f, "bad frame magic: {b:#04x}"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported frame version: {v}"),
+            Self::UnknownFlags(flags) => write!(f, "unknown frame flags: {flags:#010b}"),
+            Self::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "frame checksum mismatch: expected {expected:#010x}, got {actual:#010x}"
+                )
+            }
+            Self::TooLarge { len, max } => {
+                write!(f, "frame of {len} bytes exceeds the {max} byte limit")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FrameError {}
+
+/// A decoded v2 frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFrame<T> {
+    /// Producer that emitted the frame.
+    pub producer_id: usize,
+    /// Per-producer sequence number.
+    pub sequence: u64,
+    /// Timestamp, if the producer had a clock attached.
+    pub timestamp: Option<u64>,
+    /// The decoded payload.
+    pub item: T,
+}
+
+/// Decode a frame produced by [`FramedSpoutV2`].
+///
+/// Validates magic, version, flags, declared payload length and the CRC32
+/// trailer. Does not track ordering; use [`FrameDecoder`] for that.
+pub fn decode_frame_v2<T: FromBytes>(frame: &[u8]) -> Result<DecodedFrame<T>, FrameError> {
+    let min = FRAME_V2_HEADER_SIZE + FRAME_V2_TRAILER_SIZE;
+    if frame.len() < min {
+        return Err(FrameError::Bytes(BytesError::UnexpectedEof {
+            needed: min,
+            available: frame.len(),
+        }));
+    }
+
+    let mut reader = ByteReader::new(frame);
+    let magic: u8 = reader.read()?;
+    if magic != FRAME_MAGIC {
+        return Err(FrameError::BadMagic(magic));
+    }
+    let version: u8 = reader.read()?;
+    if version != FRAME_VERSION {
+        return Err(FrameError::UnsupportedVersion(version));
+    }
+    let flags: u8 = reader.read()?;
+    if flags & !FLAG_TIMESTAMP != 0 {
+        return Err(FrameError::UnknownFlags(flags));
+    }
+    let producer_id: usize = reader.read()?;
+    let sequence: u64 = reader.read()?;
+    let timestamp = if flags & FLAG_TIMESTAMP != 0 {
+        Some(reader.read::<u64>()?)
+    } else {
+        None
+    };
+    let payload_len: u32 = reader.read()?;
+
+    let header_size = reader.position();
+    let expected_len = header_size + payload_len as usize + FRAME_V2_TRAILER_SIZE;
+    if frame.len() != expected_len {
+        return Err(FrameError::Bytes(BytesError::UnexpectedEof {
+            needed: expected_len,
+            available: frame.len(),
+        }));
+    }
+
+    let body = expected_len - FRAME_V2_TRAILER_SIZE;
+    let (expected, _) = u32::from_bytes(&frame[body..])?;
+    let actual = super::crc32::crc32(&frame[..body]);
+    if expected != actual {
+        return Err(FrameError::ChecksumMismatch { expected, actual });
+    }
+
+    let (item, consumed) = T::from_bytes(&frame[header_size..body])?;
+    if consumed != payload_len as usize {
+        return Err(FrameError::Bytes(BytesError::InvalidData {
+            message: "payload length mismatch",
+        }));
+    }
+
+    Ok(DecodedFrame {
+        producer_id,
+        sequence,
+        timestamp,
+        item,
+    })
+}
+
+/// Ordering of a decoded frame relative to earlier frames from the same producer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// First frame seen from this producer.
+    First,
+    /// Sequence number is exactly one past the previous frame.
+    InOrder,
+    /// One or more sequence numbers were skipped.
+    Gap {
+        /// Sequence number that was expected.
+        expected: u64,
+        /// Number of frames missing between the previous and this one.
+        missing: u64,
+    },
+    /// Sequence number is at or below one already seen.
+    Duplicate {
+        /// Highest sequence number seen so far from this producer.
+        last: u64,
+    },
+}
+
+/// Stateful decoder for v2 frames that tracks per-producer ordering.
+///
+/// Each decoded frame is classified as [`SequenceCheck::InOrder`],
+/// [`Gap`](SequenceCheck::Gap) or [`Duplicate`](SequenceCheck::Duplicate).
+/// Totals are kept for reporting.
+#[derive(Debug, Clone, Default)]
+pub struct FrameDecoder {
+    last: BTreeMap<usize, u64>,
+    gaps: u64,
+    missing: u64,
+    duplicates: u64,
+}
+
+impl FrameDecoder {
+    /// Create a new decoder with no producer history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode a frame and classify its sequence number.
+    ///
+    /// Frames that fail validation do not affect ordering state.
+    pub fn decode<T: FromBytes>(
+        &mut self,
+        frame: &[u8],
+    ) -> Result<(DecodedFrame<T>, SequenceCheck), FrameError> {
+        let decoded = decode_frame_v2::<T>(frame)?;
+        let check = self.observe(decoded.producer_id, decoded.sequence);
+        Ok((decoded, check))
+    }
+
+    /// Record a sequence number for `producer_id` and classify it.
+    pub fn observe(&mut self, producer_id: usize, sequence: u64) -> SequenceCheck {
+        let Some(last) = self.last.get_mut(&producer_id) else {
+            self.last.insert(producer_id, sequence);
+            return SequenceCheck::First;
+        };
+
+        let expected = last.wrapping_add(1);
+        if sequence == expected {
+            *last = sequence;
+            SequenceCheck::InOrder
+        } else if sequence > expected {
+            let missing = sequence - expected;
+            *last = sequence;
+            self.gaps += 1;
+            self.missing += missing;
+            SequenceCheck::Gap { expected, missing }
+        } else {
+            self.duplicates += 1;
+            SequenceCheck::Duplicate { last: *last }
+        }
+    }
+
+    /// Number of gaps detected across all producers.
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    /// Total number of frames skipped by gaps.
+    pub fn missing(&self) -> u64 {
+        self.missing
+    }
+
+    /// Number of duplicate or stale frames detected.
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// Highest sequence number seen from `producer_id`.
+    pub fn last_sequence(&self, producer_id: usize) -> Option<u64> {
+        self.last.get(&producer_id).copied()
+    }
+
+    /// Forget all producer history and reset counters.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Error from a [`DecodeSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeSourceError<E> {
+    /// The underlying frame source returned an error.
+    Source(E),
+    /// A frame failed to decode.
+    Frame(FrameError),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for DecodeSourceError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "{e}"),
+            Self::Frame(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Decodes v2 frames pulled from an inner [`Source`] of byte frames.
+///
+/// Each frame is run through a [`FrameDecoder`], so ordering gaps and
+/// duplicates are counted and can be read through
+/// [`decoder`](Self::decoder). A frame that fails to decode is returned as
+/// an error; the next call continues with the following frame.
+pub struct DecodeSource<S, T> {
+    inner: S,
+    decoder: FrameDecoder,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<S, T> DecodeSource<S, T> {
+    /// Create a new decoding source.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            decoder: FrameDecoder::new(),
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Get the frame decoder and its ordering statistics.
+    pub fn decoder(&self) -> &FrameDecoder {
+        &self.decoder
+    }
+
+    /// Get a reference to the inner source.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner source.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn decode(
+        &mut self,
+        frame: Option<Vec<u8>>,
+    ) -> Result<Option<DecodedFrame<T>>, DecodeSourceError<S::Error>>
+    where
+        S: Source<Vec<u8>>,
+        T: FromBytes,
+    {
+        let Some(frame) = frame else {
+            return Ok(None);
+        };
+        let (decoded, _) = self
+            .decoder
+            .decode(&frame)
+            .map_err(DecodeSourceError::Frame)?;
+        Ok(Some(decoded))
+    }
+}
+
+impl<S: Source<Vec<u8>>, T: FromBytes> Source<DecodedFrame<T>> for DecodeSource<S, T> {
+    type Error = DecodeSourceError<S::Error>;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<DecodedFrame<T>>, Self::Error> {
+        let frame = self.inner.recv().map_err(DecodeSourceError::Source)?;
+        self.decode(frame)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<DecodedFrame<T>>, Self::Error> {
+        let frame = self.inner.try_recv().map_err(DecodeSourceError::Source)?;
+        self.decode(frame)
+    }
+}
+
+/// Length of the v2 frame at the front of `buf`.
+///
+/// Returns `Ok(None)` while the header or the rest of the frame has not
+/// arrived yet. Only the header is validated; the checksum and payload are
+/// checked when the frame is decoded.
+pub fn frame_v2_len(buf: &[u8]) -> Result<Option<usize>, FrameError> {
+    Ok(frame_v2_declared_len(buf)?.filter(|&len| buf.len() >= len))
+}
+
+/// Length the v2 header at the front of `buf` declares for its frame, or
+/// `Ok(None)` while the header has not arrived yet.
+fn frame_v2_declared_len(buf: &[u8]) -> Result<Option<usize>, FrameError> {
+    if buf.len() < 3 {
+        return Ok(None);
+    }
+    if buf[0] != FRAME_MAGIC {
+        return Err(FrameError::BadMagic(buf[0]));
+    }
+    if buf[1] != FRAME_VERSION {
+        return Err(FrameError::UnsupportedVersion(buf[1]));
+    }
+    let flags = buf[2];
+    if flags & !FLAG_TIMESTAMP != 0 {
+        return Err(FrameError::UnknownFlags(flags));
+    }
+    let header_size = if flags & FLAG_TIMESTAMP != 0 {
+        FRAME_V2_HEADER_SIZE + FRAME_V2_TIMESTAMP_SIZE
+    } else {
+        FRAME_V2_HEADER_SIZE
+    };
+    if buf.len() < header_size {
+        return Ok(None);
+    }
+    let (payload_len, _) = u32::from_bytes(&buf[header_size - 4..header_size])?;
+    Ok(Some(
+        (payload_len as usize).saturating_add(header_size + FRAME_V2_TRAILER_SIZE),
+    ))
+}
+
+/// Default limit on the length of one frame in a [`FrameStreamDecoder`].
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Splits a byte stream of back-to-back v2 frames into decoded frames.
+///
+/// Frames are run through a [`FrameDecoder`], so ordering statistics are
+/// available through [`decoder`](Self::decoder). Use it with a
+/// `ListenerSource` to receive frames written by a
+/// [`FramedSpoutV2`] over a socket.
+///
+/// A frame whose header declares more than
+/// [`max_frame_len`](Self::with_max_frame_len) bytes is rejected with
+/// [`FrameError::TooLarge`] as soon as the header arrives, so a bad or
+/// hostile peer cannot make the reader buffer without bound.
+pub struct FrameStreamDecoder<T> {
+    decoder: FrameDecoder,
+    max_frame_len: usize,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> FrameStreamDecoder<T> {
+    /// Create a new stream decoder with no producer history, accepting
+    /// frames of up to [`DEFAULT_MAX_FRAME_LEN`] bytes.
+    pub fn new() -> Self {
+        Self {
+            decoder: FrameDecoder::new(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Reject frames longer than `bytes`, header and trailer included.
+    #[must_use]
+    pub fn with_max_frame_len(mut self, bytes: usize) -> Self {
+        self.max_frame_len = bytes;
+        self
+    }
+
+    /// Get the frame decoder and its ordering statistics.
+    pub fn decoder(&self) -> &FrameDecoder {
+        &self.decoder
+    }
+}
+
+impl<T> Default for FrameStreamDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FromBytes> crate::StreamDecoder for FrameStreamDecoder<T> {
+    type Item = DecodedFrame<T>;
+    type Error = FrameError;
+
+    fn decode(&mut self, buf: &[u8]) -> Result<Option<(usize, DecodedFrame<T>)>, FrameError> {
+        let Some(len) = frame_v2_declared_len(buf)? else {
+            return Ok(None);
+        };
+        if len > self.max_frame_len {
+            return Err(FrameError::TooLarge {
+                len,
+                max: self.max_frame_len,
+            });
+        }
+        if buf.len() < len {
+            return Ok(None);
+        }
+        let (decoded, _) = self.decoder.decode(&buf[..len])?;
+        Ok(Some((len, decoded)))
+    }
+}
+
 // --- BatchSpout snapshot serialization ---
 
 use crate::BatchSpout;
@@ -2,7 +2,7 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::sync::atomic::AtomicUsize;
 
-use crate::{Flush, Spout};
+use crate::{Flush, Source, Spout};
 
 /// Drops all items.
 #[derive(Debug, Clone, Copy, Default)]
@@ -114,6 +114,206 @@ where
     FnFlushSpout::new(send, flush)
 }
 
+/// An item that could not be delivered, paired with the error that rejected it.
+///
+/// `item` is `None` when the failure came from `flush`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter<T, E> {
+    /// The rejected item, if the failure came from `send`.
+    pub item: Option<T>,
+    /// The error returned by the inner spout.
+    pub error: E,
+}
+
+/// Makes a fallible spout infallible by routing failures to a dead-letter spout.
+///
+/// Each item is cloned before being sent to the inner spout. If the inner
+/// spout rejects it, the clone and the error are forwarded to the
+/// dead-letter spout as a [`DeadLetter`] and the failure counter is
+/// incremented. Flush failures are forwarded with `item: None`.
+///
+/// Use this to put files, sockets or other fallible sinks behind
+/// consumers that require `Error = Infallible`, such as `SpillRing`. If
+/// the item does not need to be recovered, or is not `Clone`, use
+/// [`ErrorCaptureSpout`] instead.
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, DeadLetterSpout, Spout};
+///
+/// struct Picky;
+/// impl Spout<i32> for Picky {
+///     type Error = &'static str;
+///     fn send(&mut self, item: i32) -> Result<(), Self::Error> {
+///         if item < 0 { Err("negative") } else { Ok(()) }
+///     }
+/// }
+///
+/// let mut s = DeadLetterSpout::new(Picky, CollectSpout::new());
+/// s.send(1).unwrap();
+/// s.send(-1).unwrap();
+/// assert_eq!(s.failures(), 1);
+/// assert_eq!(s.dead_letters().items()[0].item, Some(-1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadLetterSpout<S, D> {
+    inner: S,
+    dead: D,
+    failures: u64,
+}
+
+impl<S, D> DeadLetterSpout<S, D> {
+    /// Create a new dead-letter adapter.
+    pub fn new(inner: S, dead: D) -> Self {
+        Self {
+            inner,
+            dead,
+            failures: 0,
+        }
+    }
+
+    /// Number of failed sends and flushes routed to the dead-letter spout.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Get a reference to the dead-letter spout.
+    pub fn dead_letters(&self) -> &D {
+        &self.dead
+    }
+
+    /// Get a mutable reference to the dead-letter spout.
+    pub fn dead_letters_mut(&mut self) -> &mut D {
+        &mut self.dead
+    }
+
+    /// Consume and return the inner and dead-letter spouts.
+    pub fn into_parts(self) -> (S, D) {
+        (self.inner, self.dead)
+    }
+}
+
+impl<T, S, D> Spout<T> for DeadLetterSpout<S, D>
+where
+    T: Clone,
+    S: Spout<T>,
+    D: Spout<DeadLetter<T, S::Error>, Error = core::convert::Infallible>,
+{
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        if let Err(error) = self.inner.send(item.clone()) {
+            self.failures += 1;
+            let _ = self.dead.send(DeadLetter {
+                item: Some(item),
+                error,
+            });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        if let Err(error) = self.inner.flush() {
+            self.failures += 1;
+            let _ = self.dead.send(DeadLetter { item: None, error });
+        }
+        let _ = self.dead.flush();
+        Ok(())
+    }
+}
+
+/// Makes a fallible spout infallible by routing its errors to a secondary spout.
+///
+/// Like [`DeadLetterSpout`], but only the error is kept; the rejected item
+/// is dropped. Does not require `T: Clone`.
+#[derive(Debug, Clone)]
+pub struct ErrorCaptureSpout<S, D> {
+    inner: S,
+    errors: D,
+    failures: u64,
+}
+
+impl<S, D> ErrorCaptureSpout<S, D> {
+    /// Create a new error-capturing adapter.
+    pub fn new(inner: S, errors: D) -> Self {
+        Self {
+            inner,
+            errors,
+            failures: 0,
+        }
+    }
+
+    /// Number of failed sends and flushes routed to the error spout.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Get a reference to the error spout.
+    pub fn errors(&self) -> &D {
+        &self.errors
+    }
+
+    /// Get a mutable reference to the error spout.
+    pub fn errors_mut(&mut self) -> &mut D {
+        &mut self.errors
+    }
+
+    /// Consume and return the inner and error spouts.
+    pub fn into_parts(self) -> (S, D) {
+        (self.inner, self.errors)
+    }
+}
+
+impl<T, S, D> Spout<T> for ErrorCaptureSpout<S, D>
+where
+    S: Spout<T>,
+    D: Spout<S::Error, Error = core::convert::Infallible>,
+{
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        if let Err(error) = self.inner.send(item) {
+            self.failures += 1;
+            let _ = self.errors.send(error);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        if let Err(error) = self.inner.flush() {
+            self.failures += 1;
+            let _ = self.errors.send(error);
+        }
+        let _ = self.errors.flush();
+        Ok(())
+    }
+}
+
 pub struct ProducerSpout<S, F> {
     /// The inner spout (created lazily on first send)
     inner: Option<S>,
@@ -367,3 +567,112 @@ where
         self.sink.flush()
     }
 }
+
+/// Adapts any [`Iterator`] into a [`Source`].
+#[derive(Debug, Clone)]
+pub struct IterSource<I>(pub I);
+
+impl<I: Iterator> Source<I::Item> for IterSource<I> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<I::Item>, Self::Error> {
+        Ok(self.0.next())
+    }
+}
+
+/// Error from [`pump`] or [`pump_until`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PumpError<SE, DE> {
+    /// The source returned an error.
+    Source(SE),
+    /// The spout returned an error.
+    Spout(DE),
+}
+
+impl<SE: core::fmt::Display, DE: core::fmt::Display> core::fmt::Display for PumpError<SE, DE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "source: {e}"),
+            Self::Spout(e) => write!(f, "spout: {e}"),
+        }
+    }
+}
+
+/// Move every item from `source` into `spout` until the source is exhausted.
+///
+/// Blocks on [`Source::recv`] for the first item of each batch, then takes
+/// up to `batch - 1` more with [`Source::try_recv`] and forwards them with
+/// a single [`Spout::send_all`]. Flushes the spout at the end. Returns the
+/// number of items moved.
+///
+/// # Panics
+/// Panics if `batch` is 0.
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, IterSource, pump};
+///
+/// let mut sink = CollectSpout::new();
+/// let moved = pump(&mut IterSource(0..10), &mut sink, 4).unwrap();
+/// assert_eq!(moved, 10);
+/// assert_eq!(sink.items(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+pub fn pump<T, Src, Sp>(
+    source: &mut Src,
+    spout: &mut Sp,
+    batch: usize,
+) -> Result<usize, PumpError<Src::Error, Sp::Error>>
+where
+    Src: Source<T>,
+    Sp: Spout<T>,
+{
+    pump_until(source, spout, batch, |_| false)
+}
+
+/// Like [`pump`], but stops after forwarding the first item for which
+/// `done` returns `true`.
+///
+/// The matching item is forwarded. The spout is flushed before returning,
+/// whether the source was exhausted or `done` matched.
+///
+/// # Panics
+/// Panics if `batch` is 0.
+pub fn pump_until<T, Src, Sp>(
+    source: &mut Src,
+    spout: &mut Sp,
+    batch: usize,
+    mut done: impl FnMut(&T) -> bool,
+) -> Result<usize, PumpError<Src::Error, Sp::Error>>
+where
+    Src: Source<T>,
+    Sp: Spout<T>,
+{
+    assert!(batch > 0, "pump batch size must be at least 1");
+    let mut buf = Vec::with_capacity(batch);
+    let mut total = 0;
+    let mut stop = false;
+
+    while !stop {
+        let Some(item) = source.recv().map_err(PumpError::Source)? else {
+            break;
+        };
+        stop = done(&item);
+        buf.push(item);
+
+        while !stop && buf.len() < batch {
+            let Some(item) = source.try_recv().map_err(PumpError::Source)? else {
+                break;
+            };
+            stop = done(&item);
+            buf.push(item);
+        }
+
+        total += buf.len();
+        spout.send_all(buf.drain(..)).map_err(PumpError::Spout)?;
+    }
+
+    spout.flush().map_err(PumpError::Spout)?;
+    Ok(total)
+}
@@ -0,0 +1,416 @@
+//! Deduplication with bounded memory.
+//!
+//! [`Dedup`] drops items whose key was seen recently. What "recently"
+//! means, and whether the answer is exact, is decided by its
+//! [`DedupFilter`]: [`LruFilter`] remembers the last `capacity` distinct
+//! keys exactly, [`BloomFilter`] remembers roughly as many in a fixed bit
+//! budget at a configured false-positive rate.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use crate::Spout;
+
+/// Remembers keys for [`Dedup`].
+///
+/// [`Dedup`] checks a key before forwarding an item and inserts it only
+/// once the item was delivered, so an item the inner spout rejected is not
+/// treated as a duplicate when it is retried.
+pub trait DedupFilter<K> {
+    /// Report whether `key` is remembered, refreshing it if so.
+    fn check(&mut self, key: &K) -> bool;
+
+    /// Remember `key`.
+    fn insert(&mut self, key: K);
+
+    /// Record `key` and report whether it was already remembered.
+    fn check_insert(&mut self, key: K) -> bool {
+        if self.check(&key) {
+            return true;
+        }
+        self.insert(key);
+        false
+    }
+
+    /// Forget every key.
+    fn clear(&mut self);
+}
+
+/// Exact filter remembering the `capacity` most recently seen keys.
+///
+/// Seeing a key again refreshes it. When a new key arrives at capacity,
+/// the least recently seen key is forgotten.
+#[derive(Debug, Clone)]
+pub struct LruFilter<K> {
+    capacity: usize,
+    tick: u64,
+    by_key: BTreeMap<K, u64>,
+    by_tick: BTreeMap<u64, K>,
+}
+
+impl<K> LruFilter<K> {
+    /// Create a filter remembering up to `capacity` keys.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LruFilter capacity must be at least 1");
+        Self {
+            capacity,
+            tick: 0,
+            by_key: BTreeMap::new(),
+            by_tick: BTreeMap::new(),
+        }
+    }
+
+    /// Maximum number of keys remembered.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of keys currently remembered.
+    pub fn len(&self) -> usize {
+        self.by_key.len()
+    }
+
+    /// Returns `true` if no keys are remembered.
+    pub fn is_empty(&self) -> bool {
+        self.by_key.is_empty()
+    }
+}
+
+impl<K: Ord + Clone> DedupFilter<K> for LruFilter<K> {
+    fn check(&mut self, key: &K) -> bool {
+        let Some(last) = self.by_key.get_mut(key) else {
+            return false;
+        };
+        let tick = self.tick;
+        self.tick += 1;
+        let key = self.by_tick.remove(last).expect("LRU index out of sync");
+        *last = tick;
+        self.by_tick.insert(tick, key);
+        true
+    }
+
+    fn insert(&mut self, key: K) {
+        let tick = self.tick;
+        self.tick += 1;
+
+        if let Some(last) = self.by_key.get_mut(&key) {
+            self.by_tick.remove(last).expect("LRU index out of sync");
+            *last = tick;
+            self.by_tick.insert(tick, key);
+            return;
+        }
+
+        if self.by_key.len() == self.capacity {
+            let (_, oldest) = self.by_tick.pop_first().expect("LRU index out of sync");
+            self.by_key.remove(&oldest);
+        }
+        self.by_key.insert(key.clone(), tick);
+        self.by_tick.insert(tick, key);
+    }
+
+    fn clear(&mut self) {
+        self.by_key.clear();
+        self.by_tick.clear();
+    }
+}
+
+/// FNV-1a, finished with a SplitMix64 mix so low bits are well distributed.
+#[derive(Debug, Clone)]
+struct KeyHasher(u64);
+
+impl KeyHasher {
+    fn new() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+}
+
+impl Hasher for KeyHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01B3);
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Approximate filter backed by two rotating Bloom filters.
+///
+/// Keys go into the current generation. After `capacity` new keys it
+/// becomes the previous generation and a fresh one starts, so the filter
+/// remembers between `capacity` and `2 * capacity` recent keys.
+///
+/// Unseen keys are reported as duplicates with roughly the configured
+/// false-positive rate; seen keys within the window are never missed.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    current: Vec<u64>,
+    previous: Vec<u64>,
+    bits: usize,
+    hashes: u32,
+    capacity: usize,
+    inserted: usize,
+}
+
+impl BloomFilter {
+    /// Size a filter for `capacity` keys per generation at `fp_rate`.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0 or `fp_rate` is not in `(0, 1)`.
+    pub fn new(capacity: usize, fp_rate: f64) -> Self {
+        assert!(capacity > 0, "BloomFilter capacity must be at least 1");
+        assert!(
+            fp_rate > 0.0 && fp_rate < 1.0,
+            "BloomFilter false-positive rate must be in (0, 1)"
+        );
+        // Optimal k = log2(1/p), rounded up; m = n * k / ln 2.
+        let mut hashes = 0u32;
+        let mut x = fp_rate;
+        while x < 1.0 {
+            x *= 2.0;
+            hashes += 1;
+        }
+        let bits = ((capacity as f64 * hashes as f64 * core::f64::consts::LOG2_E) as usize).max(64);
+        let words = bits.div_ceil(64);
+        Self {
+            current: vec![0; words],
+            previous: vec![0; words],
+            bits: words * 64,
+            hashes,
+            capacity,
+            inserted: 0,
+        }
+    }
+
+    /// Number of bits per generation.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Number of hash functions.
+    pub fn hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Number of keys per generation.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Estimated false-positive rate of the current generation.
+    pub fn estimated_fp_rate(&self) -> f64 {
+        let set: u32 = self.current.iter().map(|w| w.count_ones()).sum();
+        let fill = set as f64 / self.bits as f64;
+        let mut rate = 1.0;
+        for _ in 0..self.hashes {
+            rate *= fill;
+        }
+        rate
+    }
+
+    fn positions(&self, hash: u64) -> impl Iterator<Item = usize> + use<> {
+        // Kirsch-Mitzenmacher double hashing: h1 + i * h2.
+        let h1 = hash as u32 as u64;
+        let h2 = (hash >> 32) | 1;
+        let bits = self.bits as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bits) as usize)
+    }
+
+    fn contains(words: &[u64], mut positions: impl Iterator<Item = usize>) -> bool {
+        positions.all(|p| words[p / 64] & (1 << (p % 64)) != 0)
+    }
+
+    fn hash<K: Hash>(key: &K) -> u64 {
+        let mut hasher = KeyHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Set `hash` in the current generation, rotating first if it is full.
+    fn add(&mut self, hash: u64) {
+        if self.inserted == self.capacity {
+            core::mem::swap(&mut self.current, &mut self.previous);
+            self.current.fill(0);
+            self.inserted = 0;
+        }
+        for p in self.positions(hash) {
+            self.current[p / 64] |= 1 << (p % 64);
+        }
+        self.inserted += 1;
+    }
+}
+
+impl<K: Hash> DedupFilter<K> for BloomFilter {
+    fn check(&mut self, key: &K) -> bool {
+        let hash = Self::hash(key);
+        if Self::contains(&self.current, self.positions(hash)) {
+            return true;
+        }
+        if Self::contains(&self.previous, self.positions(hash)) {
+            // Carry the key into the current generation.
+            self.add(hash);
+            return true;
+        }
+        false
+    }
+
+    fn insert(&mut self, key: K) {
+        self.add(Self::hash(&key));
+    }
+
+    fn clear(&mut self) {
+        self.current.fill(0);
+        self.previous.fill(0);
+        self.inserted = 0;
+    }
+}
+
+/// Counters reported by [`Dedup`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Items received.
+    pub seen: u64,
+    /// Items the inner spout accepted.
+    pub forwarded: u64,
+    /// Items dropped as duplicates.
+    pub duplicates: u64,
+}
+
+/// Drops items whose key was seen recently.
+///
+/// The key is extracted with `key_fn` and checked against a
+/// [`DedupFilter`]. First sightings are forwarded; repeats are dropped and
+/// counted in [`stats`](Self::stats).
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, Dedup, Spout};
+///
+/// let mut s = Dedup::exact(16, |x: &i32| *x, CollectSpout::new());
+/// s.send_all([1, 2, 1, 3, 2].into_iter()).unwrap();
+///
+/// assert_eq!(s.inner().items(), vec![1, 2, 3]);
+/// assert_eq!(s.stats().duplicates, 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dedup<S, K, KF, M> {
+    filter: M,
+    key_fn: KF,
+    stats: DedupStats,
+    inner: S,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<S, K, KF> Dedup<S, K, KF, LruFilter<K>> {
+    /// Exact deduplication over the last `capacity` distinct keys.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn exact(capacity: usize, key_fn: KF, inner: S) -> Self {
+        Self::with_filter(LruFilter::new(capacity), key_fn, inner)
+    }
+}
+
+impl<S, K, KF> Dedup<S, K, KF, BloomFilter> {
+    /// Approximate deduplication sized for `capacity` keys at `fp_rate`.
+    ///
+    /// A false positive drops an item that was not a duplicate.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0 or `fp_rate` is not in `(0, 1)`.
+    pub fn bloom(capacity: usize, fp_rate: f64, key_fn: KF, inner: S) -> Self {
+        Self::with_filter(BloomFilter::new(capacity, fp_rate), key_fn, inner)
+    }
+}
+
+impl<S, K, KF, M> Dedup<S, K, KF, M> {
+    /// Deduplicate with a custom filter.
+    pub fn with_filter(filter: M, key_fn: KF, inner: S) -> Self {
+        Self {
+            filter,
+            key_fn,
+            stats: DedupStats::default(),
+            inner,
+            _key: PhantomData,
+        }
+    }
+
+    /// Counters since construction or the last [`reset`](Self::reset).
+    pub fn stats(&self) -> DedupStats {
+        self.stats
+    }
+
+    /// Get a reference to the filter.
+    pub fn filter(&self) -> &M {
+        &self.filter
+    }
+
+    /// Forget every key and zero the counters.
+    pub fn reset(&mut self)
+    where
+        M: DedupFilter<K>,
+    {
+        self.filter.clear();
+        self.stats = DedupStats::default();
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S, K, KF, M> Spout<T> for Dedup<S, K, KF, M>
+where
+    S: Spout<T>,
+    KF: FnMut(&T) -> K,
+    M: DedupFilter<K>,
+{
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.stats.seen += 1;
+        let key = (self.key_fn)(&item);
+        if self.filter.check(&key) {
+            self.stats.duplicates += 1;
+            return Ok(());
+        }
+        // Remember the key only once the item is delivered, so a retry of
+        // a failed send is not dropped as a duplicate.
+        self.inner.send(item)?;
+        self.filter.insert(key);
+        self.stats.forwarded += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
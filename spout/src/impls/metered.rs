@@ -0,0 +1,308 @@
+//! Metrics instrumentation for any spout.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Clock, Spout};
+
+/// Number of latency histogram buckets.
+///
+/// Bucket `i` counts latencies in `[2^i, 2^(i+1))` ticks, except bucket 0
+/// which also holds 0 and the last bucket which holds everything above.
+pub const LATENCY_BUCKETS: usize = 32;
+
+/// Measures the byte volume of an item for [`Metered`].
+pub trait ByteCount<T> {
+    /// Byte size of `item`, or `None` if unknown.
+    fn byte_count(&self, item: &T) -> Option<u64>;
+}
+
+/// Does not count bytes.
+impl<T> ByteCount<T> for () {
+    #[inline]
+    fn byte_count(&self, _item: &T) -> Option<u64> {
+        None
+    }
+}
+
+/// Counts bytes via [`ToBytes::byte_len`](bytecast::ToBytes::byte_len).
+#[cfg(feature = "bytecast")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByteLen;
+
+#[cfg(feature = "bytecast")]
+impl<T: bytecast::ToBytes> ByteCount<T> for ByteLen {
+    #[inline]
+    fn byte_count(&self, item: &T) -> Option<u64> {
+        item.byte_len().map(|n| n as u64)
+    }
+}
+
+/// Shared atomic counters recorded by [`Metered`].
+///
+/// Several `Metered` wrappers can record into one `SpoutMetrics`, and it
+/// can be read from any thread while they run.
+#[derive(Debug)]
+pub struct SpoutMetrics {
+    sends: AtomicU64,
+    send_errors: AtomicU64,
+    flushes: AtomicU64,
+    flush_errors: AtomicU64,
+    bytes: AtomicU64,
+    latency: [AtomicU64; LATENCY_BUCKETS],
+}
+
+impl SpoutMetrics {
+    /// Create zeroed metrics.
+    pub const fn new() -> Self {
+        Self {
+            sends: AtomicU64::new(0),
+            send_errors: AtomicU64::new(0),
+            flushes: AtomicU64::new(0),
+            flush_errors: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            latency: [const { AtomicU64::new(0) }; LATENCY_BUCKETS],
+        }
+    }
+
+    /// Read all counters.
+    ///
+    /// Counters are read individually, so a snapshot taken while sends are
+    /// in flight may be off by the sends in progress.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            sends: self.sends.load(Ordering::Relaxed),
+            send_errors: self.send_errors.load(Ordering::Relaxed),
+            flushes: self.flushes.load(Ordering::Relaxed),
+            flush_errors: self.flush_errors.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            latency: core::array::from_fn(|i| self.latency[i].load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Zero all counters.
+    pub fn reset(&self) {
+        self.sends.store(0, Ordering::Relaxed);
+        self.send_errors.store(0, Ordering::Relaxed);
+        self.flushes.store(0, Ordering::Relaxed);
+        self.flush_errors.store(0, Ordering::Relaxed);
+        self.bytes.store(0, Ordering::Relaxed);
+        for bucket in &self.latency {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn record_latency(&self, ticks: u64) {
+        let bucket = (u64::BITS - ticks.leading_zeros()).saturating_sub(1) as usize;
+        self.latency[bucket.min(LATENCY_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for SpoutMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Point-in-time copy of [`SpoutMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Calls to `send`, successful or not.
+    pub sends: u64,
+    /// Calls to `send` that returned an error.
+    pub send_errors: u64,
+    /// Calls to `flush`, successful or not.
+    pub flushes: u64,
+    /// Calls to `flush` that returned an error.
+    pub flush_errors: u64,
+    /// Total byte volume of sent items, if a [`ByteCount`] is configured.
+    pub bytes: u64,
+    /// Latency histogram of inner `send` calls, in clock ticks.
+    pub latency: [u64; LATENCY_BUCKETS],
+}
+
+impl MetricsSnapshot {
+    /// Tick range `[low, high)` covered by latency bucket `i`.
+    ///
+    /// The last bucket is open-ended and reports `u64::MAX` as its bound.
+    pub fn bucket_bounds(i: usize) -> (u64, u64) {
+        let low = if i == 0 { 0 } else { 1u64 << i };
+        let high = if i + 1 >= LATENCY_BUCKETS {
+            u64::MAX
+        } else {
+            1u64 << (i + 1)
+        };
+        (low, high)
+    }
+
+    /// Number of latency samples recorded.
+    pub fn latency_samples(&self) -> u64 {
+        self.latency.iter().sum()
+    }
+
+    /// Upper bound of the bucket holding quantile `q` (0.0 to 1.0).
+    ///
+    /// Returns `None` if no latency samples were recorded.
+    pub fn latency_quantile(&self, q: f64) -> Option<u64> {
+        let total = self.latency_samples();
+        if total == 0 {
+            return None;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * total as f64) as u64).clamp(1, total);
+        let mut seen = 0;
+        for (i, &count) in self.latency.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(Self::bucket_bounds(i).1);
+            }
+        }
+        None
+    }
+}
+
+/// Records sends, errors, flushes, byte volume and send latency.
+///
+/// Counters live in a shared [`SpoutMetrics`], reachable through
+/// [`metrics`](Self::metrics), so another thread can take snapshots while
+/// this spout runs.
+///
+/// Latency is measured only when a [`Clock`] is attached via
+/// [`with_clock`](Self::with_clock); byte volume only when a [`ByteCount`]
+/// is attached via [`with_byte_count`](Self::with_byte_count).
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, Metered, Spout};
+///
+/// let mut s = Metered::new(CollectSpout::new());
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+///
+/// let snap = s.metrics().snapshot();
+/// assert_eq!(snap.sends, 2);
+/// assert_eq!(snap.send_errors, 0);
+/// ```
+#[derive(Debug)]
+pub struct Metered<S, C = fn() -> u64, B = ()> {
+    inner: S,
+    metrics: Arc<SpoutMetrics>,
+    clock: Option<C>,
+    bytes: B,
+}
+
+impl<S> Metered<S> {
+    /// Wrap `inner` with fresh metrics.
+    pub fn new(inner: S) -> Self {
+        Self::with_metrics(inner, Arc::new(SpoutMetrics::new()))
+    }
+
+    /// Wrap `inner`, recording into existing shared metrics.
+    pub fn with_metrics(inner: S, metrics: Arc<SpoutMetrics>) -> Self {
+        Self {
+            inner,
+            metrics,
+            clock: None,
+            bytes: (),
+        }
+    }
+}
+
+impl<S, C, B> Metered<S, C, B> {
+    /// Measure inner `send` latency with `clock`.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> Metered<S, C2, B> {
+        Metered {
+            inner: self.inner,
+            metrics: self.metrics,
+            clock: Some(clock),
+            bytes: self.bytes,
+        }
+    }
+
+    /// Record byte volume with `bytes`.
+    pub fn with_byte_count<B2>(self, bytes: B2) -> Metered<S, C, B2> {
+        Metered {
+            inner: self.inner,
+            metrics: self.metrics,
+            clock: self.clock,
+            bytes,
+        }
+    }
+
+    /// Get the shared metrics.
+    pub fn metrics(&self) -> &Arc<SpoutMetrics> {
+        &self.metrics
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Clone, C: Clone, B: Clone> Clone for Metered<S, C, B> {
+    /// Clones share the same metrics.
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            metrics: Arc::clone(&self.metrics),
+            clock: self.clock.clone(),
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<T, S, C, B> Spout<T> for Metered<S, C, B>
+where
+    S: Spout<T>,
+    C: Clock,
+    B: ByteCount<T>,
+{
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let bytes = self.bytes.byte_count(&item);
+        let start = self.clock.as_ref().map(Clock::now);
+
+        let result = self.inner.send(item);
+
+        if let (Some(clock), Some(start)) = (&self.clock, start) {
+            self.metrics
+                .record_latency(clock.now().saturating_sub(start));
+        }
+        self.metrics.sends.fetch_add(1, Ordering::Relaxed);
+        match &result {
+            Ok(()) => {
+                if let Some(n) = bytes {
+                    self.metrics.bytes.fetch_add(n, Ordering::Relaxed);
+                }
+            }
+            Err(_) => {
+                self.metrics.send_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        let result = self.inner.flush();
+        self.metrics.flushes.fetch_add(1, Ordering::Relaxed);
+        if result.is_err() {
+            self.metrics.flush_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
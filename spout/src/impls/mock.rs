@@ -0,0 +1,241 @@
+//! Scriptable spout for testing error paths.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use std::vec::Vec;
+
+use crate::Spout;
+
+/// Error injected by [`MockSpout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockError {
+    /// An injected send failure, with the 1-based index of the item.
+    Send(u64),
+    /// An injected flush failure, with the 1-based index of the flush.
+    Flush(u64),
+}
+
+impl core::fmt::Display for MockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Send(n) => write!(f, "mock spout injected failure on send {n}"),
+            Self::Flush(n) => write!(f, "mock spout injected failure on flush {n}"),
+        }
+    }
+}
+
+/// One call recorded by [`MockSpout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockCall<T> {
+    /// A `send`, with the item and whether it was accepted.
+    Send { item: T, ok: bool },
+    /// A `send_all`, with the items consumed and whether all were accepted.
+    ///
+    /// On failure, `items` ends with the rejected item; later items were not
+    /// consumed.
+    SendAll { items: Vec<T>, ok: bool },
+    /// A `flush`, and whether it succeeded.
+    Flush { ok: bool },
+}
+
+/// Spout that fails, stalls or panics on demand and records every call.
+///
+/// Faults are configured with builder methods and apply to individual
+/// items, so an item inside a `send_all` counts the same as one passed to
+/// `send`. Send indices are 1-based and count every item offered.
+///
+/// # Example
+///
+/// ```
+/// use spout::{MockCall, MockError, MockSpout, Spout};
+///
+/// let mut s = MockSpout::new().fail_on(2);
+/// assert_eq!(s.send('a'), Ok(()));
+/// assert_eq!(s.send('b'), Err(MockError::Send(2)));
+/// assert_eq!(s.send('c'), Ok(()));
+///
+/// assert_eq!(s.accepted().copied().collect::<Vec<_>>(), vec!['a', 'c']);
+/// assert_eq!(s.calls()[1], MockCall::Send { item: 'b', ok: false });
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockSpout<T> {
+    fail_on: Vec<u64>,
+    fail_every: Option<u64>,
+    fail_while: Option<Arc<AtomicBool>>,
+    fail_flush_on: Vec<u64>,
+    panic_on: Option<u64>,
+    delay: Option<Duration>,
+    sends: u64,
+    flushes: u64,
+    calls: Vec<MockCall<T>>,
+}
+
+impl<T> MockSpout<T> {
+    /// Create a mock that accepts everything.
+    pub fn new() -> Self {
+        Self {
+            fail_on: Vec::new(),
+            fail_every: None,
+            fail_while: None,
+            fail_flush_on: Vec::new(),
+            panic_on: None,
+            delay: None,
+            sends: 0,
+            flushes: 0,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Fail the `n`th item sent. May be called repeatedly.
+    pub fn fail_on(mut self, n: u64) -> Self {
+        self.fail_on.push(n);
+        self
+    }
+
+    /// Fail every `k`th item sent.
+    ///
+    /// # Panics
+    /// Panics if `k` is 0.
+    pub fn fail_every(mut self, k: u64) -> Self {
+        assert!(k > 0, "MockSpout fail_every must be at least 1");
+        self.fail_every = Some(k);
+        self
+    }
+
+    /// Fail every send while `flag` is set.
+    pub fn fail_while(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.fail_while = Some(flag);
+        self
+    }
+
+    /// Fail the `n`th flush. May be called repeatedly.
+    pub fn fail_flush_on(mut self, n: u64) -> Self {
+        self.fail_flush_on.push(n);
+        self
+    }
+
+    /// Panic when the `n`th item is sent.
+    pub fn panic_on(mut self, n: u64) -> Self {
+        self.panic_on = Some(n);
+        self
+    }
+
+    /// Sleep for `delay` before handling each item and each flush.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Every call, in order.
+    pub fn calls(&self) -> &[MockCall<T>] {
+        &self.calls
+    }
+
+    /// Items accepted so far, in order.
+    pub fn accepted(&self) -> impl Iterator<Item = &T> {
+        self.calls.iter().flat_map(|call| {
+            let items: &[T] = match call {
+                MockCall::Send { item, ok: true } => core::slice::from_ref(item),
+                MockCall::Send { ok: false, .. } | MockCall::Flush { .. } => &[],
+                MockCall::SendAll { items, ok: true } => items,
+                // The last item is the one that was rejected.
+                MockCall::SendAll { items, ok: false } => &items[..items.len() - 1],
+            };
+            items.iter()
+        })
+    }
+
+    /// Number of items offered, accepted or not.
+    pub fn sends(&self) -> u64 {
+        self.sends
+    }
+
+    /// Number of flushes, successful or not.
+    pub fn flushes(&self) -> u64 {
+        self.flushes
+    }
+
+    /// Forget recorded calls and reset the send and flush counters.
+    ///
+    /// Configured faults are kept.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+        self.sends = 0;
+        self.flushes = 0;
+    }
+
+    /// Apply the fault script to the next item.
+    fn check_send(&mut self) -> Result<(), MockError> {
+        self.sends += 1;
+        let n = self.sends;
+        if self.panic_on == Some(n) {
+            panic!("MockSpout panic on send {n}");
+        }
+        if let Some(delay) = self.delay {
+            std::thread::sleep(delay);
+        }
+        let fail = self.fail_on.contains(&n)
+            || self.fail_every.is_some_and(|k| n % k == 0)
+            || self
+                .fail_while
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Acquire));
+        if fail {
+            Err(MockError::Send(n))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Default for MockSpout<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Spout<T> for MockSpout<T> {
+    type Error = MockError;
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let result = self.check_send();
+        self.calls.push(MockCall::Send {
+            item,
+            ok: result.is_ok(),
+        });
+        result
+    }
+
+    fn send_all(&mut self, items: impl Iterator<Item = T>) -> Result<(), Self::Error> {
+        let mut consumed = Vec::new();
+        let mut result = Ok(());
+        for item in items {
+            result = self.check_send();
+            consumed.push(item);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.calls.push(MockCall::SendAll {
+            items: consumed,
+            ok: result.is_ok(),
+        });
+        result
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flushes += 1;
+        let n = self.flushes;
+        if let Some(delay) = self.delay {
+            std::thread::sleep(delay);
+        }
+        let result = if self.fail_flush_on.contains(&n) {
+            Err(MockError::Flush(n))
+        } else {
+            Ok(())
+        };
+        self.calls.push(MockCall::Flush { ok: result.is_ok() });
+        result
+    }
+}
@@ -1,15 +1,53 @@
+mod async_impls;
 mod core_impls;
+mod dedup;
+mod metered;
+mod sample;
+mod sequence;
+mod window;
 
+#[cfg(feature = "std")]
+mod async_channel;
+#[cfg(feature = "std")]
+mod bounded;
+#[cfg(feature = "std")]
+mod socket;
 #[cfg(feature = "std")]
 mod std_impls;
 
+#[cfg(feature = "testing")]
+mod mock;
+
+#[cfg(any(feature = "json", feature = "csv"))]
+mod text;
+
 #[cfg(feature = "bytecast")]
 mod bytecast_impls;
+#[cfg(feature = "bytecast")]
+pub(crate) mod crc32;
 
+pub use async_impls::*;
 pub use core_impls::*;
+pub use dedup::*;
+pub use metered::*;
+pub use sample::*;
+pub use sequence::*;
+pub use window::*;
 
+#[cfg(feature = "std")]
+pub use async_channel::*;
+#[cfg(feature = "std")]
+pub use bounded::*;
+#[cfg(feature = "std")]
+pub use socket::*;
 #[cfg(feature = "std")]
 pub use std_impls::*;
 
+#[cfg(feature = "testing")]
+pub use mock::*;
+
+#[cfg(any(feature = "json", feature = "csv"))]
+pub use text::*;
+
 #[cfg(feature = "bytecast")]
 pub use bytecast_impls::*;
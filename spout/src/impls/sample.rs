@@ -0,0 +1,368 @@
+//! Sampling and rate-limiting spouts.
+//!
+//! Each spout forwards a subset of its input to an inner spout and counts
+//! what it dropped. All of them are `no_std` + `alloc`.
+
+use alloc::vec::Vec;
+
+use crate::{Clock, Spout};
+
+/// SplitMix64 generator. Small, fast, and good enough for sampling decisions.
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound` (Lemire's multiply-shift, slight bias is acceptable).
+    #[inline]
+    fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// Forwards every `n`th item, starting with the first.
+#[derive(Debug, Clone)]
+pub struct EveryNth<S> {
+    n: u64,
+    count: u64,
+    dropped: u64,
+    inner: S,
+}
+
+impl<S> EveryNth<S> {
+    /// Create a new every-nth sampler.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    pub fn new(n: u64, inner: S) -> Self {
+        assert!(n > 0, "EveryNth n must be at least 1");
+        Self {
+            n,
+            count: 0,
+            dropped: 0,
+            inner,
+        }
+    }
+
+    /// Get the sampling interval.
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Number of items dropped.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S: Spout<T>> Spout<T> for EveryNth<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let keep = self.count % self.n == 0;
+        self.count = self.count.wrapping_add(1);
+        if keep {
+            self.inner.send(item)
+        } else {
+            self.dropped += 1;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+/// Forwards each item independently with a fixed probability.
+///
+/// Decisions come from an in-crate PRNG seeded at construction, so a run
+/// is reproducible for a given seed.
+#[derive(Debug, Clone)]
+pub struct ProbabilisticSample<S> {
+    /// Items are kept when a random `u64` falls below this value.
+    threshold: u64,
+    /// `probability >= 1.0` keeps everything, which `threshold` cannot express.
+    keep_all: bool,
+    rng: SplitMix64,
+    dropped: u64,
+    inner: S,
+}
+
+impl<S> ProbabilisticSample<S> {
+    /// Create a new sampler that keeps each item with `probability`.
+    ///
+    /// Values at or below `0.0` drop everything; values at or above `1.0`
+    /// keep everything.
+    pub fn new(probability: f64, seed: u64, inner: S) -> Self {
+        let keep_all = probability >= 1.0;
+        let threshold = if probability <= 0.0 || probability.is_nan() {
+            0
+        } else if keep_all {
+            u64::MAX
+        } else {
+            (probability * u64::MAX as f64) as u64
+        };
+        Self {
+            threshold,
+            keep_all,
+            rng: SplitMix64(seed),
+            dropped: 0,
+            inner,
+        }
+    }
+
+    /// Number of items dropped.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S: Spout<T>> Spout<T> for ProbabilisticSample<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        if self.keep_all || self.rng.next_u64() < self.threshold {
+            self.inner.send(item)
+        } else {
+            self.dropped += 1;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+/// Keeps a uniform random sample of `K` items and emits it on `flush`.
+///
+/// Uses reservoir sampling (Algorithm R): every item seen since the last
+/// flush has an equal chance of being in the sample. On `flush`, the sample
+/// is forwarded with a single `send_all`, the reservoir starts over, and the
+/// inner spout is flushed. If `send_all` fails, the items it did not take
+/// stay in the sample and the count of items seen is kept, so the flush can
+/// be retried.
+#[derive(Debug, Clone)]
+pub struct Reservoir<T, S, const K: usize> {
+    sample: Vec<T>,
+    seen: u64,
+    rng: SplitMix64,
+    dropped: u64,
+    inner: S,
+}
+
+impl<T, S, const K: usize> Reservoir<T, S, K> {
+    /// Create a new reservoir sampler.
+    pub fn new(seed: u64, inner: S) -> Self {
+        const { assert!(K > 0, "reservoir size must be > 0") };
+        Self {
+            sample: Vec::with_capacity(K),
+            seen: 0,
+            rng: SplitMix64(seed),
+            dropped: 0,
+            inner,
+        }
+    }
+
+    /// Items seen since the last flush.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// The current sample.
+    pub fn sample(&self) -> &[T] {
+        &self.sample
+    }
+
+    /// Number of items that will not be emitted because the reservoir was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    ///
+    /// The current sample is dropped. Call `flush()` first to emit it.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S: Spout<T>, const K: usize> Spout<T> for Reservoir<T, S, K> {
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.seen += 1;
+        if self.sample.len() < K {
+            self.sample.push(item);
+            return Ok(());
+        }
+        self.dropped += 1;
+        let j = self.rng.below(self.seen);
+        if (j as usize) < K {
+            self.sample[j as usize] = item;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        if !self.sample.is_empty() {
+            let mut items = core::mem::take(&mut self.sample).into_iter();
+            if let Err(e) = self.inner.send_all(&mut items) {
+                self.sample = items.collect();
+                return Err(e);
+            }
+            self.sample = Vec::with_capacity(K);
+        }
+        self.seen = 0;
+        self.inner.flush()
+    }
+}
+
+/// Token-bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens and gains one token every
+/// `interval` ticks of the [`Clock`]. Each forwarded item spends a token;
+/// items arriving to an empty bucket are dropped.
+#[derive(Debug, Clone)]
+pub struct RateLimit<S, C> {
+    burst: u64,
+    interval: u64,
+    tokens: u64,
+    last_refill: u64,
+    clock: C,
+    dropped: u64,
+    inner: S,
+}
+
+impl<S, C: Clock> RateLimit<S, C> {
+    /// Create a new rate limiter with a full bucket.
+    ///
+    /// # Panics
+    /// Panics if `burst` or `interval` is 0.
+    pub fn new(burst: u64, interval: u64, clock: C, inner: S) -> Self {
+        assert!(burst > 0, "RateLimit burst must be at least 1");
+        assert!(interval > 0, "RateLimit interval must be at least 1");
+        let last_refill = clock.now();
+        Self {
+            burst,
+            interval,
+            tokens: burst,
+            last_refill,
+            clock,
+            dropped: 0,
+            inner,
+        }
+    }
+
+    /// Tokens currently available, as of the last send.
+    pub fn tokens(&self) -> u64 {
+        self.tokens
+    }
+
+    /// Number of items dropped.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        let intervals = now.saturating_sub(self.last_refill) / self.interval;
+        if intervals == 0 {
+            return;
+        }
+        self.tokens = self.tokens.saturating_add(intervals).min(self.burst);
+        // Keep the remainder so partial intervals still count toward the next token.
+        self.last_refill += intervals * self.interval;
+    }
+}
+
+impl<T, S: Spout<T>, C: Clock> Spout<T> for RateLimit<S, C> {
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.refill();
+        if self.tokens > 0 {
+            self.tokens -= 1;
+            self.inner.send(item)
+        } else {
+            self.dropped += 1;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,382 @@
+//! Sequence stamping and in-order reassembly.
+//!
+//! [`Sequenced`] stamps each item with a producer ID and a monotonic
+//! sequence number. [`ReorderSpout`] sits on the consumer side, buffers
+//! items that arrive early, and releases them in sequence order.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Clock, Spout};
+
+/// An item stamped by [`Sequenced`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stamped<T> {
+    /// ID of the producer that sent the item.
+    pub producer_id: usize,
+    /// Sequence number, starting at 0.
+    pub sequence: u64,
+    /// The item.
+    pub item: T,
+}
+
+/// Stamps items with a producer ID and a monotonic sequence number.
+///
+/// Clones share the sequence counter, so several producers cloned from one
+/// `Sequenced` draw from a single global sequence. For per-producer
+/// sequences, give each producer its own `Sequenced` (for example from a
+/// [`ProducerSpout`](crate::ProducerSpout) factory).
+///
+/// The sequence number is consumed even if the inner send fails, so
+/// downstream sees the failure as a gap.
+#[derive(Debug, Clone)]
+pub struct Sequenced<S> {
+    producer_id: usize,
+    next: Arc<AtomicU64>,
+    inner: S,
+}
+
+impl<S> Sequenced<S> {
+    /// Create a sequenced spout with its own counter starting at 0.
+    pub fn new(producer_id: usize, inner: S) -> Self {
+        Self::with_counter(producer_id, Arc::new(AtomicU64::new(0)), inner)
+    }
+
+    /// Create a sequenced spout drawing from a shared counter.
+    pub fn with_counter(producer_id: usize, counter: Arc<AtomicU64>, inner: S) -> Self {
+        Self {
+            producer_id,
+            next: counter,
+            inner,
+        }
+    }
+
+    /// Get this producer's ID.
+    pub fn producer_id(&self) -> usize {
+        self.producer_id
+    }
+
+    /// Get the shared sequence counter.
+    pub fn counter(&self) -> &Arc<AtomicU64> {
+        &self.next
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume and return the inner spout.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T, S: Spout<Stamped<T>>> Spout<T> for Sequenced<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let sequence = self.next.fetch_add(1, Ordering::Relaxed);
+        self.inner.send(Stamped {
+            producer_id: self.producer_id,
+            sequence,
+            item,
+        })
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+/// Which sequence an incoming item belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReorderMode {
+    /// One sequence shared by all producers.
+    Global,
+    /// An independent sequence per producer ID.
+    PerProducer,
+}
+
+/// Reorder state for one sequence.
+#[derive(Debug, Clone)]
+struct Stream<T> {
+    next: u64,
+    pending: BTreeMap<u64, Stamped<T>>,
+    /// Clock time the current gap was first observed.
+    stalled_since: Option<u64>,
+}
+
+impl<T> Stream<T> {
+    fn new() -> Self {
+        Self {
+            next: 0,
+            pending: BTreeMap::new(),
+            stalled_since: None,
+        }
+    }
+
+    /// Forward every pending item that is next in sequence.
+    ///
+    /// An item leaves the buffer only once `sink` accepts it.
+    fn release<S: Spout<Stamped<T>>>(&mut self, sink: &mut S) -> Result<(), S::Error>
+    where
+        T: Clone,
+    {
+        while let Some(entry) = self.pending.first_entry() {
+            if *entry.key() != self.next {
+                break;
+            }
+            sink.send(entry.get().clone())?;
+            entry.remove();
+            self.next += 1;
+        }
+        Ok(())
+    }
+
+    /// Give up on the current gap and resume at the lowest buffered sequence.
+    ///
+    /// Returns the number of sequence numbers skipped.
+    fn skip_gap(&mut self) -> u64 {
+        match self.pending.keys().next() {
+            Some(&first) if first > self.next => {
+                let missing = first - self.next;
+                self.next = first;
+                missing
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Releases [`Stamped`] items in sequence order.
+///
+/// Items that arrive ahead of the next expected sequence number are
+/// buffered. Once the missing item arrives, it and every consecutive
+/// buffered item are forwarded. Items at or behind the expected sequence
+/// number are late duplicates and are dropped.
+///
+/// A gap is given up on, and counted in [`skipped`](Self::skipped), when:
+/// - more than `capacity` items are buffered for one sequence, or
+/// - a timeout is configured and the gap has been open for at least that
+///   many clock ticks.
+///
+/// `flush` gives up on every gap, forwards everything buffered in order,
+/// then flushes the inner spout.
+///
+/// Items are forwarded as clones and leave the buffer only once the inner
+/// spout accepts them, so after a sink error the rejected item is sent
+/// again on the next `send`, `expire_gaps` or `flush`.
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, ReorderSpout, Spout, Stamped};
+///
+/// let stamp = |sequence, item| Stamped { producer_id: 0, sequence, item };
+/// let mut s = ReorderSpout::new(8, CollectSpout::new());
+/// s.send(stamp(1, 'b')).unwrap();
+/// s.send(stamp(2, 'c')).unwrap();
+/// assert!(s.inner().items().is_empty());
+///
+/// s.send(stamp(0, 'a')).unwrap();
+/// let items: Vec<char> = s.inner().items().iter().map(|s| s.item).collect();
+/// assert_eq!(items, vec!['a', 'b', 'c']);
+/// ```
+#[derive(Debug)]
+pub struct ReorderSpout<T, S, C = fn() -> u64> {
+    mode: ReorderMode,
+    capacity: usize,
+    timeout: Option<(u64, C)>,
+    streams: BTreeMap<usize, Stream<T>>,
+    skipped: u64,
+    late: u64,
+    sink: S,
+}
+
+impl<T, S> ReorderSpout<T, S> {
+    /// Reorder by a single sequence shared by all producers.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize, sink: S) -> Self {
+        Self::with_mode(ReorderMode::Global, capacity, sink)
+    }
+
+    /// Reorder each producer's sequence independently.
+    ///
+    /// Items from different producers are not ordered relative to each
+    /// other. `capacity` applies to each producer separately.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn per_producer(capacity: usize, sink: S) -> Self {
+        Self::with_mode(ReorderMode::PerProducer, capacity, sink)
+    }
+
+    fn with_mode(mode: ReorderMode, capacity: usize, sink: S) -> Self {
+        assert!(capacity > 0, "ReorderSpout capacity must be at least 1");
+        Self {
+            mode,
+            capacity,
+            timeout: None,
+            streams: BTreeMap::new(),
+            skipped: 0,
+            late: 0,
+            sink,
+        }
+    }
+}
+
+impl<T, S, C> ReorderSpout<T, S, C> {
+    /// Give up on a gap once it has been open for `timeout` ticks of `clock`.
+    pub fn with_timeout<C2: Clock>(self, timeout: u64, clock: C2) -> ReorderSpout<T, S, C2> {
+        ReorderSpout {
+            mode: self.mode,
+            capacity: self.capacity,
+            timeout: Some((timeout, clock)),
+            streams: self.streams,
+            skipped: self.skipped,
+            late: self.late,
+            sink: self.sink,
+        }
+    }
+
+    /// Number of items currently buffered.
+    pub fn buffered(&self) -> usize {
+        self.streams.values().map(|s| s.pending.len()).sum()
+    }
+
+    /// Number of missing sequence numbers given up on.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Number of late or duplicate items dropped.
+    pub fn late(&self) -> u64 {
+        self.late
+    }
+
+    /// Next sequence number expected from `producer_id`.
+    ///
+    /// In global mode the producer ID is ignored.
+    pub fn expected(&self, producer_id: usize) -> u64 {
+        self.streams
+            .get(&self.key(producer_id))
+            .map_or(0, |s| s.next)
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consume and return the inner spout.
+    ///
+    /// Buffered items are dropped. Call `flush()` first to emit them.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn key(&self, producer_id: usize) -> usize {
+        match self.mode {
+            ReorderMode::Global => 0,
+            ReorderMode::PerProducer => producer_id,
+        }
+    }
+}
+
+impl<T, S, C> ReorderSpout<T, S, C>
+where
+    T: Clone,
+    S: Spout<Stamped<T>>,
+    C: Clock,
+{
+    /// Give up on every gap that has been open past the timeout.
+    ///
+    /// Called automatically on each send. Call it directly to release items
+    /// when no more input is arriving. No-op without a timeout.
+    pub fn expire_gaps(&mut self) -> Result<(), S::Error> {
+        let Some((timeout, clock)) = &self.timeout else {
+            return Ok(());
+        };
+        let now = clock.now();
+        for stream in self.streams.values_mut() {
+            let Some(since) = stream.stalled_since else {
+                continue;
+            };
+            if now.saturating_sub(since) < *timeout {
+                continue;
+            }
+            self.skipped += stream.skip_gap();
+            let result = stream.release(&mut self.sink);
+            stream.stalled_since = (!stream.pending.is_empty()).then_some(now);
+            result?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, S, C> Spout<Stamped<T>> for ReorderSpout<T, S, C>
+where
+    T: Clone,
+    S: Spout<Stamped<T>>,
+    C: Clock,
+{
+    type Error = S::Error;
+
+    fn send(&mut self, item: Stamped<T>) -> Result<(), Self::Error> {
+        let now = self.timeout.as_ref().map(|(_, clock)| clock.now());
+        let key = self.key(item.producer_id);
+        let stream = self.streams.entry(key).or_insert_with(Stream::new);
+
+        if item.sequence < stream.next || stream.pending.contains_key(&item.sequence) {
+            self.late += 1;
+        } else {
+            let before = stream.next;
+            stream.pending.insert(item.sequence, item);
+            let mut result = stream.release(&mut self.sink);
+            if stream.pending.len() > self.capacity {
+                self.skipped += stream.skip_gap();
+                if result.is_ok() {
+                    result = stream.release(&mut self.sink);
+                }
+            }
+            // A gap that moved is a new gap; restart its timer.
+            stream.stalled_since = if stream.pending.is_empty() {
+                None
+            } else if stream.next != before || stream.stalled_since.is_none() {
+                now
+            } else {
+                stream.stalled_since
+            };
+            result?;
+        }
+
+        self.expire_gaps()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        for stream in self.streams.values_mut() {
+            while !stream.pending.is_empty() {
+                self.skipped += stream.skip_gap();
+                stream.release(&mut self.sink)?;
+            }
+            stream.stalled_since = None;
+        }
+        self.sink.flush()
+    }
+}
@@ -0,0 +1,607 @@
+//! Byte spouts over local sockets, and a listener-side source.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+use crate::{Source, Spout, StreamDecoder};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::PathBuf;
+
+/// Opens the stream a [`SocketSpout`] writes to.
+pub trait Connect {
+    /// The connected stream.
+    type Stream: Write;
+
+    /// Open a new connection to the peer.
+    fn connect(&mut self) -> io::Result<Self::Stream>;
+}
+
+/// Connects to a TCP address, with `TCP_NODELAY` set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpConnector {
+    addr: SocketAddr,
+}
+
+impl TcpConnector {
+    /// Create a connector for `addr`.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    /// The peer address.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Connect for TcpConnector {
+    type Stream = TcpStream;
+
+    fn connect(&mut self) -> io::Result<TcpStream> {
+        let stream = TcpStream::connect(self.addr)?;
+        stream.set_nodelay(true)?;
+        Ok(stream)
+    }
+}
+
+/// Connects to a Unix domain socket path.
+#[cfg(unix)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixConnector {
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl UnixConnector {
+    /// Create a connector for the socket at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The socket path.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl Connect for UnixConnector {
+    type Stream = UnixStream;
+
+    fn connect(&mut self) -> io::Result<UnixStream> {
+        UnixStream::connect(&self.path)
+    }
+}
+
+/// What a [`SocketSpout`] does with items while the peer is unreachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectedPolicy {
+    /// Keep up to this many items for the next connection; drop the rest.
+    Buffer(usize),
+    /// Drop items until the connection is back.
+    Drop,
+}
+
+/// Exponential reconnect delay.
+///
+/// The first attempt after a disconnect is immediate. Each failed attempt
+/// doubles the wait before the next one, starting at `initial` and capped
+/// at `max`. A successful connection resets the delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Wait after the first failed attempt.
+    pub initial: Duration,
+    /// Upper bound on the wait.
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(10),
+            max: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Default number of buffered bytes that triggers a write.
+const DEFAULT_WRITE_THRESHOLD: usize = 8 * 1024;
+
+/// Byte spout over a stream socket that reconnects when the peer goes away.
+///
+/// Items are buffered and written back to back once
+/// [`write_threshold`](Self::with_write_threshold) bytes are pending, and
+/// on `flush`. Items carry no framing of their own, so send self-delimiting
+/// records such as [`FramedSpoutV2`](crate::FramedSpoutV2) output.
+///
+/// The connection is opened lazily on the first send. When a write fails,
+/// the connection is dropped and its unwritten items are kept, subject to
+/// the [`DisconnectedPolicy`]. Reconnects are attempted on later sends and
+/// flushes, spaced out by a [`Backoff`].
+///
+/// Delivery is best-effort. A successful write only means the OS accepted
+/// the bytes, so items written just before the peer went away can be lost
+/// without an error. A batch that failed partway is written again in full,
+/// so the peer may also see the start of it twice.
+///
+/// Sending never fails: items that cannot be kept are counted in
+/// [`dropped`](Self::dropped).
+///
+/// # Example
+///
+/// ```no_run
+/// use spout::{DisconnectedPolicy, Spout, TcpSpout};
+///
+/// let mut s = TcpSpout::tcp("127.0.0.1:9000".parse().unwrap())
+///     .with_policy(DisconnectedPolicy::Buffer(1024));
+/// s.send(b"hello\n".to_vec()).unwrap();
+/// s.flush().unwrap();
+/// ```
+pub struct SocketSpout<C: Connect> {
+    connector: C,
+    stream: Option<C::Stream>,
+    pending: VecDeque<Vec<u8>>,
+    pending_bytes: usize,
+    scratch: Vec<u8>,
+    write_threshold: usize,
+    policy: DisconnectedPolicy,
+    backoff: Backoff,
+    delay: Duration,
+    next_attempt: Option<Instant>,
+    connected_once: bool,
+    reconnects: u64,
+    connect_failures: u64,
+    dropped: u64,
+}
+
+/// [`SocketSpout`] over TCP.
+pub type TcpSpout = SocketSpout<TcpConnector>;
+
+/// [`SocketSpout`] over a Unix domain socket.
+#[cfg(unix)]
+pub type UnixStreamSpout = SocketSpout<UnixConnector>;
+
+impl SocketSpout<TcpConnector> {
+    /// Create a spout that connects to `addr` over TCP.
+    pub fn tcp(addr: SocketAddr) -> Self {
+        Self::new(TcpConnector::new(addr))
+    }
+}
+
+#[cfg(unix)]
+impl SocketSpout<UnixConnector> {
+    /// Create a spout that connects to the Unix socket at `path`.
+    pub fn unix(path: impl Into<PathBuf>) -> Self {
+        Self::new(UnixConnector::new(path))
+    }
+}
+
+impl<C: Connect> SocketSpout<C> {
+    /// Create a spout using `connector`.
+    ///
+    /// Defaults to [`DisconnectedPolicy::Buffer(1024)`](DisconnectedPolicy::Buffer),
+    /// the default [`Backoff`] and an 8 KiB write threshold.
+    pub fn new(connector: C) -> Self {
+        let backoff = Backoff::default();
+        Self {
+            connector,
+            stream: None,
+            pending: VecDeque::new(),
+            pending_bytes: 0,
+            scratch: Vec::new(),
+            write_threshold: DEFAULT_WRITE_THRESHOLD,
+            policy: DisconnectedPolicy::Buffer(1024),
+            backoff,
+            delay: backoff.initial,
+            next_attempt: None,
+            connected_once: false,
+            reconnects: 0,
+            connect_failures: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Set the policy for items that arrive while disconnected.
+    pub fn with_policy(mut self, policy: DisconnectedPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the reconnect backoff.
+    ///
+    /// # Panics
+    /// Panics if `backoff.initial` is zero or greater than `backoff.max`.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        assert!(
+            !backoff.initial.is_zero(),
+            "SocketSpout backoff must be at least 1ns"
+        );
+        assert!(
+            backoff.initial <= backoff.max,
+            "SocketSpout backoff initial must not exceed max"
+        );
+        self.backoff = backoff;
+        self.delay = backoff.initial;
+        self
+    }
+
+    /// Write once this many bytes are buffered. 0 writes every item
+    /// immediately.
+    pub fn with_write_threshold(mut self, bytes: usize) -> Self {
+        self.write_threshold = bytes;
+        self
+    }
+
+    /// Whether a connection is currently open.
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Number of items waiting to be written.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of items dropped by the disconnected policy.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Number of successful connections after the first.
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects
+    }
+
+    /// Number of failed connection attempts.
+    pub fn connect_failures(&self) -> u64 {
+        self.connect_failures
+    }
+
+    /// Get a reference to the connector.
+    pub fn connector(&self) -> &C {
+        &self.connector
+    }
+
+    /// Close the current connection, if any. The next send reconnects
+    /// without waiting for the backoff.
+    pub fn disconnect(&mut self) {
+        self.stream = None;
+        self.next_attempt = None;
+    }
+
+    /// Connect if disconnected and the backoff has elapsed.
+    fn try_connect(&mut self) {
+        if self.stream.is_some() {
+            return;
+        }
+        let now = Instant::now();
+        if self.next_attempt.is_some_and(|at| now < at) {
+            return;
+        }
+        match self.connector.connect() {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                self.next_attempt = None;
+                self.delay = self.backoff.initial;
+                if self.connected_once {
+                    self.reconnects += 1;
+                }
+                self.connected_once = true;
+            }
+            Err(_) => {
+                self.connect_failures += 1;
+                self.next_attempt = Some(now + self.delay);
+                self.delay = (self.delay * 2).min(self.backoff.max);
+            }
+        }
+    }
+
+    /// Drop the connection after an I/O error and apply the policy to
+    /// whatever is still pending.
+    fn on_write_error(&mut self) {
+        self.stream = None;
+        self.next_attempt = Some(Instant::now() + self.delay);
+        self.delay = (self.delay * 2).min(self.backoff.max);
+        let keep = match self.policy {
+            DisconnectedPolicy::Buffer(n) => n,
+            DisconnectedPolicy::Drop => 0,
+        };
+        while self.pending.len() > keep {
+            let item = self.pending.pop_back().expect("len > keep");
+            self.pending_bytes -= item.len();
+            self.dropped += 1;
+        }
+    }
+
+    /// Write every pending item in one go.
+    fn write_pending(&mut self) -> bool {
+        let Some(stream) = self.stream.as_mut() else {
+            return false;
+        };
+        if self.pending.is_empty() {
+            return true;
+        }
+        self.scratch.clear();
+        for item in &self.pending {
+            self.scratch.extend_from_slice(item);
+        }
+        if stream.write_all(&self.scratch).is_err() {
+            self.on_write_error();
+            return false;
+        }
+        self.pending.clear();
+        self.pending_bytes = 0;
+        true
+    }
+}
+
+impl<C: Connect> Spout<Vec<u8>> for SocketSpout<C> {
+    type Error = core::convert::Infallible;
+
+    fn send(&mut self, item: Vec<u8>) -> Result<(), Self::Error> {
+        self.try_connect();
+        if self.stream.is_none() {
+            let room = match self.policy {
+                DisconnectedPolicy::Buffer(n) => self.pending.len() < n,
+                DisconnectedPolicy::Drop => false,
+            };
+            if room {
+                self.pending_bytes += item.len();
+                self.pending.push_back(item);
+            } else {
+                self.dropped += 1;
+            }
+            return Ok(());
+        }
+
+        self.pending_bytes += item.len();
+        self.pending.push_back(item);
+        if self.pending_bytes >= self.write_threshold {
+            self.write_pending();
+        }
+        Ok(())
+    }
+
+    /// Write pending items and flush the socket. Reconnects first if the
+    /// backoff allows; otherwise items stay buffered.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.try_connect();
+        if self.write_pending() {
+            let failed = self.stream.as_mut().is_some_and(|s| s.flush().is_err());
+            if failed {
+                self.on_write_error();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C: Connect + core::fmt::Debug> core::fmt::Debug for SocketSpout<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SocketSpout")
+            .field("connector", &self.connector)
+            .field("connected", &self.stream.is_some())
+            .field("pending", &self.pending.len())
+            .field("policy", &self.policy)
+            .field("dropped", &self.dropped)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A listening socket that [`ListenerSource`] accepts connections from.
+pub trait Accept: Send + 'static {
+    /// An accepted connection.
+    type Stream: Read + Send + 'static;
+
+    /// Put the listener in non-blocking mode.
+    fn set_nonblocking(&self) -> io::Result<()>;
+
+    /// Accept a pending connection, or `Ok(None)` if there is none.
+    ///
+    /// The returned stream must be blocking with a short read timeout so
+    /// reader threads can notice shutdown.
+    fn accept_stream(&self, read_timeout: Duration) -> io::Result<Option<Self::Stream>>;
+}
+
+impl Accept for TcpListener {
+    type Stream = TcpStream;
+
+    fn set_nonblocking(&self) -> io::Result<()> {
+        TcpListener::set_nonblocking(self, true)
+    }
+
+    fn accept_stream(&self, read_timeout: Duration) -> io::Result<Option<TcpStream>> {
+        match self.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(read_timeout))?;
+                Ok(Some(stream))
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Accept for UnixListener {
+    type Stream = UnixStream;
+
+    fn set_nonblocking(&self) -> io::Result<()> {
+        UnixListener::set_nonblocking(self, true)
+    }
+
+    fn accept_stream(&self, read_timeout: Duration) -> io::Result<Option<UnixStream>> {
+        match self.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(read_timeout))?;
+                Ok(Some(stream))
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// How often background threads check for shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Source of items decoded from every connection made to a listener.
+///
+/// A background thread accepts connections and starts a reader thread for
+/// each one. Each reader feeds its connection's byte stream to its own
+/// decoder, made by the factory passed to [`new`](Self::new), and forwards
+/// the decoded items. Items from one connection arrive in order; items from
+/// different connections interleave.
+///
+/// A decode error is returned from `recv` and closes the connection it came
+/// from, since the stream can no longer be split reliably. Bytes are
+/// buffered until the decoder can use them, so decoders should reject a
+/// record too long to accept, as
+/// [`FrameStreamDecoder`](crate::FrameStreamDecoder) does. Dropping the
+/// source stops the background threads. `recv` returns `Ok(None)` only
+/// after [`shutdown`](Self::shutdown) once every connection has closed.
+pub struct ListenerSource<T, E> {
+    rx: mpsc::Receiver<Result<T, E>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static, E: Send + 'static> ListenerSource<T, E> {
+    /// Start accepting connections on `listener`, decoding each with a
+    /// fresh decoder from `make_decoder`.
+    pub fn new<L, D, F>(listener: L, mut make_decoder: F) -> io::Result<Self>
+    where
+        L: Accept,
+        D: StreamDecoder<Item = T, Error = E> + Send + 'static,
+        F: FnMut() -> D + Send + 'static,
+    {
+        listener.set_nonblocking()?;
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let accept_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            while !accept_stop.load(Ordering::Acquire) {
+                match listener.accept_stream(POLL_INTERVAL) {
+                    Ok(Some(stream)) => {
+                        let decoder = make_decoder();
+                        let tx = tx.clone();
+                        let stop = Arc::clone(&accept_stop);
+                        thread::spawn(move || read_connection(stream, decoder, tx, stop));
+                    }
+                    Ok(None) | Err(_) => thread::sleep(POLL_INTERVAL),
+                }
+            }
+        });
+
+        Ok(Self { rx, stop })
+    }
+}
+
+impl<T, E> ListenerSource<T, E> {
+    /// Stop accepting and close every connection.
+    ///
+    /// Items already decoded can still be received.
+    pub fn shutdown(&self) {
+        self.stop.store(true, Ordering::Release);
+    }
+
+    /// Wait up to `timeout` for the next item.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<T>, E> {
+        match self.rx.recv_timeout(timeout) {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl<T, E> Drop for ListenerSource<T, E> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<T, E> core::fmt::Debug for ListenerSource<T, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ListenerSource")
+            .field("stopped", &self.stop.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E> Source<T> for ListenerSource<T, E> {
+    type Error = E;
+
+    fn recv(&mut self) -> Result<Option<T>, E> {
+        match self.rx.recv() {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn try_recv(&mut self) -> Result<Option<T>, E> {
+        match self.rx.try_recv() {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Reader thread body: decode items from one connection until it closes,
+/// fails to decode, or the source shuts down.
+fn read_connection<R: Read, D: StreamDecoder>(
+    mut stream: R,
+    mut decoder: D,
+    tx: mpsc::Sender<Result<D::Item, D::Error>>,
+    stop: Arc<AtomicBool>,
+) {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8 * 1024];
+    while !stop.load(Ordering::Acquire) {
+        let n = match stream.read(&mut chunk) {
+            Ok(0) => return,
+            Ok(n) => n,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::Interrupted
+                ) =>
+            {
+                continue;
+            }
+            Err(_) => return,
+        };
+        buf.extend_from_slice(&chunk[..n]);
+
+        let mut start = 0;
+        loop {
+            match decoder.decode(&buf[start..]) {
+                Ok(Some((used, item))) => {
+                    start += used;
+                    if tx.send(Ok(item)).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            }
+        }
+        buf.drain(..start);
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::mpsc;
 
-use crate::Spout;
+use crate::{Clock, Source, Spout};
 
 #[derive(Debug, Clone)]
 pub struct ChannelSpout<T> {
@@ -33,6 +33,21 @@ impl<T> Spout<T> for ChannelSpout<T> {
     }
 }
 
+/// Receives from a std channel. Exhausted once every sender is dropped.
+impl<T> Source<T> for mpsc::Receiver<T> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(mpsc::Receiver::recv(self).ok())
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        Ok(mpsc::Receiver::try_recv(self).ok())
+    }
+}
+
 /// Bounded channel spout with backpressure.
 ///
 /// Wraps a [`SyncSender`](mpsc::SyncSender) — when the channel is full,
@@ -131,3 +146,31 @@ impl<T, S: Spout<T>> Spout<T> for std::sync::Arc<std::sync::Mutex<S>> {
             .map_err(MutexSpoutError::Spout)
     }
 }
+
+/// [`Clock`] reading nanoseconds elapsed since construction.
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    origin: std::time::Instant,
+}
+
+impl MonotonicClock {
+    /// Create a clock starting at zero now.
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    #[inline]
+    fn now(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+}
@@ -0,0 +1,499 @@
+//! Line-oriented text sinks for `serde::Serialize` items.
+//!
+//! [`JsonLinesSpout`] writes one JSON document per line. [`CsvSpout`]
+//! writes a header row followed by one row per item.
+
+use std::io::{self, Write};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use serde::Serialize;
+
+use crate::Spout;
+
+/// Error returned by the text sinks.
+#[derive(Debug)]
+pub enum TextSpoutError {
+    /// The underlying writer failed.
+    Io(io::Error),
+    /// The item could not be encoded.
+    Encode(String),
+}
+
+impl From<io::Error> for TextSpoutError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl core::fmt::Display for TextSpoutError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "text sink I/O error: {e}"),
+            Self::Encode(msg) => write!(f, "text sink encode error: {msg}"),
+        }
+    }
+}
+
+impl core::error::Error for TextSpoutError {}
+
+/// Writes each item as one line of JSON ([JSON Lines](https://jsonlines.org)).
+///
+/// Strings are escaped by `serde_json`, so embedded newlines never split a
+/// record. Each item is encoded in full before anything is written, so an
+/// item that fails to encode leaves no partial line behind. Wrap the writer
+/// in a [`BufWriter`](std::io::BufWriter) to avoid a write call per item;
+/// `flush` flushes the writer.
+///
+/// # Example
+///
+/// ```
+/// use spout::{JsonLinesSpout, Spout};
+///
+/// let mut s = JsonLinesSpout::new(Vec::new());
+/// s.send(("a", 1)).unwrap();
+/// s.send(("b\n", 2)).unwrap();
+///
+/// assert_eq!(s.into_inner(), b"[\"a\",1]\n[\"b\\n\",2]\n");
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug)]
+pub struct JsonLinesSpout<W> {
+    writer: W,
+    /// Reused encoding buffer.
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "json")]
+impl<W> JsonLinesSpout<W> {
+    /// Create a JSON Lines sink over `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the writer.
+    pub fn inner(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume and return the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: Serialize, W: Write> Spout<T> for JsonLinesSpout<W> {
+    type Error = TextSpoutError;
+
+    #[inline]
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.buf.clear();
+        serde_json::to_writer(&mut self.buf, &item).map_err(json_error)?;
+        self.buf.push(b'\n');
+        self.writer.write_all(&self.buf)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Map a `serde_json` error, keeping I/O failures as [`TextSpoutError::Io`].
+#[cfg(feature = "json")]
+fn json_error(e: serde_json::Error) -> TextSpoutError {
+    if e.is_io() {
+        TextSpoutError::Io(e.into())
+    } else {
+        TextSpoutError::Encode(e.to_string())
+    }
+}
+
+/// Writes items as CSV rows (RFC 4180 quoting) under a header row.
+///
+/// Items must serialize as structs or maps. The header is taken from the
+/// first item's field names; every later item must have the same fields in
+/// the same order. Scalar fields are written as text, `None` and unit as
+/// an empty cell, and nested values (sequences, structs, maps) as compact
+/// JSON in a single cell.
+///
+/// Cells containing the delimiter, a quote, or a line break are quoted,
+/// with embedded quotes doubled. `flush` flushes the writer.
+///
+/// # Example
+///
+/// ```
+/// use spout::{CsvSpout, Spout};
+///
+/// #[derive(serde::Serialize)]
+/// struct Row {
+///     name: &'static str,
+///     score: u32,
+/// }
+///
+/// let mut s = CsvSpout::new(Vec::new());
+/// s.send(Row { name: "ada", score: 3 }).unwrap();
+/// s.send(Row { name: "b, c", score: 5 }).unwrap();
+///
+/// assert_eq!(s.into_inner(), b"name,score\nada,3\n\"b, c\",5\n");
+/// ```
+#[cfg(feature = "csv")]
+#[derive(Debug)]
+pub struct CsvSpout<W> {
+    writer: W,
+    delimiter: u8,
+    write_header: bool,
+    header: Option<Vec<String>>,
+    row: Vec<(String, String)>,
+}
+
+#[cfg(feature = "csv")]
+impl<W> CsvSpout<W> {
+    /// Create a comma-separated sink over `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            delimiter: b',',
+            write_header: true,
+            header: None,
+            row: Vec::new(),
+        }
+    }
+
+    /// Use `delimiter` instead of a comma.
+    ///
+    /// # Panics
+    /// Panics if `delimiter` is a quote, `\r` or `\n`.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        assert!(
+            !matches!(delimiter, b'"' | b'\r' | b'\n'),
+            "CsvSpout delimiter must not be a quote or line break"
+        );
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Do not write a header row. Field names are still checked.
+    pub fn without_header(mut self) -> Self {
+        self.write_header = false;
+        self
+    }
+
+    /// The header, once the first item has been written.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    /// Get a reference to the writer.
+    pub fn inner(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume and return the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<W: Write> CsvSpout<W> {
+    fn write_record<'a>(&mut self, cells: impl Iterator<Item = &'a str>) -> io::Result<()> {
+        let mut line = Vec::new();
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            let needs_quotes = cell
+                .bytes()
+                .any(|b| b == self.delimiter || matches!(b, b'"' | b'\r' | b'\n'));
+            if needs_quotes {
+                line.push(b'"');
+                for b in cell.bytes() {
+                    if b == b'"' {
+                        line.push(b'"');
+                    }
+                    line.push(b);
+                }
+                line.push(b'"');
+            } else {
+                line.extend_from_slice(cell.as_bytes());
+            }
+        }
+        line.push(b'\n');
+        self.writer.write_all(&line)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<T: Serialize, W: Write> Spout<T> for CsvSpout<W> {
+    type Error = TextSpoutError;
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let mut row = core::mem::take(&mut self.row);
+        row.clear();
+        item.serialize(row::RowSerializer { cells: &mut row })
+            .map_err(|e| TextSpoutError::Encode(e.0))?;
+
+        let result = match &self.header {
+            Some(header) => {
+                if header.len() != row.len() || header.iter().zip(&row).any(|(h, (k, _))| h != k) {
+                    Err(TextSpoutError::Encode(
+                        "CSV row fields do not match the header".to_string(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            None => {
+                let header: Vec<String> = row.iter().map(|(k, _)| k.clone()).collect();
+                if self.write_header {
+                    self.write_record(header.iter().map(String::as_str))?;
+                }
+                self.header = Some(header);
+                Ok(())
+            }
+        };
+        if result.is_ok() {
+            self.write_record(row.iter().map(|(_, v)| v.as_str()))?;
+        }
+        self.row = row;
+        result
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Serializer capturing a struct or map as `(field, cell)` pairs.
+#[cfg(feature = "csv")]
+mod row {
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    use serde::Serialize;
+    use serde::ser::{self, Impossible};
+
+    #[derive(Debug)]
+    pub(super) struct RowError(pub(super) String);
+
+    impl core::fmt::Display for RowError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl core::error::Error for RowError {}
+
+    impl ser::Error for RowError {
+        fn custom<M: core::fmt::Display>(msg: M) -> Self {
+            Self(msg.to_string())
+        }
+    }
+
+    fn not_a_row<T>() -> Result<T, RowError> {
+        Err(RowError(
+            "CSV rows must serialize as a struct or map".to_string(),
+        ))
+    }
+
+    /// Render one field value as a cell.
+    fn cell<T: Serialize + ?Sized>(value: &T) -> Result<String, RowError> {
+        use serde_json::Value;
+        match serde_json::to_value(value).map_err(|e| RowError(e.to_string()))? {
+            Value::Null => Ok(String::new()),
+            Value::String(s) => Ok(s),
+            Value::Bool(b) => Ok(b.to_string()),
+            Value::Number(n) => Ok(n.to_string()),
+            // Re-serialize directly so struct fields keep declaration order.
+            _ => serde_json::to_string(value).map_err(|e| RowError(e.to_string())),
+        }
+    }
+
+    pub(super) struct RowSerializer<'a> {
+        pub(super) cells: &'a mut Vec<(String, String)>,
+    }
+
+    pub(super) struct RowFields<'a> {
+        cells: &'a mut Vec<(String, String)>,
+        key: Option<String>,
+    }
+
+    macro_rules! reject {
+        ($($method:ident($($arg:ty),*)),* $(,)?) => {
+            $(
+                fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                    not_a_row()
+                }
+            )*
+        };
+    }
+
+    impl<'a> ser::Serializer for RowSerializer<'a> {
+        type Ok = ();
+        type Error = RowError;
+        type SerializeSeq = Impossible<(), RowError>;
+        type SerializeTuple = Impossible<(), RowError>;
+        type SerializeTupleStruct = Impossible<(), RowError>;
+        type SerializeTupleVariant = Impossible<(), RowError>;
+        type SerializeMap = RowFields<'a>;
+        type SerializeStruct = RowFields<'a>;
+        type SerializeStructVariant = Impossible<(), RowError>;
+
+        reject!(
+            serialize_bool(bool),
+            serialize_i8(i8),
+            serialize_i16(i16),
+            serialize_i32(i32),
+            serialize_i64(i64),
+            serialize_u8(u8),
+            serialize_u16(u16),
+            serialize_u32(u32),
+            serialize_u64(u64),
+            serialize_f32(f32),
+            serialize_f64(f64),
+            serialize_char(char),
+            serialize_str(&str),
+            serialize_bytes(&[u8]),
+            serialize_none(),
+            serialize_unit(),
+            serialize_unit_struct(&'static str),
+            serialize_unit_variant(&'static str, u32, &'static str),
+        );
+
+        fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), RowError> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            value: &T,
+        ) -> Result<(), RowError> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            _index: u32,
+            _variant: &'static str,
+            _value: &T,
+        ) -> Result<(), RowError> {
+            not_a_row()
+        }
+
+        fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, RowError> {
+            not_a_row()
+        }
+
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, RowError> {
+            not_a_row()
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleStruct, RowError> {
+            not_a_row()
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, RowError> {
+            not_a_row()
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, RowError> {
+            Ok(RowFields {
+                cells: self.cells,
+                key: None,
+            })
+        }
+
+        fn serialize_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStruct, RowError> {
+            Ok(RowFields {
+                cells: self.cells,
+                key: None,
+            })
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStructVariant, RowError> {
+            not_a_row()
+        }
+    }
+
+    impl ser::SerializeStruct for RowFields<'_> {
+        type Ok = ();
+        type Error = RowError;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), RowError> {
+            self.cells.push((key.to_string(), cell(value)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<(), RowError> {
+            Ok(())
+        }
+    }
+
+    impl ser::SerializeMap for RowFields<'_> {
+        type Ok = ();
+        type Error = RowError;
+
+        fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), RowError> {
+            self.key = Some(cell(key)?);
+            Ok(())
+        }
+
+        fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RowError> {
+            let key = self
+                .key
+                .take()
+                .ok_or_else(|| RowError("map value without a key".to_string()))?;
+            self.cells.push((key, cell(value)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<(), RowError> {
+            Ok(())
+        }
+    }
+}
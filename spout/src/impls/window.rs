@@ -0,0 +1,539 @@
+//! Keyed windowed aggregation.
+//!
+//! [`WindowSpout`] groups items by a key function, assigns them to tumbling
+//! or sliding windows measured in clock ticks or per-key item counts, folds
+//! each window with an [`Aggregator`], and emits `(key, window, aggregate)`
+//! when the window closes.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+
+use crate::{Clock, Spout};
+
+/// Folds the items of one window into an aggregate.
+///
+/// A window's aggregate is created from its first item, so min/max style
+/// aggregators never need an empty state.
+pub trait Aggregator<T> {
+    /// The aggregate emitted when a window closes.
+    type Output;
+
+    /// Start a window's aggregate from its first item.
+    fn first(&mut self, item: &T) -> Self::Output;
+
+    /// Fold another item into a window's aggregate.
+    fn update(&mut self, acc: &mut Self::Output, item: &T);
+}
+
+/// Counts items per window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Count;
+
+impl<T> Aggregator<T> for Count {
+    type Output = u64;
+
+    #[inline]
+    fn first(&mut self, _item: &T) -> u64 {
+        1
+    }
+
+    #[inline]
+    fn update(&mut self, acc: &mut u64, _item: &T) {
+        *acc += 1;
+    }
+}
+
+/// Sums a value extracted from each item.
+#[derive(Debug, Clone, Copy)]
+pub struct Sum<F>(pub F);
+
+impl<T, V, F> Aggregator<T> for Sum<F>
+where
+    F: FnMut(&T) -> V,
+    V: core::ops::AddAssign,
+{
+    type Output = V;
+
+    #[inline]
+    fn first(&mut self, item: &T) -> V {
+        (self.0)(item)
+    }
+
+    #[inline]
+    fn update(&mut self, acc: &mut V, item: &T) {
+        *acc += (self.0)(item);
+    }
+}
+
+/// Keeps the smallest value extracted from each item.
+#[derive(Debug, Clone, Copy)]
+pub struct Min<F>(pub F);
+
+impl<T, V, F> Aggregator<T> for Min<F>
+where
+    F: FnMut(&T) -> V,
+    V: Ord,
+{
+    type Output = V;
+
+    #[inline]
+    fn first(&mut self, item: &T) -> V {
+        (self.0)(item)
+    }
+
+    #[inline]
+    fn update(&mut self, acc: &mut V, item: &T) {
+        let v = (self.0)(item);
+        if v < *acc {
+            *acc = v;
+        }
+    }
+}
+
+/// Keeps the largest value extracted from each item.
+#[derive(Debug, Clone, Copy)]
+pub struct Max<F>(pub F);
+
+impl<T, V, F> Aggregator<T> for Max<F>
+where
+    F: FnMut(&T) -> V,
+    V: Ord,
+{
+    type Output = V;
+
+    #[inline]
+    fn first(&mut self, item: &T) -> V {
+        (self.0)(item)
+    }
+
+    #[inline]
+    fn update(&mut self, acc: &mut V, item: &T) {
+        let v = (self.0)(item);
+        if v > *acc {
+            *acc = v;
+        }
+    }
+}
+
+/// Custom fold starting from a cloned initial accumulator.
+#[derive(Debug, Clone)]
+pub struct Fold<A, F> {
+    init: A,
+    fold: F,
+}
+
+impl<A, F> Fold<A, F> {
+    /// Create a fold that starts every window from `init`.
+    pub fn new(init: A, fold: F) -> Self {
+        Self { init, fold }
+    }
+}
+
+impl<T, A, F> Aggregator<T> for Fold<A, F>
+where
+    A: Clone,
+    F: FnMut(&mut A, &T),
+{
+    type Output = A;
+
+    #[inline]
+    fn first(&mut self, item: &T) -> A {
+        let mut acc = self.init.clone();
+        (self.fold)(&mut acc, item);
+        acc
+    }
+
+    #[inline]
+    fn update(&mut self, acc: &mut A, item: &T) {
+        (self.fold)(acc, item);
+    }
+}
+
+/// Window size and slide, in ticks or item counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowSpec {
+    size: u64,
+    slide: u64,
+}
+
+impl WindowSpec {
+    /// Non-overlapping windows of `size`.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    pub fn tumbling(size: u64) -> Self {
+        Self::sliding(size, size)
+    }
+
+    /// Windows of `size` starting every `slide`.
+    ///
+    /// With `slide < size` windows overlap and each item is counted in
+    /// several of them.
+    ///
+    /// # Panics
+    /// Panics if `size` or `slide` is 0.
+    pub fn sliding(size: u64, slide: u64) -> Self {
+        assert!(size > 0, "window size must be at least 1");
+        assert!(slide > 0, "window slide must be at least 1");
+        Self { size, slide }
+    }
+
+    /// Window length.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Distance between consecutive window starts.
+    pub fn slide(&self) -> u64 {
+        self.slide
+    }
+
+    /// Start of the earliest window containing `pos`, or `None` if `pos`
+    /// falls in a gap between hopping windows.
+    fn first_start(&self, pos: u64) -> Option<u64> {
+        let last = pos - pos % self.slide;
+        if last + self.size <= pos {
+            return None;
+        }
+        let back = (self.size - 1 - (pos - last)) / self.slide;
+        Some(last - back.min(last / self.slide) * self.slide)
+    }
+}
+
+/// Bounds of an emitted window: `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Window {
+    /// First tick or count position in the window.
+    pub start: u64,
+    /// One past the last position in the window.
+    pub end: u64,
+}
+
+/// How items are positioned within windows.
+#[derive(Debug, Clone)]
+enum Measure<C> {
+    /// Processing time read from a clock.
+    Time(C),
+    /// Per-key item count.
+    Count,
+}
+
+/// Open windows for one key, oldest first.
+#[derive(Debug, Clone)]
+struct KeyState<A> {
+    /// Items seen for this key (count windows only).
+    count: u64,
+    windows: VecDeque<(u64, A)>,
+}
+
+/// Keyed windowed aggregation.
+///
+/// Each item is keyed by `key_fn`, placed in every window that covers its
+/// position, and folded into that window's aggregate. When a window
+/// closes, `(key, window, aggregate)` is sent to the inner spout.
+///
+/// - Time windows ([`WindowSpout::time`]) are positioned by a [`Clock`].
+///   A window closes once the clock reaches its end, checked on every send
+///   and on [`close_expired`](Self::close_expired). Items older than an
+///   already-closed window are only added to windows still open and are
+///   counted as [`late`](Self::late) if none remain.
+/// - Count windows ([`WindowSpout::count`]) are positioned by how many
+///   items the key has seen. A window closes as soon as its last item
+///   arrives.
+///
+/// `flush` closes every open window, emits it, and resets per-key counts.
+///
+/// Each closed window is sent as a clone and dropped only once the inner
+/// spout accepts it. If the spout fails, the window and any after it are
+/// kept and sent ahead of newer ones on the next send, `close_expired` or
+/// `flush`.
+///
+/// # Example
+///
+/// ```
+/// use spout::{CollectSpout, Spout, Sum, Window, WindowSpec, WindowSpout};
+///
+/// // Sum bytes per producer over tumbling windows of 3 items.
+/// let mut s = WindowSpout::count(
+///     WindowSpec::tumbling(3),
+///     |&(producer, _): &(u32, u64)| producer,
+///     Sum(|&(_, bytes): &(u32, u64)| bytes),
+///     CollectSpout::new(),
+/// );
+/// for bytes in [10, 20, 30, 40] {
+///     s.send((7, bytes)).unwrap();
+/// }
+/// assert_eq!(s.inner().items(), &[(7, Window { start: 0, end: 3 }, 60)]);
+///
+/// s.flush().unwrap();
+/// assert_eq!(s.inner().items()[1], (7, Window { start: 3, end: 6 }, 40));
+/// ```
+pub struct WindowSpout<T, K, KF, A, S, C = fn() -> u64>
+where
+    A: Aggregator<T>,
+{
+    spec: WindowSpec,
+    measure: Measure<C>,
+    key_fn: KF,
+    aggregator: A,
+    keys: BTreeMap<K, KeyState<A::Output>>,
+    /// Time windows ending at or before this tick have been closed.
+    closed_until: u64,
+    /// Closed windows the inner spout has not accepted yet, in emit order.
+    unsent: VecDeque<(K, Window, A::Output)>,
+    late: u64,
+    sink: S,
+    _marker: core::marker::PhantomData<fn(T)>,
+}
+
+impl<T, K, KF, A, S> WindowSpout<T, K, KF, A, S>
+where
+    A: Aggregator<T>,
+{
+    /// Create count-based windows, positioned by per-key item count.
+    pub fn count(spec: WindowSpec, key_fn: KF, aggregator: A, sink: S) -> Self {
+        Self::with_measure(spec, Measure::Count, key_fn, aggregator, sink)
+    }
+}
+
+impl<T, K, KF, A, S, C> WindowSpout<T, K, KF, A, S, C>
+where
+    A: Aggregator<T>,
+{
+    /// Create time-based windows, positioned by `clock`.
+    pub fn time(spec: WindowSpec, clock: C, key_fn: KF, aggregator: A, sink: S) -> Self
+    where
+        C: Clock,
+    {
+        Self::with_measure(spec, Measure::Time(clock), key_fn, aggregator, sink)
+    }
+
+    fn with_measure(
+        spec: WindowSpec,
+        measure: Measure<C>,
+        key_fn: KF,
+        aggregator: A,
+        sink: S,
+    ) -> Self {
+        Self {
+            spec,
+            measure,
+            key_fn,
+            aggregator,
+            keys: BTreeMap::new(),
+            closed_until: 0,
+            unsent: VecDeque::new(),
+            late: 0,
+            sink,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Get the window spec.
+    pub fn spec(&self) -> WindowSpec {
+        self.spec
+    }
+
+    /// Number of windows currently open across all keys.
+    pub fn open_windows(&self) -> usize {
+        self.keys.values().map(|k| k.windows.len()).sum()
+    }
+
+    /// Number of items that arrived after every window covering them closed.
+    pub fn late(&self) -> u64 {
+        self.late
+    }
+
+    /// Get a reference to the inner spout.
+    pub fn inner(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner spout.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consume and return the inner spout.
+    ///
+    /// Open windows, and closed ones the spout has not accepted, are
+    /// dropped. Call `flush()` first to emit them.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<T, K, KF, A, S, C> WindowSpout<T, K, KF, A, S, C>
+where
+    K: Ord + Clone,
+    KF: FnMut(&T) -> K,
+    A: Aggregator<T>,
+    A::Output: Clone,
+    S: Spout<(K, Window, A::Output)>,
+    C: Clock,
+{
+    /// Close and emit every time window whose end the clock has reached.
+    ///
+    /// Called automatically on each send. Call it directly to close windows
+    /// for keys that have gone quiet. No-op for count windows.
+    pub fn close_expired(&mut self) -> Result<(), S::Error> {
+        if let Measure::Time(clock) = &self.measure {
+            let now = clock.now();
+            self.close_until(now)?;
+        }
+        Ok(())
+    }
+
+    /// Emit every window ending at or before `until`, ordered by window then key.
+    fn close_until(&mut self, until: u64) -> Result<(), S::Error> {
+        let closed = self.take_closed(until);
+        self.emit(closed)
+    }
+
+    /// Remove every window ending at or before `until`.
+    fn take_closed(&mut self, until: u64) -> Vec<(K, Window, A::Output)> {
+        let size = self.spec.size;
+        let mut closed = Vec::new();
+        for (key, state) in self.keys.iter_mut() {
+            while let Some(&(start, _)) = state.windows.front() {
+                if start + size > until {
+                    break;
+                }
+                let (start, acc) = state.windows.pop_front().unwrap();
+                closed.push((
+                    key.clone(),
+                    Window {
+                        start,
+                        end: start + size,
+                    },
+                    acc,
+                ));
+            }
+        }
+        self.keys.retain(|_, state| !state.windows.is_empty());
+        self.closed_until = self.closed_until.max(until);
+        closed
+    }
+
+    /// Send `closed` after any windows left over from a failed send.
+    fn emit(&mut self, mut closed: Vec<(K, Window, A::Output)>) -> Result<(), S::Error> {
+        closed.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+        self.unsent.extend(closed);
+        while let Some(window) = self.unsent.front() {
+            self.sink.send(window.clone())?;
+            self.unsent.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Fold `item` into every open window covering `pos` for `state`.
+    fn assign(&mut self, key: &K, pos: u64, item: &T) -> bool {
+        let Some(first) = self.spec.first_start(pos) else {
+            return false;
+        };
+        let state = self.keys.get_mut(key).unwrap();
+        let mut assigned = false;
+        let mut start = first;
+        while start <= pos && start + self.spec.size > pos {
+            if start + self.spec.size > self.closed_until || matches!(self.measure, Measure::Count)
+            {
+                match state.windows.binary_search_by_key(&start, |&(s, _)| s) {
+                    Ok(i) => self.aggregator.update(&mut state.windows[i].1, item),
+                    Err(i) => state
+                        .windows
+                        .insert(i, (start, self.aggregator.first(item))),
+                }
+                assigned = true;
+            }
+            start += self.spec.slide;
+        }
+        assigned
+    }
+}
+
+impl<T, K, KF, A, S, C> Spout<T> for WindowSpout<T, K, KF, A, S, C>
+where
+    K: Ord + Clone,
+    KF: FnMut(&T) -> K,
+    A: Aggregator<T>,
+    A::Output: Clone,
+    S: Spout<(K, Window, A::Output)>,
+    C: Clock,
+{
+    type Error = S::Error;
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        let key = (self.key_fn)(&item);
+        match &self.measure {
+            Measure::Time(clock) => {
+                let now = clock.now();
+                let closed = self.take_closed(now);
+                self.keys.entry(key.clone()).or_insert_with(|| KeyState {
+                    count: 0,
+                    windows: VecDeque::new(),
+                });
+                if !self.assign(&key, now, &item) {
+                    self.late += 1;
+                }
+                if self.keys.get(&key).is_some_and(|s| s.windows.is_empty()) {
+                    self.keys.remove(&key);
+                }
+                self.emit(closed)
+            }
+            Measure::Count => {
+                let pos = {
+                    let state = self.keys.entry(key.clone()).or_insert_with(|| KeyState {
+                        count: 0,
+                        windows: VecDeque::new(),
+                    });
+                    let pos = state.count;
+                    state.count += 1;
+                    pos
+                };
+                self.assign(&key, pos, &item);
+
+                let size = self.spec.size;
+                let state = self.keys.get_mut(&key).unwrap();
+                let mut closed = Vec::new();
+                while let Some(&(start, _)) = state.windows.front() {
+                    if start + size > state.count {
+                        break;
+                    }
+                    let (start, acc) = state.windows.pop_front().unwrap();
+                    closed.push((
+                        key.clone(),
+                        Window {
+                            start,
+                            end: start + size,
+                        },
+                        acc,
+                    ));
+                }
+                self.emit(closed)
+            }
+        }
+    }
+
+    /// Close and emit every open window, then flush the inner spout.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        let size = self.spec.size;
+        let mut closed = Vec::new();
+        for (key, state) in core::mem::take(&mut self.keys) {
+            for (start, acc) in state.windows {
+                closed.push((
+                    key.clone(),
+                    Window {
+                        start,
+                        end: start + size,
+                    },
+                    acc,
+                ));
+            }
+        }
+        if let Measure::Time(clock) = &self.measure {
+            self.closed_until = self.closed_until.max(clock.now());
+        }
+        self.emit(closed)?;
+        self.sink.flush()
+    }
+}
@@ -12,10 +12,18 @@ mod traits;
 mod tests;
 
 pub use impls::*;
-pub use traits::{Flush, Spout};
+pub use traits::{AsyncSpout, Clock, Flush, FlushFuture, SendFuture, Source, Spout, StreamDecoder};
 
 #[cfg(feature = "std")]
-pub use impls::{ChannelSpout, SyncChannelSpout};
+pub use impls::{
+    Accept, AsyncChannelSpout, AsyncReceiver, Backoff, BoundedReceiver, BoundedSendError,
+    BoundedSpout, ChannelClosed, ChannelSpout, Connect, DisconnectedPolicy, ListenerSource,
+    MonotonicClock, OverflowPolicy, RecvFuture, SocketSpout, SyncChannelSpout, TcpConnector,
+    TcpSpout, async_channel, bounded,
+};
+
+#[cfg(all(feature = "std", unix))]
+pub use impls::{UnixConnector, UnixStreamSpout};
 
 #[cfg(feature = "bytecast")]
 pub use bytecast::{FromBytes, FromBytesExt, ToBytes, ToBytesExt};
@@ -0,0 +1,220 @@
+extern crate std;
+
+use core::convert::Infallible;
+use core::task::{Context, Poll, Waker};
+use std::vec;
+use std::vec::Vec;
+
+use crate::{
+    AsyncAdapter, AsyncBatchSpout, AsyncSpout, BlockingSpout, CollectSpout, Spout, block_on,
+};
+
+/// Accepts one item per two `poll_ready` calls, to exercise `Pending`.
+#[derive(Default)]
+struct Sluggish {
+    items: Vec<Vec<i32>>,
+    polls: u32,
+    flushes: u32,
+}
+
+impl AsyncSpout<Vec<i32>> for Sluggish {
+    type Error = Infallible;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.polls += 1;
+        if self.polls % 2 == 1 {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn send(&mut self, item: Vec<i32>) -> Result<(), Self::Error> {
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn poll_flush(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.flushes += 1;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn block_on_returns_output() {
+    assert_eq!(block_on(async { 1 + 2 }), 3);
+}
+
+#[test]
+fn async_adapter_forwards_to_sync_spout() {
+    let mut s = AsyncAdapter::new(CollectSpout::new());
+    block_on(async {
+        s.send_async(1).await.unwrap();
+        s.send_async(2).await.unwrap();
+        s.flush_async().await.unwrap();
+    });
+    assert_eq!(s.into_inner().into_items(), vec![1, 2]);
+}
+
+#[test]
+fn blocking_spout_drives_async_spout() {
+    let mut s = BlockingSpout::new(Sluggish::default());
+    s.send(vec![1]).unwrap();
+    s.send(vec![2]).unwrap();
+    s.flush().unwrap();
+
+    let inner = s.into_inner();
+    assert_eq!(inner.items, vec![vec![1], vec![2]]);
+    assert_eq!(inner.polls, 4);
+    assert_eq!(inner.flushes, 1);
+}
+
+#[test]
+fn round_trip_through_both_adapters() {
+    let mut s = BlockingSpout::new(AsyncAdapter::new(CollectSpout::new()));
+    s.send_all(0..4).unwrap();
+    assert_eq!(s.inner().inner().items(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn async_batch_spout_forwards_on_next_ready() {
+    let mut s = AsyncBatchSpout::new(2, AsyncAdapter::new(CollectSpout::new()));
+    block_on(async {
+        for i in 1..=5 {
+            s.send_async(i).await.unwrap();
+        }
+    });
+    // The third batch is only buffered until the next ready or flush.
+    assert_eq!(s.inner().inner().items(), vec![vec![1, 2], vec![3, 4]]);
+    assert_eq!(s.buffered(), 1);
+
+    block_on(s.flush_async()).unwrap();
+    assert_eq!(
+        s.into_inner().into_inner().into_items(),
+        vec![vec![1, 2], vec![3, 4], vec![5]]
+    );
+}
+
+#[test]
+fn async_batch_spout_waits_for_inner_readiness() {
+    let mut s = AsyncBatchSpout::new(1, Sluggish::default());
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert_eq!(s.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    s.send(7).unwrap();
+    // Inner is not ready: the batch stays buffered and the producer waits.
+    assert_eq!(s.poll_ready(&mut cx), Poll::Pending);
+    assert_eq!(s.buffered(), 1);
+    assert_eq!(s.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(s.inner().items, vec![vec![7]]);
+}
+
+#[test]
+#[should_panic(expected = "AsyncBatchSpout threshold must be at least 1")]
+fn async_batch_spout_rejects_zero_threshold() {
+    let _: AsyncBatchSpout<i32, Sluggish> = AsyncBatchSpout::new(0, Sluggish::default());
+}
+
+#[cfg(feature = "std")]
+mod channel {
+    use super::*;
+
+    use crate::{ChannelClosed, Source, async_channel};
+
+    #[test]
+    fn async_channel_applies_backpressure() {
+        let (mut tx, mut rx) = async_channel(2);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        for i in 0..2 {
+            assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+            tx.send(i).unwrap();
+        }
+        assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+        assert_eq!(tx.poll_flush(&mut cx), Poll::Pending);
+
+        assert_eq!(rx.try_recv(), Ok(0));
+        assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(tx.poll_flush(&mut cx), Poll::Ready(Ok(())));
+    }
+
+    #[test]
+    fn async_channel_across_threads() {
+        let (tx, mut rx) = async_channel(4);
+        let producers: Vec<_> = (0..3)
+            .map(|p| {
+                let mut tx = tx.clone();
+                std::thread::spawn(move || {
+                    block_on(async {
+                        for i in 0..100 {
+                            tx.send_async(p * 1000 + i).await.unwrap();
+                        }
+                        tx.flush_async().await.unwrap();
+                    })
+                })
+            })
+            .collect();
+        drop(tx);
+
+        let mut received = block_on(async {
+            let mut received = Vec::new();
+            while let Some(item) = rx.recv().await {
+                received.push(item);
+            }
+            received
+        });
+        for p in producers {
+            p.join().unwrap();
+        }
+
+        received.sort_unstable();
+        let mut expected: Vec<i32> = (0..3)
+            .flat_map(|p| (0..100).map(move |i| p * 1000 + i))
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn async_channel_reports_closed_receiver() {
+        let (mut tx, rx) = async_channel::<i32>(1);
+        drop(rx);
+        assert_eq!(block_on(tx.send_async(1)), Err(ChannelClosed));
+    }
+
+    #[test]
+    fn async_receiver_is_a_source() {
+        let (tx, mut rx) = async_channel(8);
+        let mut tx = BlockingSpout::new(tx);
+        tx.send_all(0..3).unwrap();
+        drop(tx);
+
+        let mut out = CollectSpout::new();
+        let n = crate::pump(&mut rx, &mut out, 2).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(out.into_items(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn batches_into_async_channel() {
+        let (tx, mut rx) = async_channel(1);
+        let consumer = std::thread::spawn(move || {
+            let mut batches = Vec::new();
+            while let Ok(Some(batch)) = Source::recv(&mut rx) {
+                batches.push(batch);
+            }
+            batches
+        });
+
+        let mut s = BlockingSpout::new(AsyncBatchSpout::new(3, tx));
+        s.send_all(0..7).unwrap();
+        s.flush().unwrap();
+        drop(s);
+
+        assert_eq!(
+            consumer.join().unwrap(),
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+extern crate std;
+
+use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+use std::thread;
+use std::time::Duration;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{BoundedSendError, OverflowPolicy, Spout, bounded};
+
+#[test]
+fn bounded_fifo_order() {
+    let (mut tx, rx) = bounded(4, OverflowPolicy::Error);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    tx.send(3).unwrap();
+
+    assert_eq!(rx.len(), 3);
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok(3));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn bounded_error_policy_returns_item() {
+    let (mut tx, rx) = bounded(2, OverflowPolicy::Error);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+
+    assert_eq!(tx.send(3), Err(BoundedSendError::Full(3)));
+    assert_eq!(rx.dropped(), 0);
+    assert_eq!(rx.try_recv(), Ok(1));
+    tx.send(3).unwrap();
+}
+
+#[test]
+fn bounded_drop_newest_counts_drops() {
+    let (mut tx, rx) = bounded(2, OverflowPolicy::DropNewest);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+
+    assert_eq!(tx.dropped_newest(), 3);
+    assert_eq!(rx.dropped(), 3);
+    assert_eq!(rx.try_recv(), Ok(0));
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn bounded_drop_oldest_counts_drops() {
+    let (mut tx, rx) = bounded(2, OverflowPolicy::DropOldest);
+    tx.send_all(0..5).unwrap();
+
+    assert_eq!(rx.dropped_oldest(), 3);
+    assert_eq!(rx.dropped_newest(), 0);
+    assert_eq!(rx.try_recv(), Ok(3));
+    assert_eq!(rx.try_recv(), Ok(4));
+}
+
+#[test]
+fn bounded_block_waits_for_receiver() {
+    let (mut tx, rx) = bounded(1, OverflowPolicy::Block);
+    tx.send(1).unwrap();
+
+    let handle = thread::spawn(move || {
+        tx.send(2).unwrap(); // Blocks until 1 is received
+        tx.send(3).unwrap();
+    });
+
+    let received: Vec<_> = (0..3).map(|_| rx.recv().unwrap()).collect();
+    handle.join().unwrap();
+    assert_eq!(received, vec![1, 2, 3]);
+    assert_eq!(rx.dropped(), 0);
+}
+
+#[test]
+fn bounded_block_timeout_expires() {
+    let (mut tx, rx) = bounded(1, OverflowPolicy::BlockTimeout(Duration::from_millis(20)));
+    tx.send(1).unwrap();
+
+    assert_eq!(tx.send(2), Err(BoundedSendError::Timeout(2)));
+    assert_eq!(rx.try_recv(), Ok(1));
+    tx.send(2).unwrap();
+}
+
+#[test]
+fn bounded_send_fails_after_receiver_dropped() {
+    let (mut tx, rx) = bounded(1, OverflowPolicy::Block);
+    tx.send(1).unwrap();
+
+    let handle = thread::spawn(move || tx.send(2));
+    thread::sleep(Duration::from_millis(10));
+    drop(rx);
+
+    let err = handle.join().unwrap().unwrap_err();
+    assert_eq!(err, BoundedSendError::Disconnected(2));
+    assert_eq!(err.into_inner(), 2);
+}
+
+#[test]
+fn bounded_recv_disconnects_after_last_sender() {
+    let (mut tx, rx) = bounded(4, OverflowPolicy::Error);
+    let mut tx2 = tx.clone();
+    tx.send(1).unwrap();
+    tx2.send(2).unwrap();
+    drop(tx);
+    drop(tx2);
+
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.recv(), Ok(2));
+    assert_eq!(rx.recv(), Err(RecvError));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(1)),
+        Err(RecvTimeoutError::Disconnected)
+    );
+}
+
+#[test]
+fn bounded_recv_timeout_expires() {
+    let (_tx, rx) = bounded::<i32>(1, OverflowPolicy::Block);
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(5)),
+        Err(RecvTimeoutError::Timeout)
+    );
+}
+
+#[test]
+fn bounded_many_producers() {
+    let (tx, rx) = bounded(8, OverflowPolicy::Block);
+
+    thread::scope(|s| {
+        for p in 0..4 {
+            let mut tx = tx.clone();
+            s.spawn(move || {
+                for i in 0..100 {
+                    tx.send(p * 1000 + i).unwrap();
+                }
+            });
+        }
+        drop(tx);
+
+        let mut received = Vec::new();
+        while let Ok(item) = rx.recv() {
+            received.push(item);
+        }
+        assert_eq!(received.len(), 400);
+    });
+}
+
+#[test]
+#[should_panic(expected = "bounded channel capacity must be at least 1")]
+fn bounded_rejects_zero_capacity() {
+    let _ = bounded::<i32>(0, OverflowPolicy::Block);
+}
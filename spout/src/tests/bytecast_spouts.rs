@@ -5,7 +5,11 @@ use std::vec::Vec;
 
 use bytecast::{FromBytes, ToBytesExt};
 
-use crate::{BatchSpout, CollectSpout, FramedSpout, Spout, decode_frame};
+use crate::{
+    BatchSpout, CollectSpout, DecodeSource, DecodeSourceError, FRAME_MAGIC, FrameDecoder,
+    FrameError, FramedSpout, FramedSpoutError, FramedSpoutV2, SequenceCheck, Spout, decode_frame,
+    decode_frame_v2,
+};
 
 // --- FramedSpout tests ---
 
@@ -84,6 +88,274 @@ fn framed_spout_into_inner() {
     assert_eq!(inner.items().len(), 1);
 }
 
+/// Accepts `limit` frames, then rejects the rest.
+struct Limited {
+    limit: usize,
+    frames: Vec<Vec<u8>>,
+}
+
+impl Spout<Vec<u8>> for Limited {
+    type Error = &'static str;
+
+    fn send(&mut self, item: Vec<u8>) -> Result<(), Self::Error> {
+        if self.frames.len() >= self.limit {
+            return Err("full");
+        }
+        self.frames.push(item);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Err("flush failed")
+    }
+}
+
+#[test]
+fn framed_spout_propagates_inner_errors() {
+    let mut s = FramedSpout::new(
+        0,
+        Limited {
+            limit: 1,
+            frames: Vec::new(),
+        },
+    );
+
+    assert_eq!(s.send(1u32), Ok(()));
+    assert_eq!(s.send(2u32), Err(FramedSpoutError::Spout("full")));
+    assert_eq!(
+        <FramedSpout<Limited> as Spout<u32>>::flush(&mut s),
+        Err(FramedSpoutError::Spout("flush failed"))
+    );
+    assert_eq!(s.inner().frames.len(), 1);
+}
+
+// --- FramedSpoutV2 tests ---
+
+#[test]
+fn crc32_matches_reference_vector() {
+    assert_eq!(crate::impls::crc32::crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crate::impls::crc32::crc32(b""), 0);
+}
+
+#[test]
+fn framed_v2_round_trips_with_sequence() {
+    let mut s = FramedSpoutV2::new(3, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(10u32);
+    let _ = s.send(20u32);
+    assert_eq!(s.next_sequence(), 2);
+
+    let frames = s.inner().items();
+    assert_eq!(frames[0][0], FRAME_MAGIC);
+
+    let f0 = decode_frame_v2::<u32>(&frames[0]).unwrap();
+    let f1 = decode_frame_v2::<u32>(&frames[1]).unwrap();
+    assert_eq!(
+        (f0.producer_id, f0.sequence, f0.timestamp, f0.item),
+        (3, 0, None, 10)
+    );
+    assert_eq!(
+        (f1.producer_id, f1.sequence, f1.timestamp, f1.item),
+        (3, 1, None, 20)
+    );
+}
+
+#[test]
+fn framed_v2_writes_timestamp_from_clock() {
+    let tick = core::cell::Cell::new(100u64);
+    let clock = || {
+        let t = tick.get();
+        tick.set(t + 5);
+        t
+    };
+    let mut s = FramedSpoutV2::new(0, CollectSpout::<Vec<u8>>::new()).with_clock(clock);
+    let _ = s.send(1u8);
+    let _ = s.send(2u8);
+
+    let frames = s.inner().items();
+    assert_eq!(
+        decode_frame_v2::<u8>(&frames[0]).unwrap().timestamp,
+        Some(100)
+    );
+    assert_eq!(
+        decode_frame_v2::<u8>(&frames[1]).unwrap().timestamp,
+        Some(105)
+    );
+    // Timestamped frames carry 8 extra header bytes
+    assert_eq!(frames[0].len(), 23 + 8 + 1 + 4);
+}
+
+#[test]
+fn framed_v2_variable_length_payload() {
+    let mut s = FramedSpoutV2::new(1, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(vec![1u16, 2, 3]);
+
+    let frame = decode_frame_v2::<Vec<u16>>(&s.inner().items()[0]).unwrap();
+    assert_eq!(frame.item, vec![1, 2, 3]);
+}
+
+#[test]
+fn framed_v2_detects_flipped_bit() {
+    let mut s = FramedSpoutV2::new(0, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(0xDEAD_BEEFu32);
+
+    let mut frame = s.into_inner().into_items().remove(0);
+    let payload_byte = 23;
+    frame[payload_byte] ^= 0x01;
+    assert!(matches!(
+        decode_frame_v2::<u32>(&frame),
+        Err(FrameError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn framed_v2_rejects_bad_header() {
+    let mut s = FramedSpoutV2::new(0, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(1u32);
+    let frame = s.into_inner().into_items().remove(0);
+
+    let mut bad_magic = frame.clone();
+    bad_magic[0] = 0x00;
+    assert_eq!(
+        decode_frame_v2::<u32>(&bad_magic),
+        Err(FrameError::BadMagic(0x00))
+    );
+
+    let mut bad_version = frame.clone();
+    bad_version[1] = 9;
+    assert_eq!(
+        decode_frame_v2::<u32>(&bad_version),
+        Err(FrameError::UnsupportedVersion(9))
+    );
+
+    let mut bad_flags = frame.clone();
+    bad_flags[2] = 0x80;
+    assert_eq!(
+        decode_frame_v2::<u32>(&bad_flags),
+        Err(FrameError::UnknownFlags(0x80))
+    );
+
+    assert!(matches!(
+        decode_frame_v2::<u32>(&frame[..frame.len() - 1]),
+        Err(FrameError::Bytes(_))
+    ));
+}
+
+#[test]
+fn framed_v2_inner_failure_leaves_gap() {
+    let mut s = FramedSpoutV2::new(
+        0,
+        Limited {
+            limit: 1,
+            frames: Vec::new(),
+        },
+    );
+    assert!(s.send(1u32).is_ok());
+    assert_eq!(s.send(2u32), Err(FramedSpoutError::Spout("full")));
+    assert_eq!(s.next_sequence(), 2);
+}
+
+#[test]
+fn frame_decoder_reports_gaps_and_duplicates() {
+    let mut a = FramedSpoutV2::new(1, CollectSpout::<Vec<u8>>::new());
+    let mut b = FramedSpoutV2::new(2, CollectSpout::<Vec<u8>>::new());
+    for i in 0..4u32 {
+        let _ = a.send(i);
+        let _ = b.send(i + 100);
+    }
+    let a = a.into_inner().into_items();
+    let b = b.into_inner().into_items();
+
+    let mut decoder = FrameDecoder::new();
+    let check = |d: &mut FrameDecoder, f: &[u8]| d.decode::<u32>(f).unwrap().1;
+
+    assert_eq!(check(&mut decoder, &a[0]), SequenceCheck::First);
+    assert_eq!(check(&mut decoder, &b[0]), SequenceCheck::First);
+    assert_eq!(check(&mut decoder, &a[1]), SequenceCheck::InOrder);
+    // a[2] lost in transit
+    assert_eq!(
+        check(&mut decoder, &a[3]),
+        SequenceCheck::Gap {
+            expected: 2,
+            missing: 1
+        }
+    );
+    assert_eq!(check(&mut decoder, &b[1]), SequenceCheck::InOrder);
+    assert_eq!(
+        check(&mut decoder, &b[1]),
+        SequenceCheck::Duplicate { last: 1 }
+    );
+
+    assert_eq!(decoder.gaps(), 1);
+    assert_eq!(decoder.missing(), 1);
+    assert_eq!(decoder.duplicates(), 1);
+    assert_eq!(decoder.last_sequence(1), Some(3));
+    assert_eq!(decoder.last_sequence(2), Some(1));
+
+    decoder.reset();
+    assert_eq!(decoder.last_sequence(1), None);
+    assert_eq!(decoder.gaps(), 0);
+}
+
+#[test]
+fn frame_decoder_ignores_corrupt_frames() {
+    let mut s = FramedSpoutV2::new(0, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(1u32);
+    let _ = s.send(2u32);
+    let frames = s.into_inner().into_items();
+
+    let mut corrupt = frames[1].clone();
+    corrupt[23] ^= 0xFF;
+
+    let mut decoder = FrameDecoder::new();
+    decoder.decode::<u32>(&frames[0]).unwrap();
+    assert!(decoder.decode::<u32>(&corrupt).is_err());
+    assert_eq!(decoder.last_sequence(0), Some(0));
+    let (_, check) = decoder.decode::<u32>(&frames[1]).unwrap();
+    assert_eq!(check, SequenceCheck::InOrder);
+}
+
+// --- DecodeSource tests ---
+
+#[test]
+fn decode_source_pumps_decoded_frames() {
+    use crate::{IterSource, pump};
+
+    let mut s = FramedSpoutV2::new(4, CollectSpout::<Vec<u8>>::new());
+    for i in 0..5u32 {
+        let _ = s.send(i);
+    }
+    let frames = s.into_inner().into_items();
+
+    let mut source = DecodeSource::<_, u32>::new(IterSource(frames.into_iter()));
+    let mut sink = CollectSpout::new();
+    let moved = pump(&mut source, &mut sink, 2).unwrap();
+
+    assert_eq!(moved, 5);
+    let items: Vec<_> = sink.items().iter().map(|f| (f.sequence, f.item)).collect();
+    assert_eq!(items, vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+    assert_eq!(source.decoder().last_sequence(4), Some(4));
+    assert_eq!(source.decoder().gaps(), 0);
+}
+
+#[test]
+fn decode_source_reports_corrupt_frame_and_continues() {
+    use crate::{IterSource, Source};
+
+    let mut s = FramedSpoutV2::new(0, CollectSpout::<Vec<u8>>::new());
+    let _ = s.send(1u32);
+    let _ = s.send(2u32);
+    let mut frames = s.into_inner().into_items();
+    frames[0][0] = 0;
+
+    let mut source = DecodeSource::<_, u32>::new(IterSource(frames.into_iter()));
+    assert_eq!(
+        source.recv(),
+        Err(DecodeSourceError::Frame(FrameError::BadMagic(0)))
+    );
+    assert_eq!(source.recv().unwrap().unwrap().item, 2);
+    assert_eq!(source.recv(), Ok(None));
+}
+
 // --- BatchSpout ToBytes tests ---
 
 #[test]
@@ -0,0 +1,105 @@
+extern crate std;
+
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, DeadLetter, DeadLetterSpout, ErrorCaptureSpout, Spout};
+
+/// Rejects odd items and fails every flush.
+#[derive(Default)]
+struct EvenOnly {
+    accepted: Vec<i32>,
+}
+
+impl Spout<i32> for EvenOnly {
+    type Error = &'static str;
+
+    fn send(&mut self, item: i32) -> Result<(), Self::Error> {
+        if item % 2 == 0 {
+            self.accepted.push(item);
+            Ok(())
+        } else {
+            Err("odd")
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Err("flush failed")
+    }
+}
+
+#[test]
+fn dead_letter_routes_failed_items() {
+    let mut s = DeadLetterSpout::new(EvenOnly::default(), CollectSpout::new());
+    for i in 0..5 {
+        s.send(i).unwrap();
+    }
+
+    assert_eq!(s.inner().accepted, vec![0, 2, 4]);
+    assert_eq!(s.failures(), 2);
+    assert_eq!(
+        s.dead_letters().items(),
+        vec![
+            DeadLetter {
+                item: Some(1),
+                error: "odd"
+            },
+            DeadLetter {
+                item: Some(3),
+                error: "odd"
+            },
+        ]
+    );
+}
+
+#[test]
+fn dead_letter_routes_flush_failures_without_item() {
+    let mut s = DeadLetterSpout::new(EvenOnly::default(), CollectSpout::new());
+    s.flush().unwrap();
+
+    assert_eq!(s.failures(), 1);
+    let (_, dead) = s.into_parts();
+    assert_eq!(
+        dead.into_items(),
+        vec![DeadLetter {
+            item: None,
+            error: "flush failed"
+        }]
+    );
+}
+
+#[test]
+fn dead_letter_send_all_routes_each_failure() {
+    let mut s = DeadLetterSpout::new(EvenOnly::default(), CollectSpout::new());
+    s.send_all([1, 2, 3, 4].into_iter()).unwrap();
+
+    assert_eq!(s.inner().accepted, vec![2, 4]);
+    assert_eq!(s.failures(), 2);
+}
+
+#[test]
+fn error_capture_keeps_errors_only() {
+    let mut s = ErrorCaptureSpout::new(EvenOnly::default(), CollectSpout::new());
+    for i in 0..4 {
+        s.send(i).unwrap();
+    }
+    s.flush().unwrap();
+
+    assert_eq!(s.failures(), 3);
+    assert_eq!(s.errors().items(), vec!["odd", "odd", "flush failed"]);
+}
+
+#[test]
+fn error_capture_passes_through_successes() {
+    let mut s = ErrorCaptureSpout::new(
+        CollectSpout::new(),
+        CollectSpout::<core::convert::Infallible>::new(),
+    );
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+
+    assert_eq!(s.failures(), 0);
+    let (inner, errors) = s.into_parts();
+    assert_eq!(inner.into_items(), vec![1, 2]);
+    assert!(errors.into_items().is_empty());
+}
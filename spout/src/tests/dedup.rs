@@ -0,0 +1,199 @@
+extern crate std;
+
+use std::vec;
+use std::vec::Vec;
+
+use crate::{
+    BloomFilter, CollectSpout, Dedup, DedupFilter, DedupStats, DropSpout, LruFilter, Spout,
+};
+
+// --- LruFilter tests ---
+
+#[test]
+fn lru_filter_forgets_least_recent() {
+    let mut f = LruFilter::new(2);
+    assert!(!f.check_insert(1));
+    assert!(!f.check_insert(2));
+    assert!(f.check_insert(1)); // refreshes 1
+    assert!(!f.check_insert(3)); // evicts 2
+    assert_eq!(f.len(), 2);
+
+    assert!(f.check_insert(1));
+    assert!(!f.check_insert(2));
+}
+
+#[test]
+#[should_panic(expected = "LruFilter capacity must be at least 1")]
+fn lru_filter_rejects_zero_capacity() {
+    let _ = LruFilter::<u32>::new(0);
+}
+
+// --- BloomFilter tests ---
+
+#[test]
+fn bloom_filter_sizing() {
+    let f = BloomFilter::new(1000, 0.01);
+    // log2(100) rounds up to 7 hashes; ~7 * 1.44 bits per key.
+    assert_eq!(f.hashes(), 7);
+    assert!(
+        f.bits() >= 10_000 && f.bits() < 10_200,
+        "bits = {}",
+        f.bits()
+    );
+    assert_eq!(f.capacity(), 1000);
+}
+
+#[test]
+fn bloom_filter_never_misses_recent_keys() {
+    let mut f = BloomFilter::new(1000, 0.01);
+    for k in 0..1000u32 {
+        f.check_insert(k);
+    }
+    for k in 0..1000u32 {
+        assert!(f.check_insert(k), "key {k} missed");
+    }
+}
+
+#[test]
+fn bloom_filter_false_positive_rate_near_target() {
+    let mut f = BloomFilter::new(1000, 0.01);
+    for k in 0..1000u32 {
+        f.check_insert(k);
+    }
+    let estimate = f.estimated_fp_rate();
+    assert!(estimate < 0.02, "estimate = {estimate}");
+
+    let false_positives = (1_000_000..1_010_000u32)
+        .filter(|k| {
+            let mut probe = f.clone();
+            probe.check_insert(*k)
+        })
+        .count();
+    // Target 1% of 10k probes; allow generous slack.
+    assert!(false_positives < 250, "false positives = {false_positives}");
+}
+
+#[test]
+fn bloom_filter_rotates_generations() {
+    let mut f = BloomFilter::new(10, 0.001);
+    for k in 0..10u32 {
+        f.check_insert(k);
+    }
+    // Next 10 keys fill a fresh generation; the first 10 stay in the previous one.
+    for k in 100..110u32 {
+        f.check_insert(k);
+    }
+    assert!(f.check_insert(5u32));
+    // Another rotation: the first batch is gone (barring false positives).
+    for k in 200..210u32 {
+        f.check_insert(k);
+    }
+    let remembered = (0..10u32)
+        .filter(|&k| k != 5 && f.clone().check_insert(k))
+        .count();
+    assert!(remembered <= 1, "remembered = {remembered}");
+}
+
+#[test]
+#[should_panic(expected = "BloomFilter false-positive rate must be in (0, 1)")]
+fn bloom_filter_rejects_bad_rate() {
+    let _ = BloomFilter::new(10, 1.0);
+}
+
+// --- Dedup tests ---
+
+#[test]
+fn dedup_exact_drops_repeats() {
+    let mut s = Dedup::exact(8, |x: &(u32, &str)| x.0, CollectSpout::new());
+    s.send((1, "a")).unwrap();
+    s.send((2, "b")).unwrap();
+    s.send((1, "retry")).unwrap();
+    s.send((3, "c")).unwrap();
+
+    assert_eq!(s.inner().items(), vec![(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(
+        s.stats(),
+        DedupStats {
+            seen: 4,
+            forwarded: 3,
+            duplicates: 1,
+        }
+    );
+}
+
+#[test]
+fn dedup_exact_window_is_bounded() {
+    let mut s = Dedup::exact(2, |x: &i32| *x, CollectSpout::new());
+    s.send_all([1, 2, 3, 1].into_iter()).unwrap();
+
+    // 1 fell out of the window before it was repeated.
+    assert_eq!(s.inner().items(), vec![1, 2, 3, 1]);
+    assert_eq!(s.filter().len(), 2);
+}
+
+#[test]
+fn dedup_bloom_drops_repeats() {
+    let mut s = Dedup::bloom(100, 0.001, |x: &u64| *x, DropSpout);
+    for round in 0..3 {
+        for k in 0..50u64 {
+            s.send(k).unwrap();
+        }
+        assert_eq!(s.stats().duplicates, 50 * round);
+    }
+    assert!(s.stats().forwarded >= 50);
+}
+
+/// Fails the first send, then collects.
+#[derive(Default)]
+struct FailOnce {
+    failed: bool,
+    items: Vec<u32>,
+}
+
+impl Spout<u32> for FailOnce {
+    type Error = ();
+
+    fn send(&mut self, item: u32) -> Result<(), ()> {
+        if !self.failed {
+            self.failed = true;
+            return Err(());
+        }
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+#[test]
+fn dedup_retry_after_failed_send_is_delivered() {
+    let mut exact = Dedup::exact(8, |x: &u32| *x, FailOnce::default());
+    assert_eq!(exact.send(7), Err(()));
+    exact.send(7).unwrap();
+    exact.send(7).unwrap();
+    assert_eq!(exact.inner().items, [7]);
+    assert_eq!(
+        exact.stats(),
+        DedupStats {
+            seen: 3,
+            forwarded: 1,
+            duplicates: 1,
+        }
+    );
+
+    let mut bloom = Dedup::bloom(8, 0.01, |x: &u32| *x, FailOnce::default());
+    assert_eq!(bloom.send(7), Err(()));
+    bloom.send(7).unwrap();
+    bloom.send(7).unwrap();
+    assert_eq!(bloom.inner().items, [7]);
+    assert_eq!(bloom.stats().forwarded, 1);
+}
+
+#[test]
+fn dedup_reset_forgets_keys() {
+    let mut s = Dedup::exact(8, |x: &i32| *x, CollectSpout::new());
+    s.send(1).unwrap();
+    s.reset();
+    s.send(1).unwrap();
+
+    assert_eq!(s.inner().items(), vec![1, 1]);
+    assert_eq!(s.stats().seen, 1);
+}
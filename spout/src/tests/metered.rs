@@ -0,0 +1,158 @@
+extern crate std;
+
+use core::cell::Cell;
+use std::sync::Arc;
+use std::vec;
+
+use crate::{CollectSpout, LATENCY_BUCKETS, Metered, MetricsSnapshot, Spout, SpoutMetrics};
+
+/// Rejects odd items and fails every flush.
+struct EvenOnly;
+
+impl Spout<i32> for EvenOnly {
+    type Error = &'static str;
+
+    fn send(&mut self, item: i32) -> Result<(), Self::Error> {
+        if item % 2 == 0 { Ok(()) } else { Err("odd") }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Err("flush failed")
+    }
+}
+
+#[test]
+fn metered_counts_sends_and_flushes() {
+    let mut s = Metered::new(CollectSpout::new());
+    s.send_all(0..5).unwrap();
+    s.flush().unwrap();
+
+    let snap = s.metrics().snapshot();
+    assert_eq!(snap.sends, 5);
+    assert_eq!(snap.send_errors, 0);
+    assert_eq!(snap.flushes, 1);
+    assert_eq!(snap.flush_errors, 0);
+    assert_eq!(snap.bytes, 0);
+    assert_eq!(snap.latency_samples(), 0);
+    assert_eq!(s.into_inner().into_items(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn metered_counts_errors() {
+    let mut s = Metered::new(EvenOnly);
+    for i in 0..5 {
+        let _ = s.send(i);
+    }
+    assert_eq!(s.flush(), Err("flush failed"));
+
+    let snap = s.metrics().snapshot();
+    assert_eq!(snap.sends, 5);
+    assert_eq!(snap.send_errors, 2);
+    assert_eq!(snap.flushes, 1);
+    assert_eq!(snap.flush_errors, 1);
+}
+
+#[test]
+fn metered_records_latency_buckets() {
+    // Each inner send advances the clock by the item value.
+    let now = Cell::new(0u64);
+    let mut s = Metered::new(crate::FnSpout(|ticks: u64| now.set(now.get() + ticks)))
+        .with_clock(|| now.get());
+
+    for ticks in [0, 1, 2, 3, 4, 1000] {
+        s.send(ticks).unwrap();
+    }
+
+    let snap = s.metrics().snapshot();
+    assert_eq!(snap.latency_samples(), 6);
+    assert_eq!(snap.latency[0], 2); // 0, 1
+    assert_eq!(snap.latency[1], 2); // 2, 3
+    assert_eq!(snap.latency[2], 1); // 4
+    assert_eq!(snap.latency[9], 1); // 1000 in [512, 1024)
+    assert_eq!(snap.latency_quantile(0.5), Some(4));
+    assert_eq!(snap.latency_quantile(1.0), Some(1024));
+}
+
+#[test]
+fn metered_latency_top_bucket_is_open_ended() {
+    let now = Cell::new(0u64);
+    let mut s = Metered::new(crate::FnSpout(|ticks: u64| now.set(now.get() + ticks)))
+        .with_clock(|| now.get());
+    s.send(u64::MAX / 2).unwrap();
+
+    let snap = s.metrics().snapshot();
+    assert_eq!(snap.latency[LATENCY_BUCKETS - 1], 1);
+    assert_eq!(
+        MetricsSnapshot::bucket_bounds(LATENCY_BUCKETS - 1).1,
+        u64::MAX
+    );
+    assert_eq!(MetricsSnapshot::bucket_bounds(0), (0, 2));
+    assert_eq!(MetricsSnapshot::bucket_bounds(3), (8, 16));
+}
+
+#[test]
+fn metered_reset_zeroes_counters() {
+    let now = Cell::new(0u64);
+    let mut s = Metered::new(CollectSpout::new()).with_clock(|| now.get());
+    s.send_all(0..3).unwrap();
+    s.flush().unwrap();
+
+    s.metrics().reset();
+    let snap = s.metrics().snapshot();
+    assert_eq!(snap.sends, 0);
+    assert_eq!(snap.flushes, 0);
+    assert_eq!(snap.latency_samples(), 0);
+    assert_eq!(snap.latency_quantile(0.5), None);
+}
+
+#[test]
+fn metered_clones_share_metrics() {
+    let shared = Arc::new(SpoutMetrics::new());
+    let mut a = Metered::with_metrics(CollectSpout::new(), Arc::clone(&shared));
+    let mut b = a.clone();
+    a.send(1).unwrap();
+    b.send(2).unwrap();
+    b.send(3).unwrap();
+
+    assert_eq!(shared.snapshot().sends, 3);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn metered_snapshot_from_another_thread() {
+    use std::sync::mpsc;
+
+    use crate::{ChannelSpout, MonotonicClock};
+
+    let (tx, rx) = mpsc::channel();
+    let s = Metered::new(ChannelSpout::new(tx)).with_clock(MonotonicClock::new());
+    let metrics = Arc::clone(s.metrics());
+
+    let handle = std::thread::spawn(move || {
+        let mut s = s;
+        for i in 0..100 {
+            s.send(i).unwrap();
+        }
+    });
+    handle.join().unwrap();
+
+    let snap = metrics.snapshot();
+    assert_eq!(snap.sends, 100);
+    assert_eq!(snap.latency_samples(), 100);
+    assert_eq!(rx.iter().count(), 100);
+}
+
+#[cfg(feature = "bytecast")]
+#[test]
+fn metered_counts_bytes_via_byte_len() {
+    use crate::ByteLen;
+
+    let mut s = Metered::new(CollectSpout::new()).with_byte_count(ByteLen);
+    s.send(1u32).unwrap();
+    s.send(2u32).unwrap();
+    assert_eq!(s.metrics().snapshot().bytes, 8);
+
+    let mut s = Metered::new(EvenOnly).with_byte_count(ByteLen);
+    let _ = s.send(1);
+    assert_eq!(s.metrics().snapshot().bytes, 0);
+}
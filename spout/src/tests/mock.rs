@@ -0,0 +1,131 @@
+extern crate std;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, DeadLetterSpout, MockCall, MockError, MockSpout, Spout};
+
+#[test]
+fn mock_accepts_everything_by_default() {
+    let mut s = MockSpout::new();
+    s.send(1).unwrap();
+    s.send_all([2, 3].into_iter()).unwrap();
+    s.flush().unwrap();
+
+    assert_eq!(
+        s.calls(),
+        &[
+            MockCall::Send { item: 1, ok: true },
+            MockCall::SendAll {
+                items: vec![2, 3],
+                ok: true
+            },
+            MockCall::Flush { ok: true },
+        ]
+    );
+    assert_eq!(s.accepted().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(s.sends(), 3);
+    assert_eq!(s.flushes(), 1);
+}
+
+#[test]
+fn mock_fails_on_nth_send() {
+    let mut s = MockSpout::new().fail_on(2).fail_on(4);
+    let results: Vec<_> = (1..=5).map(|i| s.send(i)).collect();
+
+    assert_eq!(
+        results,
+        vec![
+            Ok(()),
+            Err(MockError::Send(2)),
+            Ok(()),
+            Err(MockError::Send(4)),
+            Ok(())
+        ]
+    );
+    assert_eq!(s.accepted().copied().collect::<Vec<_>>(), vec![1, 3, 5]);
+}
+
+#[test]
+fn mock_fails_every_k_sends() {
+    let mut s = MockSpout::new().fail_every(3);
+    let failures = (0..9).filter(|&i| s.send(i).is_err()).count();
+    assert_eq!(failures, 3);
+}
+
+#[test]
+fn mock_fails_while_flag_set() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut s = MockSpout::new().fail_while(Arc::clone(&flag));
+
+    s.send(1).unwrap();
+    flag.store(true, Ordering::Release);
+    assert!(s.send(2).is_err());
+    assert!(s.send(3).is_err());
+    flag.store(false, Ordering::Release);
+    s.send(4).unwrap();
+
+    assert_eq!(s.accepted().copied().collect::<Vec<_>>(), vec![1, 4]);
+}
+
+#[test]
+fn mock_send_all_stops_at_failure() {
+    let mut s = MockSpout::new().fail_on(3);
+    assert_eq!(s.send_all(1..=5), Err(MockError::Send(3)));
+
+    assert_eq!(
+        s.calls(),
+        &[MockCall::SendAll {
+            items: vec![1, 2, 3],
+            ok: false
+        }]
+    );
+    assert_eq!(s.accepted().copied().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn mock_fails_flush() {
+    let mut s = MockSpout::<i32>::new().fail_flush_on(2);
+    assert_eq!(s.flush(), Ok(()));
+    assert_eq!(s.flush(), Err(MockError::Flush(2)));
+    assert_eq!(s.flush(), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "MockSpout panic on send 2")]
+fn mock_panics_on_nth_send() {
+    let mut s = MockSpout::new().panic_on(2);
+    let _ = s.send(1);
+    let _ = s.send(2);
+}
+
+#[test]
+fn mock_delays_each_call() {
+    let mut s = MockSpout::new().delay(Duration::from_millis(5));
+    let start = Instant::now();
+    s.send(1).unwrap();
+    s.flush().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[test]
+fn mock_clear_keeps_script() {
+    let mut s = MockSpout::new().fail_on(1);
+    assert!(s.send(1).is_err());
+    s.clear();
+    assert!(s.calls().is_empty());
+    assert!(s.send(2).is_err());
+}
+
+#[test]
+fn mock_drives_dead_letter_path() {
+    let mut s = DeadLetterSpout::new(MockSpout::new().fail_every(2), CollectSpout::new());
+    s.send_all(1..=4).unwrap();
+
+    assert_eq!(s.failures(), 2);
+    let dead: Vec<_> = s.dead_letters().items().iter().map(|d| d.item).collect();
+    assert_eq!(dead, vec![Some(2), Some(4)]);
+}
@@ -1,10 +1,30 @@
 extern crate std;
 
+mod async_spouts;
+mod dead_letter;
+mod dedup;
+mod metered;
 mod producer_spout;
+mod sample;
+mod sequence;
+mod source;
+mod window;
 
 #[cfg(feature = "std")]
 mod std_spouts;
 
+#[cfg(feature = "std")]
+mod bounded;
+
+#[cfg(feature = "std")]
+mod socket;
+
+#[cfg(feature = "testing")]
+mod mock;
+
+#[cfg(any(feature = "json", feature = "csv"))]
+mod text;
+
 #[cfg(feature = "bytecast")]
 mod bytecast_spouts;
 
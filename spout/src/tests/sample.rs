@@ -0,0 +1,204 @@
+extern crate std;
+
+use core::cell::Cell;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, EveryNth, ProbabilisticSample, RateLimit, Reservoir, Spout};
+
+// --- EveryNth tests ---
+
+#[test]
+fn every_nth_keeps_first_of_each_group() {
+    let mut s = EveryNth::new(3, CollectSpout::new());
+    for i in 0..10 {
+        s.send(i).unwrap();
+    }
+
+    assert_eq!(s.inner().items(), vec![0, 3, 6, 9]);
+    assert_eq!(s.dropped(), 6);
+}
+
+#[test]
+fn every_nth_of_one_keeps_everything() {
+    let mut s = EveryNth::new(1, CollectSpout::new());
+    s.send_all(0..5).unwrap();
+    assert_eq!(s.into_inner().into_items(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "EveryNth n must be at least 1")]
+fn every_nth_rejects_zero() {
+    let _ = EveryNth::new(0, CollectSpout::<i32>::new());
+}
+
+// --- ProbabilisticSample tests ---
+
+#[test]
+fn probabilistic_sample_is_reproducible_for_seed() {
+    let run = |seed| {
+        let mut s = ProbabilisticSample::new(0.5, seed, CollectSpout::new());
+        s.send_all(0..1000).unwrap();
+        s.into_inner().into_items()
+    };
+
+    assert_eq!(run(42), run(42));
+    assert_ne!(run(42), run(43));
+}
+
+#[test]
+fn probabilistic_sample_rate_is_close_to_probability() {
+    let mut s = ProbabilisticSample::new(0.1, 7, CollectSpout::new());
+    s.send_all(0..100_000).unwrap();
+
+    let kept = s.inner().items().len() as u64;
+    assert_eq!(kept + s.dropped(), 100_000);
+    assert!((9_000..11_000).contains(&kept), "kept {kept}");
+}
+
+#[test]
+fn probabilistic_sample_extremes() {
+    let mut none = ProbabilisticSample::new(0.0, 1, CollectSpout::new());
+    let mut all = ProbabilisticSample::new(1.0, 1, CollectSpout::new());
+    none.send_all(0..100).unwrap();
+    all.send_all(0..100).unwrap();
+
+    assert!(none.inner().items().is_empty());
+    assert_eq!(none.dropped(), 100);
+    assert_eq!(all.inner().items().len(), 100);
+    assert_eq!(all.dropped(), 0);
+}
+
+// --- Reservoir tests ---
+
+#[test]
+fn reservoir_emits_sample_on_flush() {
+    let mut s = Reservoir::<_, _, 4>::new(3, CollectSpout::new());
+    s.send_all(0..100).unwrap();
+
+    assert!(s.inner().items().is_empty());
+    assert_eq!(s.seen(), 100);
+    assert_eq!(s.sample().len(), 4);
+    assert_eq!(s.dropped(), 96);
+
+    s.flush().unwrap();
+    let sample = s.inner().items().to_vec();
+    assert_eq!(sample.len(), 4);
+    assert!(sample.iter().all(|x| (0..100).contains(x)));
+
+    // Reservoir starts over after flush
+    assert_eq!(s.seen(), 0);
+    assert!(s.sample().is_empty());
+}
+
+#[test]
+fn reservoir_smaller_input_emits_everything() {
+    let mut s = Reservoir::<_, _, 8>::new(0, CollectSpout::new());
+    s.send_all(0..3).unwrap();
+    s.flush().unwrap();
+
+    assert_eq!(s.into_inner().into_items(), vec![0, 1, 2]);
+}
+
+#[test]
+fn reservoir_keeps_unsent_sample_when_flush_fails() {
+    /// Accepts `budget` items, then fails.
+    struct Limited {
+        budget: usize,
+        items: Vec<u32>,
+    }
+
+    impl Spout<u32> for Limited {
+        type Error = ();
+
+        fn send(&mut self, item: u32) -> Result<(), ()> {
+            if self.budget == 0 {
+                return Err(());
+            }
+            self.budget -= 1;
+            self.items.push(item);
+            Ok(())
+        }
+    }
+
+    let inner = Limited {
+        budget: 1,
+        items: Vec::new(),
+    };
+    let mut s = Reservoir::<_, _, 8>::new(0, inner);
+    s.send_all(0..3).unwrap();
+
+    assert_eq!(s.flush(), Err(()));
+    assert_eq!(s.inner().items, [0]);
+    // Item 1 went to the failing send; item 2 was never handed over.
+    assert_eq!(s.sample(), [2]);
+    assert_eq!(s.seen(), 3);
+
+    s.inner_mut().budget = 1;
+    s.flush().unwrap();
+    assert_eq!(s.inner().items, [0, 2]);
+    assert!(s.sample().is_empty());
+    assert_eq!(s.seen(), 0);
+}
+
+#[test]
+fn reservoir_sample_is_roughly_uniform() {
+    // Each of 10 items should appear in a size-5 sample about half the time.
+    let mut hits = [0u32; 10];
+    let mut s = Reservoir::<_, _, 5>::new(11, CollectSpout::new());
+    for _ in 0..2000 {
+        s.send_all(0..10usize).unwrap();
+        s.flush().unwrap();
+        for &x in s.inner().items() {
+            hits[x] += 1;
+        }
+        s.inner_mut().take();
+    }
+
+    assert!(hits.iter().all(|&h| (800..1200).contains(&h)), "{hits:?}");
+}
+
+// --- RateLimit tests ---
+
+#[test]
+fn rate_limit_allows_burst_then_drops() {
+    let now = Cell::new(0u64);
+    let mut s = RateLimit::new(3, 10, || now.get(), CollectSpout::new());
+
+    s.send_all(0..5).unwrap();
+    assert_eq!(s.inner().items(), vec![0, 1, 2]);
+    assert_eq!(s.dropped(), 2);
+    assert_eq!(s.tokens(), 0);
+}
+
+#[test]
+fn rate_limit_refills_over_time() {
+    let now = Cell::new(0u64);
+    let mut s = RateLimit::new(2, 10, || now.get(), CollectSpout::new());
+
+    s.send_all(0..2).unwrap(); // Spend the burst
+
+    now.set(9);
+    s.send(2).unwrap(); // Not yet refilled
+    now.set(10);
+    s.send(3).unwrap(); // One token
+    now.set(25);
+    s.send(4).unwrap(); // One token (15 ticks, remainder carries)
+    now.set(30);
+    s.send(5).unwrap(); // Remainder completes another token
+
+    assert_eq!(s.inner().items(), vec![0, 1, 3, 4, 5]);
+    assert_eq!(s.dropped(), 1);
+}
+
+#[test]
+fn rate_limit_caps_tokens_at_burst() {
+    let now = Cell::new(0u64);
+    let mut s = RateLimit::new(2, 1, || now.get(), CollectSpout::new());
+
+    // A long idle period refills only up to the burst size
+    now.set(1_000);
+    s.send_all(0..5).unwrap();
+    assert_eq!(s.inner().items(), vec![0, 1]);
+    assert_eq!(s.dropped(), 3);
+}
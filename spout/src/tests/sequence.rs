@@ -0,0 +1,238 @@
+extern crate std;
+
+use core::cell::Cell;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, ReorderSpout, Sequenced, Spout, Stamped};
+
+fn stamp(producer_id: usize, sequence: u64, item: i32) -> Stamped<i32> {
+    Stamped {
+        producer_id,
+        sequence,
+        item,
+    }
+}
+
+fn items(s: &CollectSpout<Stamped<i32>>) -> Vec<i32> {
+    s.items().iter().map(|s| s.item).collect()
+}
+
+// --- Sequenced tests ---
+
+#[test]
+fn sequenced_stamps_monotonic_numbers() {
+    let mut s = Sequenced::new(7, CollectSpout::new());
+    s.send_all([10, 20, 30].into_iter()).unwrap();
+
+    assert_eq!(
+        s.into_inner().into_items(),
+        vec![stamp(7, 0, 10), stamp(7, 1, 20), stamp(7, 2, 30)]
+    );
+}
+
+#[test]
+fn sequenced_clones_share_counter() {
+    let mut a = Sequenced::new(0, CollectSpout::new());
+    let mut b = a.clone();
+    a.send(1).unwrap();
+    b.send(2).unwrap();
+    a.send(3).unwrap();
+
+    let seqs: Vec<u64> = a.inner().items().iter().map(|s| s.sequence).collect();
+    assert_eq!(seqs, vec![0, 2]);
+    assert_eq!(b.inner().items()[0].sequence, 1);
+}
+
+// --- ReorderSpout tests ---
+
+#[test]
+fn reorder_releases_in_sequence_order() {
+    let mut s = ReorderSpout::new(8, CollectSpout::new());
+    for seq in [2, 0, 3, 1, 5, 4] {
+        s.send(stamp(0, seq, seq as i32)).unwrap();
+    }
+
+    assert_eq!(items(s.inner()), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(s.buffered(), 0);
+    assert_eq!(s.expected(0), 6);
+    assert_eq!(s.skipped(), 0);
+}
+
+#[test]
+fn reorder_drops_late_and_duplicate_items() {
+    let mut s = ReorderSpout::new(8, CollectSpout::new());
+    s.send(stamp(0, 0, 0)).unwrap();
+    s.send(stamp(0, 2, 2)).unwrap();
+    s.send(stamp(0, 0, 99)).unwrap(); // already released
+    s.send(stamp(0, 2, 99)).unwrap(); // already buffered
+
+    assert_eq!(s.late(), 2);
+    assert_eq!(s.buffered(), 1);
+    assert_eq!(items(s.inner()), vec![0]);
+}
+
+#[test]
+fn reorder_skips_gap_when_full() {
+    let mut s = ReorderSpout::new(2, CollectSpout::new());
+    s.send(stamp(0, 0, 0)).unwrap();
+    // 1 and 2 never arrive.
+    s.send(stamp(0, 3, 3)).unwrap();
+    s.send(stamp(0, 4, 4)).unwrap();
+    assert_eq!(items(s.inner()), vec![0]);
+
+    s.send(stamp(0, 6, 6)).unwrap();
+    assert_eq!(items(s.inner()), vec![0, 3, 4]);
+    assert_eq!(s.skipped(), 2);
+    assert_eq!(s.expected(0), 5);
+    assert_eq!(s.buffered(), 1);
+}
+
+#[test]
+fn reorder_skips_gap_after_timeout() {
+    let now = Cell::new(0u64);
+    let mut s = ReorderSpout::new(100, CollectSpout::new()).with_timeout(10, || now.get());
+
+    s.send(stamp(0, 1, 1)).unwrap();
+    now.set(5);
+    s.send(stamp(0, 2, 2)).unwrap();
+    assert!(s.inner().items().is_empty());
+
+    now.set(10);
+    s.expire_gaps().unwrap();
+    assert_eq!(items(s.inner()), vec![1, 2]);
+    assert_eq!(s.skipped(), 1);
+}
+
+#[test]
+fn reorder_timeout_restarts_for_new_gap() {
+    let now = Cell::new(0u64);
+    let mut s = ReorderSpout::new(100, CollectSpout::new()).with_timeout(10, || now.get());
+
+    s.send(stamp(0, 2, 2)).unwrap();
+    now.set(8);
+    // Fills 0 and 1; gap at 3 opens now.
+    s.send(stamp(0, 4, 4)).unwrap();
+    s.send(stamp(0, 0, 0)).unwrap();
+    s.send(stamp(0, 1, 1)).unwrap();
+    assert_eq!(items(s.inner()), vec![0, 1, 2]);
+
+    now.set(12);
+    s.expire_gaps().unwrap();
+    assert_eq!(s.skipped(), 0);
+
+    now.set(18);
+    s.expire_gaps().unwrap();
+    assert_eq!(items(s.inner()), vec![0, 1, 2, 4]);
+    assert_eq!(s.skipped(), 1);
+}
+
+/// Collects items, failing every send while `down` is set.
+#[derive(Default)]
+struct Flaky {
+    down: bool,
+    items: Vec<Stamped<i32>>,
+}
+
+impl Spout<Stamped<i32>> for Flaky {
+    type Error = ();
+
+    fn send(&mut self, item: Stamped<i32>) -> Result<(), ()> {
+        if self.down {
+            return Err(());
+        }
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+#[test]
+fn reorder_keeps_item_rejected_by_sink() {
+    let now = Cell::new(0u64);
+    let mut s = ReorderSpout::new(1, Flaky::default()).with_timeout(10, || now.get());
+    s.send(stamp(0, 1, 1)).unwrap();
+
+    s.inner_mut().down = true;
+    // Releasing 0 fails; 0 stays buffered and the sequence does not move.
+    assert_eq!(s.send(stamp(0, 0, 0)), Err(()));
+    assert_eq!(s.expected(0), 0);
+    assert_eq!(s.buffered(), 2);
+
+    s.inner_mut().down = false;
+    s.send(stamp(0, 2, 2)).unwrap();
+    let got: Vec<i32> = s.inner().items.iter().map(|s| s.item).collect();
+    assert_eq!(got, vec![0, 1, 2]);
+    assert_eq!(s.skipped(), 0);
+    assert_eq!(s.buffered(), 0);
+
+    // No stale gap timer is left behind.
+    now.set(100);
+    s.expire_gaps().unwrap();
+    assert_eq!(s.skipped(), 0);
+}
+
+#[test]
+fn reorder_flush_releases_everything() {
+    let mut s = ReorderSpout::new(8, CollectSpout::new());
+    s.send(stamp(0, 5, 5)).unwrap();
+    s.send(stamp(0, 2, 2)).unwrap();
+    s.flush().unwrap();
+
+    assert_eq!(items(s.inner()), vec![2, 5]);
+    assert_eq!(s.skipped(), 4);
+    assert_eq!(s.buffered(), 0);
+}
+
+#[test]
+fn reorder_per_producer_orders_each_stream() {
+    let mut s = ReorderSpout::per_producer(8, CollectSpout::new());
+    s.send(stamp(1, 1, 11)).unwrap();
+    s.send(stamp(2, 0, 20)).unwrap();
+    s.send(stamp(1, 0, 10)).unwrap();
+    s.send(stamp(2, 1, 21)).unwrap();
+
+    assert_eq!(items(s.inner()), vec![20, 10, 11, 21]);
+    assert_eq!(s.expected(1), 2);
+    assert_eq!(s.expected(2), 2);
+    assert_eq!(s.expected(3), 0);
+}
+
+#[test]
+#[should_panic(expected = "ReorderSpout capacity must be at least 1")]
+fn reorder_rejects_zero_capacity() {
+    let _: ReorderSpout<i32, CollectSpout<Stamped<i32>>> =
+        ReorderSpout::new(0, CollectSpout::new());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sequenced_threads_reordered_into_global_order() {
+    use std::sync::mpsc;
+
+    use crate::ChannelSpout;
+
+    let (tx, rx) = mpsc::channel();
+    let base = Sequenced::new(0, ChannelSpout::new(tx));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mut s = base.clone();
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    s.send(i).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(base);
+    for h in handles {
+        h.join().unwrap();
+    }
+
+    let mut reorder = ReorderSpout::new(1000, CollectSpout::new());
+    for stamped in rx.iter() {
+        reorder.send(stamped).unwrap();
+    }
+    let seqs: Vec<u64> = reorder.inner().items().iter().map(|s| s.sequence).collect();
+    assert_eq!(seqs, (0..1000).collect::<Vec<_>>());
+    assert_eq!(reorder.skipped(), 0);
+}
@@ -0,0 +1,329 @@
+extern crate std;
+
+use core::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::time::Duration;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{Backoff, DisconnectedPolicy, ListenerSource, Source, Spout, TcpSpout};
+
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Splits a stream into newline-terminated lines, without the newline.
+type Decoded<T> = Result<Option<(usize, T)>, Infallible>;
+
+fn line(buf: &[u8]) -> Decoded<Vec<u8>> {
+    Ok(buf
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|i| (i + 1, buf[..i].to_vec())))
+}
+
+fn lines() -> fn(&[u8]) -> Decoded<Vec<u8>> {
+    line
+}
+
+fn tcp_source() -> (SocketAddr, ListenerSource<Vec<u8>, Infallible>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    (addr, ListenerSource::new(listener, lines).unwrap())
+}
+
+/// An address with nothing listening on it.
+fn closed_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+fn recv_n<E: core::fmt::Debug>(src: &mut ListenerSource<Vec<u8>, E>, n: usize) -> Vec<Vec<u8>> {
+    (0..n)
+        .map(|_| src.recv_timeout(WAIT).unwrap().expect("timed out"))
+        .collect()
+}
+
+fn fast_backoff() -> Backoff {
+    Backoff {
+        initial: Duration::from_millis(1),
+        max: Duration::from_millis(1),
+    }
+}
+
+#[test]
+fn tcp_spout_delivers_to_listener_source() {
+    let (addr, mut src) = tcp_source();
+    let mut s = TcpSpout::tcp(addr);
+    s.send(b"hello\n".to_vec()).unwrap();
+    s.send(b"world\n".to_vec()).unwrap();
+    assert_eq!(s.pending(), 2);
+    s.flush().unwrap();
+
+    assert!(s.is_connected());
+    assert_eq!(s.pending(), 0);
+    assert_eq!(
+        recv_n(&mut src, 2),
+        vec![b"hello".to_vec(), b"world".to_vec()]
+    );
+}
+
+#[test]
+fn tcp_spout_writes_at_threshold() {
+    let (addr, mut src) = tcp_source();
+    let mut s = TcpSpout::tcp(addr).with_write_threshold(4);
+    s.send(b"ab".to_vec()).unwrap();
+    assert_eq!(s.pending(), 1);
+    s.send(b"\n\n".to_vec()).unwrap();
+    assert_eq!(s.pending(), 0);
+    assert_eq!(recv_n(&mut src, 2), vec![b"ab".to_vec(), vec![]]);
+}
+
+#[test]
+fn tcp_spout_buffers_until_peer_appears() {
+    let addr = closed_addr();
+    let mut s = TcpSpout::tcp(addr)
+        .with_policy(DisconnectedPolicy::Buffer(2))
+        .with_backoff(fast_backoff());
+    for line in [b"a\n", b"b\n", b"c\n"] {
+        s.send(line.to_vec()).unwrap();
+    }
+    assert!(!s.is_connected());
+    assert!(s.connect_failures() >= 1);
+    assert_eq!(s.pending(), 2);
+    assert_eq!(s.dropped(), 1);
+
+    let mut src = ListenerSource::new(TcpListener::bind(addr).unwrap(), lines).unwrap();
+    std::thread::sleep(Duration::from_millis(5));
+    s.flush().unwrap();
+
+    assert!(s.is_connected());
+    assert_eq!(s.reconnects(), 0);
+    assert_eq!(recv_n(&mut src, 2), vec![b"a".to_vec(), b"b".to_vec()]);
+}
+
+#[test]
+fn tcp_spout_drop_policy_discards_while_disconnected() {
+    let mut s = TcpSpout::tcp(closed_addr())
+        .with_policy(DisconnectedPolicy::Drop)
+        .with_backoff(fast_backoff());
+    s.send_all((0..3).map(|_| b"x\n".to_vec())).unwrap();
+    s.flush().unwrap();
+
+    assert_eq!(s.pending(), 0);
+    assert_eq!(s.dropped(), 3);
+}
+
+#[test]
+fn tcp_spout_backs_off_between_attempts() {
+    let mut s = TcpSpout::tcp(closed_addr()).with_backoff(Backoff {
+        initial: Duration::from_secs(60),
+        max: Duration::from_secs(60),
+    });
+    for _ in 0..5 {
+        s.send(b"x\n".to_vec()).unwrap();
+    }
+    // Only the first send tries to connect; the rest wait out the backoff.
+    assert_eq!(s.connect_failures(), 1);
+    assert_eq!(s.pending(), 5);
+}
+
+#[test]
+#[should_panic(expected = "SocketSpout backoff initial must not exceed max")]
+fn tcp_spout_rejects_inverted_backoff() {
+    let _ = TcpSpout::tcp(closed_addr()).with_backoff(Backoff {
+        initial: Duration::from_secs(2),
+        max: Duration::from_secs(1),
+    });
+}
+
+#[test]
+fn listener_source_reports_decode_errors() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut src = ListenerSource::new(listener, || {
+        |buf: &[u8]| match buf.first() {
+            None => Ok(None),
+            Some(&b) if b.is_ascii_digit() => Ok(Some((1, b - b'0'))),
+            Some(&b) => Err(b),
+        }
+    })
+    .unwrap();
+
+    let mut s = TcpSpout::tcp(addr);
+    s.send(b"12x3".to_vec()).unwrap();
+    s.flush().unwrap();
+
+    assert_eq!(src.recv_timeout(WAIT), Ok(Some(1)));
+    assert_eq!(src.recv_timeout(WAIT), Ok(Some(2)));
+    assert_eq!(src.recv_timeout(WAIT), Err(b'x'));
+}
+
+#[test]
+fn listener_source_merges_connections() {
+    let (addr, mut src) = tcp_source();
+    let mut a = TcpSpout::tcp(addr);
+    let mut b = TcpSpout::tcp(addr);
+    a.send(b"a1\na2\n".to_vec()).unwrap();
+    b.send(b"b1\n".to_vec()).unwrap();
+    a.flush().unwrap();
+    b.flush().unwrap();
+
+    let mut got = recv_n(&mut src, 3);
+    got.sort();
+    assert_eq!(got, vec![b"a1".to_vec(), b"a2".to_vec(), b"b1".to_vec()]);
+    assert_eq!(src.try_recv(), Ok(None));
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+
+    use crate::UnixStreamSpout;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(std::format!("spout-{}-{name}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn unix_spout_delivers_to_listener_source() {
+        let path = socket_path("deliver");
+        let mut src = ListenerSource::new(UnixListener::bind(&path).unwrap(), lines).unwrap();
+
+        let mut s = UnixStreamSpout::unix(&path);
+        s.send_all([b"one\n".to_vec(), b"two\n".to_vec()].into_iter())
+            .unwrap();
+        s.flush().unwrap();
+
+        assert_eq!(recv_n(&mut src, 2), vec![b"one".to_vec(), b"two".to_vec()]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn unix_spout_reconnects_after_peer_restarts() {
+        let path = socket_path("restart");
+        let mut src = ListenerSource::new(UnixListener::bind(&path).unwrap(), lines).unwrap();
+
+        let mut s = UnixStreamSpout::unix(&path)
+            .with_write_threshold(0)
+            .with_backoff(fast_backoff());
+        s.send(b"before\n".to_vec()).unwrap();
+        assert_eq!(recv_n(&mut src, 1), vec![b"before".to_vec()]);
+
+        // Take the peer away; writes start failing once the close is seen.
+        src.shutdown();
+        drop(src);
+        std::fs::remove_file(&path).unwrap();
+        let mut sent = 0;
+        while s.is_connected() {
+            assert!(sent < 1000, "write never failed after peer went away");
+            s.send(b"lost?\n".to_vec()).unwrap();
+            sent += 1;
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut src = ListenerSource::new(UnixListener::bind(&path).unwrap(), lines).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        s.send(b"after\n".to_vec()).unwrap();
+        s.flush().unwrap();
+
+        assert!(s.is_connected());
+        assert_eq!(s.reconnects(), 1);
+        // Items kept from the failed write are delivered first.
+        let mut got = Vec::new();
+        while let Ok(Some(line)) = src.recv_timeout(WAIT) {
+            let done = line == b"after";
+            got.push(line);
+            if done {
+                break;
+            }
+        }
+        assert_eq!(got.last().map(Vec::as_slice), Some(&b"after"[..]));
+        assert!(got[..got.len() - 1].iter().all(|l| l == b"lost?"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[cfg(feature = "bytecast")]
+mod frames {
+    use super::*;
+
+    use crate::{FrameError, FrameStreamDecoder, FramedSpoutV2, frame_v2_len};
+
+    #[test]
+    fn frame_v2_len_waits_for_whole_frame() {
+        let mut s = FramedSpoutV2::new(1, crate::CollectSpout::new());
+        s.send(7u32).unwrap();
+        let frame = s.into_inner().into_items().remove(0);
+
+        for cut in 0..frame.len() {
+            assert_eq!(frame_v2_len(&frame[..cut]), Ok(None));
+        }
+        assert_eq!(frame_v2_len(&frame), Ok(Some(frame.len())));
+        assert_eq!(frame_v2_len(&[0, 0, 0]), Err(FrameError::BadMagic(0)));
+    }
+
+    #[test]
+    fn oversized_frame_is_rejected_from_its_header() {
+        use crate::StreamDecoder;
+        use std::io::Write;
+
+        let mut s = FramedSpoutV2::new(1, crate::CollectSpout::new());
+        s.send(vec![0u8; 1000]).unwrap();
+        let frame = s.into_inner().into_items().remove(0);
+
+        // Rejected before the payload arrives.
+        let mut decoder = FrameStreamDecoder::<Vec<u8>>::new().with_max_frame_len(64);
+        let err = decoder.decode(&frame[..40]).unwrap_err();
+        assert_eq!(
+            err,
+            FrameError::TooLarge {
+                len: frame.len(),
+                max: 64
+            }
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut src = ListenerSource::new(listener, || {
+            FrameStreamDecoder::<Vec<u8>>::new().with_max_frame_len(64)
+        })
+        .unwrap();
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        stream.write_all(&frame).unwrap();
+        assert!(matches!(
+            src.recv_timeout(WAIT),
+            Err(FrameError::TooLarge { max: 64, .. })
+        ));
+    }
+
+    #[test]
+    fn framed_stream_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut src = ListenerSource::new(listener, FrameStreamDecoder::<u32>::new).unwrap();
+
+        let mut s = FramedSpoutV2::new(3, TcpSpout::tcp(addr));
+        s.send_all(10u32..15).unwrap();
+        Spout::<u32>::flush(&mut s).unwrap();
+
+        let frames: Vec<_> = (0..5)
+            .map(|_| src.recv_timeout(WAIT).unwrap().expect("timed out"))
+            .collect();
+        assert!(frames.iter().all(|f| f.producer_id == 3));
+        assert_eq!(
+            frames.iter().map(|f| f.sequence).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            frames.into_iter().map(|f| f.item).collect::<Vec<_>>(),
+            vec![10, 11, 12, 13, 14]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+extern crate std;
+
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, IterSource, PumpError, Source, Spout, pump, pump_until};
+
+/// Counts `send_all` and `flush` calls.
+#[derive(Default)]
+struct BatchTracker {
+    items: Vec<i32>,
+    batches: Vec<usize>,
+    flushes: usize,
+}
+
+impl Spout<i32> for BatchTracker {
+    type Error = core::convert::Infallible;
+
+    fn send(&mut self, item: i32) -> Result<(), Self::Error> {
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn send_all(&mut self, items: impl Iterator<Item = i32>) -> Result<(), Self::Error> {
+        let before = self.items.len();
+        self.items.extend(items);
+        self.batches.push(self.items.len() - before);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn iter_source_yields_then_exhausts() {
+    let mut s = IterSource([1, 2].into_iter());
+    assert_eq!(s.recv(), Ok(Some(1)));
+    assert_eq!(s.try_recv(), Ok(Some(2)));
+    assert_eq!(s.recv(), Ok(None));
+}
+
+#[test]
+fn pump_batches_through_send_all() {
+    let mut sink = BatchTracker::default();
+    let moved = pump(&mut IterSource(0..10), &mut sink, 4).unwrap();
+
+    assert_eq!(moved, 10);
+    assert_eq!(sink.items, (0..10).collect::<Vec<_>>());
+    assert_eq!(sink.batches, vec![4, 4, 2]);
+    assert_eq!(sink.flushes, 1);
+}
+
+#[test]
+fn pump_empty_source_still_flushes() {
+    let mut sink = BatchTracker::default();
+    let moved = pump(&mut IterSource(core::iter::empty()), &mut sink, 8).unwrap();
+
+    assert_eq!(moved, 0);
+    assert!(sink.batches.is_empty());
+    assert_eq!(sink.flushes, 1);
+}
+
+#[test]
+fn pump_until_stops_after_matching_item() {
+    let mut source = IterSource(0..100);
+    let mut sink = BatchTracker::default();
+    let moved = pump_until(&mut source, &mut sink, 4, |&x| x == 5).unwrap();
+
+    assert_eq!(moved, 6);
+    assert_eq!(sink.items, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(sink.batches, vec![4, 2]);
+    assert_eq!(sink.flushes, 1);
+    // Remaining items are left in the source
+    assert_eq!(source.recv(), Ok(Some(6)));
+}
+
+#[test]
+fn pump_propagates_source_error() {
+    struct Failing(u32);
+    impl Source<i32> for Failing {
+        type Error = &'static str;
+        fn recv(&mut self) -> Result<Option<i32>, Self::Error> {
+            self.0 += 1;
+            if self.0 > 2 {
+                Err("broken")
+            } else {
+                Ok(Some(1))
+            }
+        }
+    }
+
+    let mut sink = CollectSpout::new();
+    let result = pump(&mut Failing(0), &mut sink, 8);
+    assert_eq!(result, Err(PumpError::Source("broken")));
+}
+
+#[test]
+fn pump_propagates_spout_error() {
+    struct Rejecting;
+    impl Spout<i32> for Rejecting {
+        type Error = &'static str;
+        fn send(&mut self, _item: i32) -> Result<(), Self::Error> {
+            Err("rejected")
+        }
+    }
+
+    let result = pump(&mut IterSource(0..3), &mut Rejecting, 2);
+    assert_eq!(result, Err(PumpError::Spout("rejected")));
+}
+
+#[test]
+#[should_panic(expected = "pump batch size must be at least 1")]
+fn pump_rejects_zero_batch() {
+    let _ = pump(&mut IterSource(0..1), &mut CollectSpout::new(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn pump_from_std_channel_until_disconnect() {
+    use std::sync::mpsc;
+    use std::thread;
+
+    let (tx, mut rx) = mpsc::channel();
+    let producer = thread::spawn(move || {
+        for i in 0..50 {
+            tx.send(i).unwrap();
+        }
+    });
+
+    let mut sink = CollectSpout::new();
+    let moved = pump(&mut rx, &mut sink, 16).unwrap();
+    producer.join().unwrap();
+
+    assert_eq!(moved, 50);
+    assert_eq!(sink.into_items(), (0..50).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn pump_from_bounded_receiver() {
+    use crate::{OverflowPolicy, bounded};
+    use std::thread;
+
+    let (mut tx, mut rx) = bounded(4, OverflowPolicy::Block);
+    let producer = thread::spawn(move || tx.send_all(0..100).unwrap());
+
+    let mut sink = CollectSpout::new();
+    let moved = pump(&mut rx, &mut sink, 8).unwrap();
+    producer.join().unwrap();
+
+    assert_eq!(moved, 100);
+    assert_eq!(sink.into_items(), (0..100).collect::<Vec<_>>());
+}
@@ -0,0 +1,250 @@
+extern crate std;
+
+use std::io::{self, Write};
+use std::string::String;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{Spout, TextSpoutError};
+
+/// Records bytes and counts flushes; optionally fails every write.
+#[derive(Default)]
+struct Recorder {
+    bytes: Vec<u8>,
+    flushes: usize,
+    fail: bool,
+}
+
+impl Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.fail {
+            return Err(io::Error::other("disk full"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Event {
+    id: u32,
+    message: String,
+    tag: Option<&'static str>,
+    values: Vec<u8>,
+}
+
+fn event(id: u32, message: &str) -> Event {
+    Event {
+        id,
+        message: message.into(),
+        tag: None,
+        values: vec![],
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+
+    use crate::JsonLinesSpout;
+
+    #[test]
+    fn json_lines_one_record_per_line() {
+        let mut s = JsonLinesSpout::new(Vec::new());
+        s.send(event(1, "hello")).unwrap();
+        s.send(event(2, "world")).unwrap();
+
+        let out = String::from_utf8(s.into_inner()).unwrap();
+        assert_eq!(
+            out,
+            "{\"id\":1,\"message\":\"hello\",\"tag\":null,\"values\":[]}\n\
+             {\"id\":2,\"message\":\"world\",\"tag\":null,\"values\":[]}\n"
+        );
+    }
+
+    #[test]
+    fn json_lines_escapes_control_characters() {
+        let mut s = JsonLinesSpout::new(Vec::new());
+        s.send("quote \" backslash \\ newline \n tab \t bell \u{7} é")
+            .unwrap();
+
+        let out = String::from_utf8(s.into_inner()).unwrap();
+        assert_eq!(out.lines().count(), 1);
+        assert_eq!(
+            out,
+            "\"quote \\\" backslash \\\\ newline \\n tab \\t bell \\u0007 é\"\n"
+        );
+        let decoded: String = serde_json::from_str(out.trim_end()).unwrap();
+        assert_eq!(
+            decoded,
+            "quote \" backslash \\ newline \n tab \t bell \u{7} é"
+        );
+    }
+
+    #[test]
+    fn json_lines_flush_reaches_writer() {
+        let mut s = JsonLinesSpout::new(Recorder::default());
+        s.send(1).unwrap();
+        assert_eq!(s.inner().flushes, 0);
+        Spout::<i32>::flush(&mut s).unwrap();
+        assert_eq!(s.inner().flushes, 1);
+    }
+
+    #[test]
+    fn json_lines_encode_error_writes_nothing() {
+        #[derive(serde::Serialize)]
+        struct Bad {
+            id: u32,
+            // JSON object keys must be strings.
+            map: std::collections::BTreeMap<Vec<u8>, u32>,
+        }
+
+        let mut s = JsonLinesSpout::new(Vec::new());
+        s.send(event(1, "before")).unwrap();
+        let bad = Bad {
+            id: 2,
+            map: [(vec![1], 1)].into_iter().collect(),
+        };
+        assert!(matches!(s.send(bad), Err(TextSpoutError::Encode(_))));
+        s.send(event(3, "after")).unwrap();
+
+        let out = String::from_utf8(s.into_inner()).unwrap();
+        let ids: Vec<u64> = out
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["id"].as_u64().unwrap()
+            })
+            .collect();
+        assert_eq!(ids, [1, 3]);
+    }
+
+    #[test]
+    fn json_lines_reports_io_errors() {
+        let mut s = JsonLinesSpout::new(Recorder {
+            fail: true,
+            ..Recorder::default()
+        });
+        assert!(matches!(s.send(1), Err(TextSpoutError::Io(_))));
+    }
+}
+
+#[cfg(feature = "csv")]
+mod csv {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    use crate::CsvSpout;
+
+    fn output(s: CsvSpout<Vec<u8>>) -> String {
+        String::from_utf8(s.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn csv_writes_header_then_rows() {
+        let mut s = CsvSpout::new(Vec::new());
+        s.send(event(1, "a")).unwrap();
+        s.send(Event {
+            id: 2,
+            message: "b".into(),
+            tag: Some("x"),
+            values: vec![1, 2],
+        })
+        .unwrap();
+
+        assert_eq!(
+            s.header().unwrap(),
+            &["id", "message", "tag", "values"].map(String::from)
+        );
+        assert_eq!(
+            output(s),
+            "id,message,tag,values\n1,a,,[]\n2,b,x,\"[1,2]\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_special_cells() {
+        let mut s = CsvSpout::new(Vec::new());
+        for msg in ["plain", "a,b", "say \"hi\"", "two\nlines", "cr\rhere", ""] {
+            s.send(event(0, msg)).unwrap();
+        }
+
+        assert_eq!(
+            output(s),
+            "id,message,tag,values\n\
+             0,plain,,[]\n\
+             0,\"a,b\",,[]\n\
+             0,\"say \"\"hi\"\"\",,[]\n\
+             0,\"two\nlines\",,[]\n\
+             0,\"cr\rhere\",,[]\n\
+             0,,,[]\n"
+        );
+    }
+
+    #[test]
+    fn csv_custom_delimiter_changes_quoting() {
+        let mut s = CsvSpout::new(Vec::new()).with_delimiter(b';');
+        s.send(event(1, "a,b")).unwrap();
+        s.send(event(2, "a;b")).unwrap();
+
+        assert_eq!(
+            output(s),
+            "id;message;tag;values\n1;a,b;;[]\n2;\"a;b\";;[]\n"
+        );
+    }
+
+    #[test]
+    fn csv_without_header() {
+        let mut s = CsvSpout::new(Vec::new()).without_header();
+        s.send(event(1, "a")).unwrap();
+        assert_eq!(output(s), "1,a,,[]\n");
+    }
+
+    #[test]
+    fn csv_accepts_maps() {
+        let mut s = CsvSpout::new(Vec::new());
+        let row: BTreeMap<&str, f64> = [("x", 1.5), ("y", -2.0)].into_iter().collect();
+        s.send(row).unwrap();
+        assert_eq!(output(s), "x,y\n1.5,-2.0\n");
+    }
+
+    #[test]
+    fn csv_rejects_mismatched_fields() {
+        let mut s = CsvSpout::new(Vec::new());
+        s.send(BTreeMap::from([("a", 1)])).unwrap();
+        assert!(matches!(
+            s.send(BTreeMap::from([("b", 1)])),
+            Err(TextSpoutError::Encode(_))
+        ));
+        // The rejected row is not written.
+        assert_eq!(output(s), "a\n1\n");
+    }
+
+    #[test]
+    fn csv_rejects_non_struct_items() {
+        let mut s = CsvSpout::new(Vec::new());
+        assert!(matches!(s.send(42), Err(TextSpoutError::Encode(_))));
+        assert!(matches!(s.send(vec![1, 2]), Err(TextSpoutError::Encode(_))));
+        assert!(s.header().is_none());
+    }
+
+    #[test]
+    fn csv_flush_reaches_writer() {
+        let mut s = CsvSpout::new(Recorder::default());
+        s.send(event(1, "a")).unwrap();
+        Spout::<Event>::flush(&mut s).unwrap();
+        assert_eq!(s.inner().flushes, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "CsvSpout delimiter must not be a quote or line break")]
+    fn csv_rejects_quote_delimiter() {
+        let _ = CsvSpout::new(Vec::<u8>::new()).with_delimiter(b'"');
+    }
+}
@@ -0,0 +1,255 @@
+extern crate std;
+
+use core::cell::Cell;
+use std::vec;
+use std::vec::Vec;
+
+use crate::{CollectSpout, Count, Fold, Max, Min, Spout, Sum, Window, WindowSpec, WindowSpout};
+
+fn w(start: u64, end: u64) -> Window {
+    Window { start, end }
+}
+
+#[test]
+fn count_tumbling_per_key() {
+    let mut s = WindowSpout::count(
+        WindowSpec::tumbling(2),
+        |&(k, _): &(char, i32)| k,
+        Sum(|&(_, v): &(char, i32)| v),
+        CollectSpout::new(),
+    );
+
+    s.send(('a', 1)).unwrap();
+    s.send(('b', 10)).unwrap();
+    s.send(('a', 2)).unwrap(); // Closes a[0..2]
+    s.send(('a', 3)).unwrap();
+    s.send(('b', 20)).unwrap(); // Closes b[0..2]
+
+    assert_eq!(
+        s.inner().items(),
+        vec![('a', w(0, 2), 3), ('b', w(0, 2), 30)]
+    );
+    assert_eq!(s.open_windows(), 1);
+
+    s.flush().unwrap();
+    assert_eq!(s.inner().items()[2], ('a', w(2, 4), 3));
+    assert_eq!(s.open_windows(), 0);
+}
+
+#[test]
+fn count_sliding_overlaps() {
+    let mut s = WindowSpout::count(
+        WindowSpec::sliding(3, 1),
+        |_: &i32| (),
+        Sum(|&v: &i32| v),
+        CollectSpout::new(),
+    );
+    s.send_all(1..=5).unwrap();
+
+    let sums: Vec<_> = s.inner().items().iter().map(|&(_, w, v)| (w, v)).collect();
+    assert_eq!(sums, vec![(w(0, 3), 6), (w(1, 4), 9), (w(2, 5), 12)]);
+}
+
+#[test]
+fn time_tumbling_closes_on_clock() {
+    let now = Cell::new(0u64);
+    let mut s = WindowSpout::time(
+        WindowSpec::tumbling(10),
+        || now.get(),
+        |&(k, _): &(u32, u64)| k,
+        Sum(|&(_, bytes): &(u32, u64)| bytes),
+        CollectSpout::new(),
+    );
+
+    now.set(1);
+    s.send((1, 100)).unwrap();
+    now.set(5);
+    s.send((2, 50)).unwrap();
+    s.send((1, 10)).unwrap();
+    assert!(s.inner().items().is_empty());
+
+    now.set(12);
+    s.send((1, 7)).unwrap(); // Closes [0, 10) for both keys
+
+    assert_eq!(
+        s.inner().items(),
+        vec![(1, w(0, 10), 110), (2, w(0, 10), 50)]
+    );
+
+    now.set(20);
+    s.close_expired().unwrap();
+    assert_eq!(s.inner().items()[2], (1, w(10, 20), 7));
+    assert_eq!(s.open_windows(), 0);
+}
+
+/// Collects items, failing every send while `down` is set.
+struct Flaky<T> {
+    down: bool,
+    items: Vec<T>,
+}
+
+impl<T> Spout<T> for Flaky<T> {
+    type Error = ();
+
+    fn send(&mut self, item: T) -> Result<(), ()> {
+        if self.down {
+            return Err(());
+        }
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+#[test]
+fn failed_sink_keeps_closed_windows_and_incoming_item() {
+    let now = Cell::new(0u64);
+    let mut s = WindowSpout::time(
+        WindowSpec::tumbling(10),
+        || now.get(),
+        |&k: &u32| k,
+        Count,
+        Flaky {
+            down: false,
+            items: Vec::new(),
+        },
+    );
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+    s.send(1).unwrap();
+
+    s.inner_mut().down = true;
+    now.set(12);
+    // Closes [0, 10) for both keys; the sink rejects them.
+    assert_eq!(s.send(1), Err(()));
+    assert_eq!(s.open_windows(), 1);
+
+    s.inner_mut().down = false;
+    s.flush().unwrap();
+    assert_eq!(
+        s.inner().items,
+        vec![(1, w(0, 10), 2), (2, w(0, 10), 1), (1, w(10, 20), 1)]
+    );
+}
+
+#[test]
+fn count_window_retried_after_sink_failure() {
+    let mut s = WindowSpout::count(
+        WindowSpec::tumbling(2),
+        |_: &u64| 0u8,
+        Sum(|&x: &u64| x),
+        Flaky {
+            down: true,
+            items: Vec::new(),
+        },
+    );
+    s.send(1).unwrap();
+    s.send(2).unwrap_err();
+    // The rejected window is retried, and rejected again.
+    s.send(3).unwrap_err();
+    s.send(4).unwrap_err();
+
+    s.inner_mut().down = false;
+    s.flush().unwrap();
+    assert_eq!(s.inner().items, vec![(0, w(0, 2), 3), (0, w(2, 4), 7)]);
+}
+
+#[test]
+fn time_sliding_counts_each_window() {
+    let now = Cell::new(0u64);
+    let mut s = WindowSpout::time(
+        WindowSpec::sliding(10, 5),
+        || now.get(),
+        |_: &()| (),
+        Count,
+        CollectSpout::new(),
+    );
+
+    for t in [0, 3, 6, 8, 12] {
+        now.set(t);
+        s.send(()).unwrap();
+    }
+    s.flush().unwrap();
+
+    let counts: Vec<_> = s.inner().items().iter().map(|&(_, w, c)| (w, c)).collect();
+    // [0,10): 0,3,6,8  [5,15): 6,8,12  [10,20): 12
+    assert_eq!(counts, vec![(w(0, 10), 4), (w(5, 15), 3), (w(10, 20), 1)]);
+}
+
+#[test]
+fn time_window_counts_late_items() {
+    let now = Cell::new(0u64);
+    let mut s = WindowSpout::time(
+        WindowSpec::tumbling(10),
+        || now.get(),
+        |_: &i32| (),
+        Count,
+        CollectSpout::new(),
+    );
+
+    now.set(15);
+    s.send(1).unwrap();
+    s.close_expired().unwrap();
+    now.set(25);
+    s.close_expired().unwrap(); // Closes [10, 20)
+
+    // Clock stepped backward: [10, 20) already emitted
+    now.set(18);
+    s.send(2).unwrap();
+    assert_eq!(s.late(), 1);
+    assert_eq!(s.inner().items(), vec![((), w(10, 20), 1)]);
+}
+
+#[test]
+fn hopping_windows_skip_gaps() {
+    let mut s = WindowSpout::count(
+        WindowSpec::sliding(2, 4),
+        |_: &i32| (),
+        Sum(|&v: &i32| v),
+        CollectSpout::new(),
+    );
+    s.send_all(0..8).unwrap();
+    s.flush().unwrap();
+
+    // Positions 0,1 and 4,5 are covered; 2,3 and 6,7 fall in gaps
+    let sums: Vec<_> = s.inner().items().iter().map(|&(_, w, v)| (w, v)).collect();
+    assert_eq!(sums, vec![(w(0, 2), 1), (w(4, 6), 9)]);
+}
+
+#[test]
+fn min_max_fold_aggregators() {
+    let items = [5, 3, 9, 1];
+
+    let mut min = WindowSpout::count(
+        WindowSpec::tumbling(4),
+        |_: &i32| (),
+        Min(|&v: &i32| v),
+        CollectSpout::new(),
+    );
+    let mut max = WindowSpout::count(
+        WindowSpec::tumbling(4),
+        |_: &i32| (),
+        Max(|&v: &i32| v),
+        CollectSpout::new(),
+    );
+    let mut fold = WindowSpout::count(
+        WindowSpec::tumbling(4),
+        |_: &i32| (),
+        Fold::new(Vec::new(), |acc: &mut Vec<i32>, &v: &i32| acc.push(v)),
+        CollectSpout::new(),
+    );
+    for v in items {
+        min.send(v).unwrap();
+        max.send(v).unwrap();
+        fold.send(v).unwrap();
+    }
+
+    assert_eq!(min.inner().items()[0].2, 1);
+    assert_eq!(max.inner().items()[0].2, 9);
+    assert_eq!(fold.inner().items()[0].2, vec![5, 3, 9, 1]);
+}
+
+#[test]
+#[should_panic(expected = "window slide must be at least 1")]
+fn window_spec_rejects_zero_slide() {
+    let _ = WindowSpec::sliding(10, 0);
+}
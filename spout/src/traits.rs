@@ -1,3 +1,8 @@
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
 /// Consumes items.
 pub trait Spout<T> {
     /// The error type returned by fallible operations.
@@ -25,6 +30,160 @@ pub trait Spout<T> {
     }
 }
 
+/// Consumes items asynchronously, with backpressure.
+///
+/// The asynchronous counterpart to [`Spout`]. A caller waits for
+/// [`poll_ready`](Self::poll_ready) to return `Ready(Ok(()))`, then hands
+/// over exactly one item with [`send`](Self::send). Implementations that
+/// buffer must not make progress visible downstream until
+/// [`poll_flush`](Self::poll_flush) completes.
+///
+/// Only `core::task` is used, so any executor can drive it. For `async`
+/// code, [`send_async`](Self::send_async) and
+/// [`flush_async`](Self::flush_async) wrap the poll methods in futures.
+pub trait AsyncSpout<T> {
+    /// The error type returned by fallible operations.
+    type Error;
+
+    /// Wait until the spout can accept an item.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Hand over one item.
+    ///
+    /// Must be preceded by `poll_ready` returning `Ready(Ok(()))`.
+    /// Implementations may panic or return an error otherwise.
+    fn send(&mut self, item: T) -> Result<(), Self::Error>;
+
+    /// Wait until every accepted item has been delivered.
+    #[inline]
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let _ = cx;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Wait for readiness, then send `item`.
+    #[inline]
+    fn send_async(&mut self, item: T) -> SendFuture<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        SendFuture {
+            spout: self,
+            item: Some(item),
+        }
+    }
+
+    /// Wait for `poll_flush` to complete.
+    #[inline]
+    fn flush_async(&mut self) -> FlushFuture<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        FlushFuture {
+            spout: self,
+            _item: PhantomData,
+        }
+    }
+}
+
+/// Future returned by [`AsyncSpout::send_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SendFuture<'a, S, T> {
+    spout: &'a mut S,
+    item: Option<T>,
+}
+
+// Neither field is structurally pinned.
+impl<S, T> Unpin for SendFuture<'_, S, T> {}
+
+impl<S: AsyncSpout<T>, T> Future for SendFuture<'_, S, T> {
+    type Output = Result<(), S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match this.spout.poll_ready(cx) {
+            Poll::Ready(Ok(())) => {
+                let item = this
+                    .item
+                    .take()
+                    .expect("SendFuture polled after completion");
+                Poll::Ready(this.spout.send(item))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Future returned by [`AsyncSpout::flush_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct FlushFuture<'a, S, T> {
+    spout: &'a mut S,
+    _item: PhantomData<fn(T)>,
+}
+
+impl<S: AsyncSpout<T>, T> Future for FlushFuture<'_, S, T> {
+    type Output = Result<(), S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().spout.poll_flush(cx)
+    }
+}
+
+/// Produces items. The pull-side counterpart to [`Spout`].
+///
+/// `Ok(None)` from [`recv`](Self::recv) means the source is exhausted and
+/// will not produce more items. `Ok(None)` from
+/// [`try_recv`](Self::try_recv) only means nothing is available right now.
+///
+/// Use [`pump`](crate::pump) to move items from a source into a spout.
+pub trait Source<T> {
+    /// The error type returned by fallible operations.
+    type Error;
+
+    /// Produce the next item, blocking if the source supports it.
+    fn recv(&mut self) -> Result<Option<T>, Self::Error>;
+
+    /// Produce the next item without blocking.
+    ///
+    /// Default implementation calls `recv`, which is correct for sources
+    /// that never block.
+    #[inline]
+    fn try_recv(&mut self) -> Result<Option<T>, Self::Error> {
+        self.recv()
+    }
+}
+
+/// Splits a byte stream into items.
+///
+/// Given everything received so far that has not been consumed, returns
+/// the next item and how many bytes it used, or `Ok(None)` if more bytes
+/// are needed. Implemented for closures with the same signature.
+pub trait StreamDecoder {
+    /// The decoded item type.
+    type Item;
+    /// The error type for malformed input.
+    type Error;
+
+    /// Decode the next item from the front of `buf`.
+    fn decode(&mut self, buf: &[u8]) -> Result<Option<(usize, Self::Item)>, Self::Error>;
+}
+
+impl<I, E, F> StreamDecoder for F
+where
+    F: FnMut(&[u8]) -> Result<Option<(usize, I)>, E>,
+{
+    type Item = I;
+    type Error = E;
+
+    #[inline]
+    fn decode(&mut self, buf: &[u8]) -> Result<Option<(usize, I)>, E> {
+        self(buf)
+    }
+}
+
 /// Flush behavior.
 pub trait Flush {
     /// Perform flush.
@@ -42,3 +201,20 @@ impl<F: FnMut()> Flush for F {
         self()
     }
 }
+
+/// Source of monotonic ticks for timestamping and time-based policies.
+///
+/// The unit is up to the caller (nanoseconds, milliseconds, logical ticks).
+/// Implemented for any `Fn() -> u64`, so a closure over a counter works as a
+/// manual clock in tests.
+pub trait Clock {
+    /// Current tick.
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    #[inline]
+    fn now(&self) -> u64 {
+        self()
+    }
+}
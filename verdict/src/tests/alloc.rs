@@ -1431,6 +1431,46 @@ mod serde_tests {
         assert_eq!(decoded.retryable, record.retryable);
         assert_eq!(decoded.frames.len(), record.frames.len());
     }
+
+    #[test]
+    fn test_log_record_json_lines_spout() {
+        let err = Context::new(TestError::temporary("timeout"))
+            .with_ctx("connecting to db")
+            .with_ctx("handling \"request\"\nline two");
+
+        let mut sink = spout::JsonLinesSpout::new(alloc::vec::Vec::new());
+        sink.send(LogRecord::from(&err)).unwrap();
+        sink.send(LogRecord::from(&err)).unwrap();
+        Spout::<LogRecord>::flush(&mut sink).unwrap();
+
+        let out = alloc::string::String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(out.lines().count(), 2);
+        for line in out.lines() {
+            let decoded: LogRecord = serde_json::from_str(line).unwrap();
+            assert_eq!(decoded.error, "timeout");
+            assert_eq!(decoded.frames[1].message, "handling \"request\"\nline two");
+        }
+    }
+
+    #[test]
+    fn test_log_record_csv_spout() {
+        let err = Context::new(TestError::permanent("not found")).with_ctx("lookup");
+
+        let mut sink = spout::CsvSpout::new(alloc::vec::Vec::new());
+        sink.send(LogRecord::from(&err)).unwrap();
+        Spout::<LogRecord>::flush(&mut sink).unwrap();
+
+        let out = alloc::string::String::from_utf8(sink.into_inner()).unwrap();
+        let mut lines = out.lines();
+        assert_eq!(
+            lines.next(),
+            Some("error,status,retryable,frames,overflow_count")
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("not found,permanent,false,\"[{\"\"message\"\":\"\"lookup\"\""));
+        assert!(row.ends_with("}]\",0"));
+        assert_eq!(lines.next(), None);
+    }
 }
 
 // LogRecord tests (facet)